@@ -1,14 +1,28 @@
 //! Cache for exchange rates.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+/// Hit and miss counts for an [`ExchangeRateCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache.
+    pub hits: u64,
+    /// Lookups that found no rate, or only an expired one.
+    pub misses: u64,
+    /// Rates currently cached and not yet expired.
+    pub entries: usize,
+}
+
 /// Cache for exchange rates with time-to-live (TTL).
 #[derive(Debug, Clone)]
 pub struct ExchangeRateCache {
     cache: Arc<Mutex<HashMap<String, (f64, Instant)>>>,
     ttl: Duration,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
 }
 
 impl ExchangeRateCache {
@@ -17,6 +31,8 @@ impl ExchangeRateCache {
         Self {
             cache: Arc::new(Mutex::new(HashMap::new())),
             ttl,
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -30,13 +46,22 @@ impl ExchangeRateCache {
         let key = Self::make_key(from, to);
         let cache = self.cache.lock().unwrap();
 
-        cache.get(&key).and_then(|(rate, timestamp)| {
+        let rate = cache.get(&key).and_then(|(rate, timestamp)| {
             if timestamp.elapsed() < self.ttl {
                 Some(*rate)
             } else {
                 None
             }
-        })
+        });
+
+        let counter = if rate.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        rate
     }
 
     /// Puts a rate into the cache.
@@ -66,6 +91,27 @@ impl ExchangeRateCache {
         cache.clear();
     }
 
+    /// Returns the hit and miss counts since creation or the last
+    /// [`reset_stats`](Self::reset_stats), and the number of live entries.
+    pub fn stats(&self) -> CacheStats {
+        let cache = self.cache.lock().unwrap();
+
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: cache
+                .values()
+                .filter(|(_, timestamp)| timestamp.elapsed() < self.ttl)
+                .count(),
+        }
+    }
+
+    /// Resets the hit and miss counts to zero. Cached rates are kept.
+    pub fn reset_stats(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
     /// Creates a cache key from currency codes.
     fn make_key(from: &str, to: &str) -> String {
         format!("{}:{}", from.to_uppercase(), to.to_uppercase())
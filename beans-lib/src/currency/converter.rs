@@ -1,12 +1,88 @@
 //! Currency conversion using external API.
 
-use crate::currency::ExchangeRateCache;
+use crate::currency::{CacheStats, ExchangeRateCache};
 use crate::error::{BeansError, BeansResult};
 use crate::models::Currency;
 use rust_decimal::Decimal;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::time::Duration;
+use tokio::task::JoinSet;
+
+/// How often, and how patiently, rate fetches are retried.
+///
+/// Only transient failures are retried: timeouts, connection errors and
+/// server errors (5xx or 429). Errors such as an unknown currency fail
+/// immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry. Each further retry waits twice as long.
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay: Duration::ZERO,
+        }
+    }
+
+    /// Returns the delay before the retry that follows the given attempt (1-based).
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+        }
+    }
+}
+
+/// A failed fetch attempt, and whether trying again might succeed.
+struct FetchFailure {
+    error: BeansError,
+    retryable: bool,
+}
+
+impl FetchFailure {
+    fn permanent(error: BeansError) -> Self {
+        Self {
+            error,
+            retryable: false,
+        }
+    }
+
+    fn network(error: reqwest::Error) -> Self {
+        let retryable = error.is_timeout()
+            || error.is_connect()
+            || error.status().is_some_and(is_retryable_status);
+        Self {
+            error: BeansError::Network(error),
+            retryable,
+        }
+    }
+
+    fn status(status: reqwest::StatusCode) -> Self {
+        Self {
+            error: BeansError::Other(format!("API request failed with status: {}", status)),
+            retryable: is_retryable_status(status),
+        }
+    }
+}
+
+/// Returns true for HTTP statuses that indicate a transient problem.
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
 
 /// Converts between currencies using exchange rates.
 #[derive(Debug, Clone)]
@@ -15,6 +91,7 @@ pub struct CurrencyConverter {
     base_url: String,
     fallback_url: Option<String>,
     client: reqwest::Client,
+    retry_policy: RetryPolicy,
 }
 
 impl CurrencyConverter {
@@ -26,6 +103,7 @@ impl CurrencyConverter {
                 .to_string(),
             fallback_url: None,
             client: reqwest::Client::new(),
+            retry_policy: RetryPolicy::default(),
         }
     }
 
@@ -48,6 +126,21 @@ impl CurrencyConverter {
         self.fallback_url = Some(url);
     }
 
+    /// Sets the retry policy for rate fetches.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    /// Returns hit and miss counts for the converter's rate cache.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Resets the rate cache's hit and miss counts.
+    pub fn reset_cache_stats(&self) {
+        self.cache.reset_stats();
+    }
+
     /// Gets the exchange rate between two currencies.
     pub async fn get_exchange_rate<'a>(
         &self,
@@ -87,24 +180,105 @@ impl CurrencyConverter {
         from: &Currency<'a>,
         to: &Currency<'a>,
     ) -> BeansResult<Currency<'a>> {
+        Ok(self.convert_with_rate(from, to).await?.0)
+    }
+
+    /// Converts an amount from one currency to another, also returning the
+    /// exchange rate that was applied.
+    ///
+    /// The rate is 1 when both currencies are the same.
+    pub async fn convert_with_rate<'a>(
+        &self,
+        from: &Currency<'a>,
+        to: &Currency<'a>,
+    ) -> BeansResult<(Currency<'a>, Decimal)> {
         let from_code = from.code();
         let to_code = to.code();
 
         if from_code == to_code {
-            return Ok(from.clone());
+            return Ok((from.clone(), Decimal::ONE));
         }
 
         let rate = self.get_exchange_rate(from, to).await?;
         let rate_decimal = Decimal::try_from(rate)
             .map_err(|e| BeansError::Other(format!("Failed to convert rate to Decimal: {}", e)))?;
         let converted_amount = from.amount() * rate_decimal;
-        Currency::new(converted_amount, to.code())
+        Ok((Currency::new(converted_amount, to.code())?, rate_decimal))
+    }
+
+    /// Converts a list of `(amount, currency code)` pairs to the target
+    /// currency, returning the converted amounts in the same order.
+    ///
+    /// The rate for each distinct source currency is fetched once, with all
+    /// fetches running concurrently, before any amount is converted. Fails
+    /// if any code is unknown or any rate can't be fetched.
+    pub async fn convert_batch(
+        &self,
+        items: &[(Decimal, String)],
+        target: &Currency<'_>,
+    ) -> BeansResult<Vec<Decimal>> {
+        let items = items
+            .iter()
+            .map(|(amount, code)| Currency::new(*amount, code))
+            .collect::<BeansResult<Vec<_>>>()?;
+
+        let mut codes: Vec<&str> = items
+            .iter()
+            .map(|item| item.code())
+            .filter(|code| *code != target.code())
+            .collect();
+        codes.sort_unstable();
+        codes.dedup();
+
+        let mut fetches = JoinSet::new();
+        for code in codes {
+            let converter = self.clone();
+            let from = code.to_string();
+            let to = target.code().to_string();
+            fetches.spawn(async move {
+                converter
+                    .get_exchange_rate(
+                        &Currency::new(Decimal::ZERO, &from)?,
+                        &Currency::new(Decimal::ZERO, &to)?,
+                    )
+                    .await
+            });
+        }
+        while let Some(result) = fetches.join_next().await {
+            result.map_err(|e| BeansError::Other(format!("Rate fetch task failed: {}", e)))??;
+        }
+
+        // Every rate is cached now, so these conversions don't hit the network
+        let mut converted = Vec::with_capacity(items.len());
+        for item in &items {
+            converted.push(*self.convert_amount(item, target).await?.amount());
+        }
+        Ok(converted)
     }
 
     /// Fetches all exchange rates for a given base currency.
     ///
-    /// This method fetches rates from the API and caches them.
+    /// This method fetches rates from the API and caches them. Transient
+    /// failures are retried according to the retry policy.
     async fn fetch_rates(&self, base_currency: &str) -> BeansResult<HashMap<String, f64>> {
+        let mut attempt = 1;
+        loop {
+            match self.fetch_rates_once(base_currency).await {
+                Ok(rates) => return Ok(rates),
+                Err(failure) if failure.retryable && attempt < self.retry_policy.max_attempts => {
+                    tokio::time::sleep(self.retry_policy.delay_after(attempt)).await;
+                    attempt += 1;
+                }
+                Err(failure) => return Err(failure.error),
+            }
+        }
+    }
+
+    /// Makes a single attempt at fetching the rates for a base currency.
+    async fn fetch_rates_once(
+        &self,
+        base_currency: &str,
+    ) -> Result<HashMap<String, f64>, FetchFailure> {
         // Build the URL
         let url = format!("{}/currencies/{}.json", self.base_url, base_currency);
 
@@ -120,13 +294,10 @@ impl CurrencyConverter {
                         .get(&fallback_url)
                         .send()
                         .await
-                        .map_err(|e| BeansError::Network(e))?
+                        .map_err(FetchFailure::network)?
                 } else {
                     // No fallback, return the error
-                    return Err(BeansError::Other(format!(
-                        "API request failed with status: {}",
-                        resp.status()
-                    )));
+                    return Err(FetchFailure::status(resp.status()));
                 }
             }
             Err(_) if self.fallback_url.is_some() => {
@@ -140,23 +311,28 @@ impl CurrencyConverter {
                     .get(&fallback_url)
                     .send()
                     .await
-                    .map_err(|e| BeansError::Network(e))?
+                    .map_err(FetchFailure::network)?
             }
-            Err(e) => return Err(BeansError::Network(e)),
+            Err(e) => return Err(FetchFailure::network(e)),
         };
 
+        // The fallback's status hasn't been checked yet
+        if !response.status().is_success() {
+            return Err(FetchFailure::status(response.status()));
+        }
+
         // Parse the JSON response
         let json: Value = response
             .json()
             .await
-            .map_err(|e| BeansError::Json(e.to_string()))?;
+            .map_err(|e| FetchFailure::permanent(BeansError::Json(e.to_string())))?;
 
         // Extract the rates
         let rates = json.get(base_currency).ok_or_else(|| {
-            BeansError::Json(format!(
+            FetchFailure::permanent(BeansError::Json(format!(
                 "Missing base currency '{}' in response",
                 base_currency
-            ))
+            )))
         })?;
 
         // Convert to HashMap
@@ -171,10 +347,10 @@ impl CurrencyConverter {
                 }
             }
         } else {
-            return Err(BeansError::Json(format!(
+            return Err(FetchFailure::permanent(BeansError::Json(format!(
                 "Expected object for '{}' rates, got: {:?}",
                 base_currency, rates
-            )));
+            ))));
         }
 
         // Cache all the rates
@@ -3,5 +3,5 @@
 mod cache;
 mod converter;
 
-pub use cache::ExchangeRateCache;
-pub use converter::CurrencyConverter;
+pub use cache::{CacheStats, ExchangeRateCache};
+pub use converter::{CurrencyConverter, RetryPolicy};
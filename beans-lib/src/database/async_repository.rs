@@ -0,0 +1,136 @@
+//! Async access to ledger entries.
+
+use crate::database::{EntryFilter, LedgerStats, Repository};
+use crate::error::{BeansError, BeansResult};
+use crate::models::LedgerEntry;
+use chrono::{DateTime, Utc};
+use std::future::Future;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Async counterpart of the entry operations in [`Repository`].
+///
+/// Backends that are inherently async implement this directly, so callers
+/// on an async runtime never block a worker thread. Synchronous repositories
+/// can be used through [`BlockingRepository`].
+pub trait AsyncRepository: std::fmt::Debug + Send + Sync {
+    /// Creates a new entry in the repository.
+    fn create(&self, entry: &LedgerEntry) -> impl Future<Output = BeansResult<()>> + Send;
+
+    /// Creates several entries in a single transaction.
+    ///
+    /// Either all entries are created or none are.
+    fn create_many(&self, entries: &[LedgerEntry]) -> impl Future<Output = BeansResult<()>> + Send;
+
+    /// Retrieves an entry by its ID.
+    fn get(&self, id: Uuid) -> impl Future<Output = BeansResult<LedgerEntry>> + Send;
+
+    /// Updates an existing entry.
+    fn update(&self, entry: &LedgerEntry) -> impl Future<Output = BeansResult<()>> + Send;
+
+    /// Deletes an entry by its ID.
+    fn delete(&self, id: Uuid) -> impl Future<Output = BeansResult<()>> + Send;
+
+    /// Lists entries matching the given filter.
+    fn list(
+        &self,
+        filter: &EntryFilter,
+    ) -> impl Future<Output = BeansResult<Vec<LedgerEntry>>> + Send;
+
+    /// Counts entries matching the given filter.
+    fn count(&self, filter: &EntryFilter) -> impl Future<Output = BeansResult<usize>> + Send;
+
+    /// Returns the dates of the earliest and latest entries, or `None` if there are no entries.
+    fn date_span(
+        &self,
+    ) -> impl Future<Output = BeansResult<Option<(DateTime<Utc>, DateTime<Utc>)>>> + Send;
+
+    /// Computes summary statistics over all entries.
+    fn stats(&self) -> impl Future<Output = BeansResult<LedgerStats>> + Send;
+}
+
+/// Runs a synchronous [`Repository`] on tokio's blocking thread pool.
+///
+/// Every call is moved to `spawn_blocking`, so waiting on the repository's
+/// lock or disk I/O doesn't stall the async runtime.
+#[derive(Debug)]
+pub struct BlockingRepository<R> {
+    inner: Arc<R>,
+}
+
+impl<R> Clone for BlockingRepository<R> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<R: Repository + Send + Sync + 'static> BlockingRepository<R> {
+    /// Wraps a synchronous repository.
+    pub fn new(repository: R) -> Self {
+        Self {
+            inner: Arc::new(repository),
+        }
+    }
+
+    /// Returns the wrapped repository.
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Runs `f` against the wrapped repository on the blocking thread pool.
+    async fn run<T, F>(&self, f: F) -> BeansResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&R) -> BeansResult<T> + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        tokio::task::spawn_blocking(move || f(&inner))
+            .await
+            .map_err(|e| BeansError::Other(format!("Repository task failed: {}", e)))?
+    }
+}
+
+impl<R: Repository + Send + Sync + 'static> AsyncRepository for BlockingRepository<R> {
+    async fn create(&self, entry: &LedgerEntry) -> BeansResult<()> {
+        let entry = entry.clone();
+        self.run(move |repo| repo.create(&entry)).await
+    }
+
+    async fn create_many(&self, entries: &[LedgerEntry]) -> BeansResult<()> {
+        let entries = entries.to_vec();
+        self.run(move |repo| repo.create_many(&entries)).await
+    }
+
+    async fn get(&self, id: Uuid) -> BeansResult<LedgerEntry> {
+        self.run(move |repo| repo.get(id)).await
+    }
+
+    async fn update(&self, entry: &LedgerEntry) -> BeansResult<()> {
+        let entry = entry.clone();
+        self.run(move |repo| repo.update(&entry)).await
+    }
+
+    async fn delete(&self, id: Uuid) -> BeansResult<()> {
+        self.run(move |repo| repo.delete(id)).await
+    }
+
+    async fn list(&self, filter: &EntryFilter) -> BeansResult<Vec<LedgerEntry>> {
+        let filter = filter.clone();
+        self.run(move |repo| repo.list(&filter)).await
+    }
+
+    async fn count(&self, filter: &EntryFilter) -> BeansResult<usize> {
+        let filter = filter.clone();
+        self.run(move |repo| repo.count(&filter)).await
+    }
+
+    async fn date_span(&self) -> BeansResult<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+        self.run(|repo| repo.date_span()).await
+    }
+
+    async fn stats(&self) -> BeansResult<LedgerStats> {
+        self.run(|repo| repo.stats()).await
+    }
+}
@@ -1,10 +1,18 @@
 //! Database module for SQLite persistence.
 
+#[cfg(feature = "async-repository")]
+mod async_repository;
 mod query_helpers;
 mod repository;
 mod schema;
 mod sqlite_repository;
 
-pub use repository::{EntryFilter, Repository};
-pub use schema::initialize_schema;
+#[cfg(feature = "async-repository")]
+pub use async_repository::{AsyncRepository, BlockingRepository};
+pub use repository::{
+    DailyTotal, EntryFilter, IssueKind, LedgerStats, MonthlySummary, Repository, RowError,
+    SortField, ValidationIssue,
+};
+pub(crate) use schema::is_beans_header;
+pub use schema::{initialize_schema, BEANS_APPLICATION_ID};
 pub use sqlite_repository::SQLiteRepository;
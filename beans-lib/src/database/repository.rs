@@ -1,12 +1,38 @@
 //! Repository pattern for database operations.
 
-use crate::error::BeansResult;
-use crate::models::LedgerEntry;
-use chrono::{DateTime, Utc};
+use crate::error::{BeansError, BeansResult};
+use crate::models::{EntryTemplate, LedgerEntry};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
 use uuid::Uuid;
 
+/// Field used to order listed entries.
+///
+/// Serialized in snake case, like [`EntryType`](crate::models::EntryType).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortField {
+    /// Transaction date, newest first. Entries sharing a date are ordered
+    /// by insertion, most recent first, so paging is stable.
+    #[default]
+    #[serde(alias = "Date")]
+    Date,
+    /// Time the entry was added to the ledger, most recent first.
+    #[serde(alias = "CreatedAt")]
+    CreatedAt,
+}
+
 /// Filter for querying ledger entries.
-#[derive(Debug, Clone, Default)]
+///
+/// Filters serialize to JSON with the field names below, so frontends and
+/// saved presets can exchange them. Dates are RFC 3339 strings, amounts are
+/// decimal strings, entry types are `"income"` or `"expense"`, and missing
+/// fields take their default.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct EntryFilter {
     /// Start date for filtering (inclusive).
     pub start_date: Option<DateTime<Utc>>,
@@ -16,12 +42,20 @@ pub struct EntryFilter {
     pub entry_type: Option<crate::models::EntryType>,
     /// Filter by currency.
     pub currency: Option<String>,
+    /// Filter by a case-insensitive substring of the entry name.
+    pub name_contains: Option<String>,
+    /// Filter by an exact amount, compared numerically.
+    pub exact_amount: Option<Decimal>,
     /// Filter by tags (entries must have all specified tags).
     pub tags: Vec<String>,
+    /// Filter by metadata (entries must have every key set to the given value).
+    pub metadata: BTreeMap<String, String>,
     /// Maximum number of entries to return.
     pub limit: Option<usize>,
     /// Number of entries to skip.
     pub offset: Option<usize>,
+    /// Order in which entries are returned.
+    pub sort: SortField,
 }
 
 impl EntryFilter {
@@ -31,21 +65,178 @@ impl EntryFilter {
             end_date: None,
             entry_type: None,
             currency: None,
+            name_contains: None,
+            exact_amount: None,
             tags: Vec::new(),
+            metadata: BTreeMap::new(),
             limit: None,
             offset: None,
+            sort: SortField::Date,
+        }
+    }
+}
+
+/// Overview of a ledger's contents.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LedgerStats {
+    /// Total number of entries.
+    pub entry_count: usize,
+    /// Dates of the earliest and latest entries, or `None` for an empty ledger.
+    pub date_span: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    /// Currency codes used by at least one entry, sorted alphabetically.
+    pub currencies: Vec<String>,
+    /// Tags used by at least one entry, sorted alphabetically.
+    pub tags: Vec<String>,
+    /// Net amount (income - expenses) per currency code.
+    pub net_by_currency: HashMap<String, Decimal>,
+}
+
+/// Total amount of one type of entry on one day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyTotal {
+    /// UTC calendar day.
+    pub day: NaiveDate,
+    /// Type of the entries summed.
+    pub entry_type: crate::models::EntryType,
+    /// Sum of the entries' amounts, regardless of their currency.
+    pub total: Decimal,
+    /// Number of entries summed.
+    pub count: usize,
+}
+
+/// Total amount of one type of entry in one currency over a calendar month.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonthlySummary {
+    /// First day of the UTC calendar month.
+    pub month: NaiveDate,
+    /// Currency code of the entries summed.
+    pub currency: String,
+    /// Type of the entries summed.
+    pub entry_type: crate::models::EntryType,
+    /// Sum of the entries' amounts.
+    pub total: Decimal,
+    /// Number of entries summed.
+    pub count: usize,
+}
+
+/// A stored row that could not be read as a ledger entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowError {
+    /// ID stored in the row, if it could be read.
+    pub id: Option<String>,
+    /// Why the row could not be read.
+    pub message: String,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.id {
+            Some(id) => write!(f, "Entry {}: {}", id, self.message),
+            None => write!(f, "{}", self.message),
         }
     }
 }
 
+/// Kind of problem found when validating stored data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IssueKind {
+    /// The entry ID is not a valid UUID.
+    InvalidId,
+    /// The date is not a valid RFC 3339 timestamp.
+    InvalidDate,
+    /// The currency code is not a supported currency.
+    InvalidCurrency,
+    /// The amount is not a decimal number.
+    InvalidAmount,
+    /// The amount is zero or negative.
+    NonPositiveAmount,
+    /// The entry type is neither income nor expense.
+    InvalidEntryType,
+    /// A tag link refers to a tag that doesn't exist.
+    MissingTag,
+    /// A tag link or metadata row refers to an entry that doesn't exist.
+    MissingEntry,
+}
+
+/// A problem with stored data that the entry builder would have rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// ID of the entry the problem belongs to, as stored.
+    pub entry_id: String,
+    /// What is wrong.
+    pub kind: IssueKind,
+    /// Description of the problem, including the offending value.
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entry {}: {}", self.entry_id, self.message)
+    }
+}
+
+/// Returns a copy of the filter without its limit and offset.
+fn unpaged(filter: &EntryFilter) -> EntryFilter {
+    EntryFilter {
+        limit: None,
+        offset: None,
+        ..filter.clone()
+    }
+}
+
 /// Repository trait for ledger entry operations.
-pub trait Repository: std::fmt::Debug {
+///
+/// Repositories must be `Send` so a ledger can be opened on a worker thread
+/// and handed back to the caller.
+///
+/// Only the entry writes, [`get`](Self::get), [`list`](Self::list),
+/// [`count`](Self::count), [`reindex`](Self::reindex) and the preset and
+/// template storage must be implemented. The other methods have defaults;
+/// queries fall back to `list`, which reads every matching entry, so
+/// storages that can answer them more cheaply should override them.
+pub trait Repository: std::fmt::Debug + Send {
     /// Creates a new entry in the repository.
     fn create<'a>(&self, entry: &LedgerEntry) -> BeansResult<()>;
 
+    /// Creates several entries in a single transaction.
+    ///
+    /// Either all entries are created or none are.
+    fn create_many(&self, entries: &[LedgerEntry]) -> BeansResult<()>;
+
+    /// Creates several entries in a single transaction, checking referential
+    /// integrity once for the whole batch instead of on every insert.
+    ///
+    /// This is faster for large imports. If the final check finds a
+    /// violation, no entries are created. The default implementation calls
+    /// [`create_many`](Self::create_many).
+    fn create_many_deferred(&self, entries: &[LedgerEntry]) -> BeansResult<()> {
+        self.create_many(entries)
+    }
+
+    /// Deletes the entries with the given IDs and creates new entries in
+    /// a single transaction.
+    ///
+    /// Fails with a not-found error, changing nothing, if any ID is missing.
+    fn replace(&self, ids: &[Uuid], entries: &[LedgerEntry]) -> BeansResult<()>;
+
     /// Retrieves an entry by its ID.
     fn get(&self, id: Uuid) -> BeansResult<LedgerEntry>;
 
+    /// Returns the IDs of all entries whose ID starts with the given prefix,
+    /// sorted. The prefix is matched against the hyphenated lowercase form.
+    ///
+    /// The default implementation reads every entry.
+    fn ids_with_prefix(&self, prefix: &str) -> BeansResult<Vec<Uuid>> {
+        let mut ids: Vec<Uuid> = self
+            .list(&EntryFilter::default())?
+            .iter()
+            .map(|entry| entry.id())
+            .filter(|id| id.to_string().starts_with(prefix))
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
     /// Updates an existing entry.
     fn update<'a>(&self, entry: &LedgerEntry) -> BeansResult<()>;
 
@@ -55,6 +246,149 @@ pub trait Repository: std::fmt::Debug {
     /// Lists entries matching the given filter.
     fn list(&self, filter: &EntryFilter) -> BeansResult<Vec<LedgerEntry>>;
 
+    /// Lists entries matching the given filter, skipping rows that can't be read.
+    ///
+    /// Unlike [`list`](Self::list), a corrupt row doesn't fail the whole
+    /// listing; it is reported in the returned errors instead. The default
+    /// implementation calls `list`, so it reports no errors.
+    fn list_lenient(&self, filter: &EntryFilter) -> BeansResult<(Vec<LedgerEntry>, Vec<RowError>)> {
+        Ok((self.list(filter)?, Vec::new()))
+    }
+
     /// Counts entries matching the given filter.
     fn count(&self, filter: &EntryFilter) -> BeansResult<usize>;
+
+    /// Returns the sum and number of the amounts of entries matching the
+    /// given filter, regardless of their currency.
+    ///
+    /// The default implementation lists the matching entries; storages that
+    /// can aggregate should override it. The filter's sort, limit and offset
+    /// are ignored.
+    fn amount_total(&self, filter: &EntryFilter) -> BeansResult<(Decimal, usize)> {
+        let entries = self.list(&unpaged(filter))?;
+        Ok((entries.iter().map(|e| e.amount()).sum(), entries.len()))
+    }
+
+    /// Sums the amounts of entries matching the given filter per UTC day
+    /// and entry type, sorted by day and then type.
+    ///
+    /// Sums are exact; amounts the storage can't total exactly are an
+    /// error. The filter's sort, limit and offset are ignored. The default
+    /// implementation lists the matching entries; storages that can
+    /// aggregate should override it.
+    fn daily_totals(&self, filter: &EntryFilter) -> BeansResult<Vec<DailyTotal>> {
+        let mut totals: BTreeMap<(NaiveDate, String), DailyTotal> = BTreeMap::new();
+        for entry in self.list(&unpaged(filter))? {
+            let day = entry.date().date_naive();
+            let total = totals
+                .entry((day, entry.entry_type().to_string()))
+                .or_insert(DailyTotal {
+                    day,
+                    entry_type: entry.entry_type(),
+                    total: Decimal::ZERO,
+                    count: 0,
+                });
+            total.total += entry.amount();
+            total.count += 1;
+        }
+        Ok(totals.into_values().collect())
+    }
+
+    /// Recomputes the stored monthly summaries from every entry.
+    ///
+    /// Once built, the storage keeps the summaries up to date as entries
+    /// change, so this only needs to be called once. The default
+    /// implementation fails with [`BeansError::NotImplemented`].
+    fn rebuild_monthly_summaries(&self) -> BeansResult<()> {
+        Err(BeansError::NotImplemented(
+            "Monthly summaries are not supported by this repository".to_string(),
+        ))
+    }
+
+    /// Returns the stored monthly summaries for the months from `start` to
+    /// `end` inclusive, sorted by month, currency and type.
+    ///
+    /// Months whose entries changed since they were last summarized are
+    /// recomputed first. Returns `None` if the summaries were never built,
+    /// which is always the case for the default implementation.
+    fn monthly_summaries(
+        &self,
+        _start: NaiveDate,
+        _end: NaiveDate,
+    ) -> BeansResult<Option<Vec<MonthlySummary>>> {
+        Ok(None)
+    }
+
+    /// Returns the dates of the earliest and latest entries, or `None` if there are no entries.
+    ///
+    /// The default implementation reads every entry.
+    fn date_span(&self) -> BeansResult<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+        let dates = self
+            .list(&EntryFilter::default())?
+            .iter()
+            .map(|entry| entry.date())
+            .collect::<Vec<_>>();
+        Ok(dates.iter().min().copied().zip(dates.iter().max().copied()))
+    }
+
+    /// Computes summary statistics over all entries.
+    ///
+    /// The default implementation reads every entry.
+    fn stats(&self) -> BeansResult<LedgerStats> {
+        let entries = self.list(&EntryFilter::default())?;
+
+        let mut stats = LedgerStats {
+            entry_count: entries.len(),
+            date_span: self.date_span()?,
+            ..Default::default()
+        };
+        let mut currencies = BTreeSet::new();
+        let mut tags = BTreeSet::new();
+        for entry in &entries {
+            currencies.insert(entry.currency_code());
+            tags.extend(entry.tags().iter().map(|tag| tag.name().to_string()));
+            *stats
+                .net_by_currency
+                .entry(entry.currency_code())
+                .or_insert(Decimal::ZERO) += entry.signed_amount();
+        }
+        stats.currencies = currencies.into_iter().collect();
+        stats.tags = tags.into_iter().collect();
+
+        Ok(stats)
+    }
+
+    /// Checks every stored entry and link against the rules the library
+    /// enforces on write, returning all problems found.
+    ///
+    /// This finds data that bypassed validation, e.g. rows written with raw
+    /// SQL or by older versions. An empty list means no problems. The
+    /// default implementation fails with [`BeansError::NotImplemented`]
+    /// rather than report a ledger it never checked as clean.
+    fn validate(&self) -> BeansResult<Vec<ValidationIssue>> {
+        Err(BeansError::NotImplemented(
+            "Validation is not supported by this repository".to_string(),
+        ))
+    }
+
+    /// Rebuilds all indexes in the underlying storage.
+    fn reindex(&self) -> BeansResult<()>;
+
+    /// Stores a filter under the given name, replacing any existing preset with that name.
+    fn save_filter_preset(&self, name: &str, filter: &EntryFilter) -> BeansResult<()>;
+
+    /// Loads the filter stored under the given name.
+    fn load_filter_preset(&self, name: &str) -> BeansResult<EntryFilter>;
+
+    /// Lists the names of all stored filter presets, sorted alphabetically.
+    fn list_filter_presets(&self) -> BeansResult<Vec<String>>;
+
+    /// Stores an entry template under the given name, replacing any existing template with that name.
+    fn save_template(&self, name: &str, template: &EntryTemplate) -> BeansResult<()>;
+
+    /// Loads the entry template stored under the given name.
+    fn load_template(&self, name: &str) -> BeansResult<EntryTemplate>;
+
+    /// Lists the names of all stored entry templates, sorted alphabetically.
+    fn list_templates(&self) -> BeansResult<Vec<String>>;
 }
@@ -6,7 +6,19 @@ use sql_query_builder as sql;
 use std::collections::HashMap;
 
 /// Current schema version.
-pub const CURRENT_SCHEMA_VERSION: i64 = 1;
+pub const CURRENT_SCHEMA_VERSION: i64 = 6;
+
+/// Value stored in the SQLite header's `application_id` field of every ledger ("BEAN").
+///
+/// The header also carries [`CURRENT_SCHEMA_VERSION`] in its `user_version`
+/// field, so both can be read from the first 100 bytes of the file.
+pub const BEANS_APPLICATION_ID: u32 = 0x4245_414E;
+
+/// Byte offset of `application_id` in the SQLite database header.
+const APPLICATION_ID_OFFSET: usize = 68;
+
+/// Magic string at the start of every SQLite database file.
+const SQLITE_HEADER_MAGIC: &[u8] = b"SQLite format 3\0";
 
 /// Initializes the database schema.
 ///
@@ -28,12 +40,15 @@ pub fn initialize_schema(conn: &Connection) -> BeansResult<()> {
     // Get current schema version from database
     let db_version = get_schema_version(conn)?;
 
-    // If the database is new (version 0), create the initial schema
-    if db_version == 0 {
+    // If the database is new (version 0), create the initial schema and
+    // bring it up to date through the regular migrations
+    let db_version = if db_version == 0 {
         create_initial_schema(conn)?;
-        set_schema_version(conn, CURRENT_SCHEMA_VERSION)?;
-        return Ok(());
-    }
+        set_schema_version(conn, 1)?;
+        1
+    } else {
+        db_version
+    };
 
     // If the database has an older version, run migrations
     if db_version < CURRENT_SCHEMA_VERSION {
@@ -41,9 +56,32 @@ pub fn initialize_schema(conn: &Connection) -> BeansResult<()> {
         set_schema_version(conn, CURRENT_SCHEMA_VERSION)?;
     }
 
+    set_header_fields(conn)?;
+
     Ok(())
 }
 
+/// Stamps the file header with the beans application id and schema version.
+fn set_header_fields(conn: &Connection) -> BeansResult<()> {
+    conn.execute_batch(&format!(
+        "PRAGMA application_id = {}; PRAGMA user_version = {};",
+        BEANS_APPLICATION_ID as i32, CURRENT_SCHEMA_VERSION
+    ))
+    .map_err(|e| BeansError::database(format!("Failed to set file header fields: {}", e)))
+}
+
+/// Returns true if `header` starts with a SQLite header carrying the beans application id.
+pub(crate) fn is_beans_header(header: &[u8]) -> bool {
+    let id_end = APPLICATION_ID_OFFSET + 4;
+    if header.len() < id_end || !header.starts_with(SQLITE_HEADER_MAGIC) {
+        return false;
+    }
+
+    let mut id = [0u8; 4];
+    id.copy_from_slice(&header[APPLICATION_ID_OFFSET..id_end]);
+    u32::from_be_bytes(id) == BEANS_APPLICATION_ID
+}
+
 /// Creates the initial schema (version 1).
 fn create_initial_schema(conn: &Connection) -> BeansResult<()> {
     // Create entries table
@@ -133,7 +171,12 @@ fn create_initial_schema(conn: &Connection) -> BeansResult<()> {
 /// Runs migrations to upgrade the schema from one version to another.
 fn run_migrations(conn: &Connection, from_version: i64, to_version: i64) -> BeansResult<()> {
     // Define migrations as a map from version to migration function
-    let migrations: HashMap<i64, fn(&Connection) -> BeansResult<()>> = HashMap::new();
+    let mut migrations: HashMap<i64, fn(&Connection) -> BeansResult<()>> = HashMap::new();
+    migrations.insert(2, migrate_to_v2);
+    migrations.insert(3, migrate_to_v3);
+    migrations.insert(4, migrate_to_v4);
+    migrations.insert(5, migrate_to_v5);
+    migrations.insert(6, migrate_to_v6);
 
     // Run migrations in order
     for version in from_version + 1..=to_version {
@@ -145,6 +188,178 @@ fn run_migrations(conn: &Connection, from_version: i64, to_version: i64) -> Bean
     Ok(())
 }
 
+/// Adds the filter_presets table (version 2).
+fn migrate_to_v2(conn: &Connection) -> BeansResult<()> {
+    let create_filter_presets_table = sql::CreateTable::new()
+        .create_table_if_not_exists("filter_presets")
+        .column("name TEXT PRIMARY KEY")
+        .column("filter TEXT NOT NULL")
+        .column("updated_at TEXT NOT NULL")
+        .as_string();
+
+    conn.execute(&create_filter_presets_table, [])
+        .map_err(|e| {
+            BeansError::database(format!("Failed to create filter_presets table: {}", e))
+        })?;
+
+    Ok(())
+}
+
+/// Adds the templates table (version 3).
+fn migrate_to_v3(conn: &Connection) -> BeansResult<()> {
+    let create_templates_table = sql::CreateTable::new()
+        .create_table_if_not_exists("templates")
+        .column("name TEXT PRIMARY KEY")
+        .column("template TEXT NOT NULL")
+        .column("updated_at TEXT NOT NULL")
+        .as_string();
+
+    conn.execute(&create_templates_table, [])
+        .map_err(|e| BeansError::database(format!("Failed to create templates table: {}", e)))?;
+
+    Ok(())
+}
+
+/// Adds a numeric copy of entry amounts (version 4).
+///
+/// The TEXT `amount` column remains the exact value; `amount_value` is a REAL
+/// kept alongside it so SQL can filter, sort and aggregate by amount.
+/// Existing rows are backfilled from the TEXT column.
+fn migrate_to_v4(conn: &Connection) -> BeansResult<()> {
+    conn.execute_batch(
+        "
+        ALTER TABLE entries ADD COLUMN amount_value REAL NOT NULL DEFAULT 0;
+        UPDATE entries SET amount_value = CAST(amount AS REAL);
+        ",
+    )
+    .map_err(|e| BeansError::database(format!("Failed to add amount_value column: {}", e)))?;
+
+    let create_idx_entries_amount_value = sql::CreateIndex::new()
+        .create_index_if_not_exists("idx_entries_amount_value")
+        .on("entries")
+        .column("amount_value")
+        .as_string();
+
+    conn.execute(&create_idx_entries_amount_value, [])
+        .map_err(|e| {
+            BeansError::database(format!("Failed to create idx_entries_amount_value: {}", e))
+        })?;
+
+    Ok(())
+}
+
+/// Adds the entry_metadata table (version 5).
+fn migrate_to_v5(conn: &Connection) -> BeansResult<()> {
+    let create_entry_metadata_table = sql::CreateTable::new()
+        .create_table_if_not_exists("entry_metadata")
+        .column("entry_id TEXT NOT NULL")
+        .column("key TEXT NOT NULL")
+        .column("value TEXT NOT NULL")
+        .column("PRIMARY KEY (entry_id, key)")
+        .column("FOREIGN KEY (entry_id) REFERENCES entries (id) ON DELETE CASCADE")
+        .as_string();
+
+    conn.execute(&create_entry_metadata_table, [])
+        .map_err(|e| {
+            BeansError::database(format!("Failed to create entry_metadata table: {}", e))
+        })?;
+
+    let create_idx_entry_metadata_key_value = sql::CreateIndex::new()
+        .create_index_if_not_exists("idx_entry_metadata_key_value")
+        .on("entry_metadata")
+        .column("key, value")
+        .as_string();
+
+    conn.execute(&create_idx_entry_metadata_key_value, [])
+        .map_err(|e| {
+            BeansError::database(format!(
+                "Failed to create idx_entry_metadata_key_value: {}",
+                e
+            ))
+        })?;
+
+    Ok(())
+}
+
+/// Adds the monthly_summaries table (version 6).
+///
+/// The table holds per-month totals built on demand. Once built, triggers
+/// record the month of every entry inserted, updated or deleted, including
+/// by raw SQL, in monthly_summaries_stale, and only those months are
+/// recomputed the next time summaries are read. A row in
+/// monthly_summaries_built marks the totals as built, so an empty ledger
+/// is told apart from one that was never summarized.
+fn migrate_to_v6(conn: &Connection) -> BeansResult<()> {
+    let create_monthly_summaries_table = sql::CreateTable::new()
+        .create_table_if_not_exists("monthly_summaries")
+        .column("month TEXT NOT NULL")
+        .column("currency TEXT NOT NULL")
+        .column("entry_type TEXT NOT NULL")
+        .column("total TEXT NOT NULL")
+        .column("count INTEGER NOT NULL")
+        .column("PRIMARY KEY (month, currency, entry_type)")
+        .as_string();
+
+    conn.execute(&create_monthly_summaries_table, [])
+        .map_err(|e| {
+            BeansError::database(format!("Failed to create monthly_summaries table: {}", e))
+        })?;
+
+    let create_stale_table = sql::CreateTable::new()
+        .create_table_if_not_exists("monthly_summaries_stale")
+        .column("month TEXT PRIMARY KEY")
+        .as_string();
+
+    conn.execute(&create_stale_table, []).map_err(|e| {
+        BeansError::database(format!(
+            "Failed to create monthly_summaries_stale table: {}",
+            e
+        ))
+    })?;
+
+    let create_built_table = sql::CreateTable::new()
+        .create_table_if_not_exists("monthly_summaries_built")
+        .column("built_at TEXT NOT NULL")
+        .as_string();
+
+    conn.execute(&create_built_table, []).map_err(|e| {
+        BeansError::database(format!(
+            "Failed to create monthly_summaries_built table: {}",
+            e
+        ))
+    })?;
+
+    let mark_stale = |row: &str| {
+        format!(
+            "INSERT OR IGNORE INTO monthly_summaries_stale (month)
+             VALUES (strftime('%Y-%m-01', {}.date));",
+            row
+        )
+    };
+    for (event, rows) in [
+        ("INSERT", vec!["NEW"]),
+        ("UPDATE", vec!["OLD", "NEW"]),
+        ("DELETE", vec!["OLD"]),
+    ] {
+        let create_trigger = format!(
+            "CREATE TRIGGER IF NOT EXISTS monthly_summaries_after_{} AFTER {} ON entries
+             WHEN EXISTS (SELECT 1 FROM monthly_summaries_built)
+             BEGIN {} END",
+            event.to_lowercase(),
+            event,
+            rows.into_iter().map(mark_stale).collect::<String>()
+        );
+        conn.execute(&create_trigger, []).map_err(|e| {
+            BeansError::database(format!(
+                "Failed to create monthly_summaries {} trigger: {}",
+                event, e
+            ))
+        })?;
+    }
+
+    Ok(())
+}
+
 /// Returns the current schema version from the database.
 ///
 /// Returns 0 if the schema_version table doesn't exist or is empty.
@@ -207,7 +422,16 @@ fn set_schema_version(conn: &Connection, version: i64) -> BeansResult<()> {
 /// This checks that all required tables and indexes exist.
 pub fn validate_schema(conn: &Connection) -> BeansResult<bool> {
     // List of required tables
-    let required_tables = vec!["entries", "tags", "entry_tags", "schema_version"];
+    let required_tables = vec![
+        "entries",
+        "tags",
+        "entry_tags",
+        "schema_version",
+        "filter_presets",
+        "templates",
+        "entry_metadata",
+        "monthly_summaries",
+    ];
 
     // List of required indexes
     let required_indexes = vec![
@@ -215,6 +439,8 @@ pub fn validate_schema(conn: &Connection) -> BeansResult<bool> {
         "idx_entries_entry_type",
         "idx_entries_currency",
         "idx_tags_name",
+        "idx_entries_amount_value",
+        "idx_entry_metadata_key_value",
     ];
 
     // Check tables
@@ -1,16 +1,171 @@
 //! SQLite implementation of the Repository trait.
 
-use crate::database::{EntryFilter, Repository};
+use crate::database::{
+    DailyTotal, EntryFilter, IssueKind, LedgerStats, MonthlySummary, Repository, RowError,
+    SortField, ValidationIssue,
+};
 use crate::error::{BeansError, BeansResult};
-use crate::models::{EntryType, LedgerEntry, LedgerEntryBuilder, Tag};
-use chrono::{DateTime, Utc};
+use crate::models::currency::find_iso_currency;
+use crate::models::{EntryTemplate, EntryType, LedgerEntry, LedgerEntryBuilder, Tag};
+use chrono::{DateTime, NaiveDate, Utc};
 use rusqlite::{params, types::Type, Connection, Transaction};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use sql_query_builder as sql;
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+/// Condition matching entries with a given metadata key and value.
+const METADATA_SUBQUERY: &str =
+    "id IN (SELECT entry_id FROM entry_metadata WHERE key = ? AND value = ?)";
+
+/// Condition matching entries whose name contains a LIKE pattern.
+const NAME_CONTAINS_CLAUSE: &str = "name LIKE ? ESCAPE '\\'";
+
+/// Condition matching entries with an amount numerically equal to a value,
+/// so `1200` matches a stored `1200.00`.
+///
+/// The indexed `amount_value` narrows the search to a tight range around
+/// the value; the stored TEXT, with trailing zeros trimmed, is then compared
+/// exactly. Takes the parameters from [`exact_amount_params`].
+const EXACT_AMOUNT_CLAUSE: &str = "amount_value BETWEEN ? AND ? AND \
+     CASE WHEN instr(amount, '.') > 0 THEN rtrim(rtrim(amount, '0'), '.') ELSE amount END = ?";
+
+/// Returns the parameters of [`EXACT_AMOUNT_CLAUSE`] for an amount.
+fn exact_amount_params(amount: Decimal) -> [Box<dyn rusqlite::ToSql>; 3] {
+    // Wide enough to absorb rounding in the stored REAL copy
+    let value = amount.to_f64().unwrap_or_default();
+    let slack = value.abs() * 1e-9;
+    [
+        Box::new(value - slack),
+        Box::new(value + slack),
+        Box::new(amount.normalize().to_string()),
+    ]
+}
+
+/// Builds a LIKE pattern matching any name that contains `needle`.
+///
+/// Wildcard characters in `needle` are escaped so they match literally.
+fn like_pattern(needle: &str) -> String {
+    let mut pattern = String::with_capacity(needle.len() + 2);
+    pattern.push('%');
+    for c in needle.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// Parses an entry type as stored in the database.
+fn parse_entry_type(value: &str) -> BeansResult<EntryType> {
+    match value {
+        "Income" => Ok(EntryType::Income),
+        "Expense" => Ok(EntryType::Expense),
+        other => Err(BeansError::database(format!(
+            "Invalid entry type in database: {}",
+            other
+        ))),
+    }
+}
+
+/// Number of decimal places in a stored amount.
+const AMOUNT_SCALE_EXPR: &str =
+    "CASE WHEN instr(amount, '.') > 0 THEN length(amount) - instr(amount, '.') ELSE 0 END";
+
+/// Largest scale at which amounts are totalled in the database.
+const MAX_SUM_SCALE: u32 = 18;
+
+/// Largest single amount, in units of the totalling scale, that fits
+/// comfortably in SQLite's 64-bit integers.
+const MAX_SUM_UNITS: f64 = 1e18;
+
+/// Returns the SQL for the exact sum of the matched amounts in integer
+/// units of `10^-scale`.
+///
+/// Each stored amount has its decimal point dropped and is padded with
+/// zeros up to `scale` places, so no floating point is involved. SQLite
+/// fails the query rather than wrap if the sum overflows.
+fn amount_units_sum(scale: u32) -> String {
+    format!(
+        "SUM(CAST(replace(amount, '.', '') || substr('{}', 1, {} - ({})) AS INTEGER))",
+        "0".repeat(scale as usize),
+        scale,
+        AMOUNT_SCALE_EXPR
+    )
+}
+
+/// Returns the scale at which the amounts matched by `query` can be summed
+/// exactly in the database, or `None` if nothing matched.
+///
+/// `query` must select the largest amount scale and the largest absolute
+/// `amount_value`. Amounts too precise or too large to total as 64-bit
+/// integers are an error.
+fn amount_sum_scale(
+    conn: &Connection,
+    query: &str,
+    params: impl rusqlite::Params,
+) -> BeansResult<Option<u32>> {
+    let (scale, largest): (Option<i64>, Option<f64>) = conn
+        .query_row(query, params, |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| BeansError::database(format!("Failed to query amount scale: {}", e)))?;
+    let (Some(scale), Some(largest)) = (scale, largest) else {
+        return Ok(None);
+    };
+
+    let scale = u32::try_from(scale).unwrap_or(u32::MAX);
+    if scale > MAX_SUM_SCALE || largest * 10f64.powi(scale as i32) >= MAX_SUM_UNITS {
+        return Err(BeansError::database(
+            "Amounts are too large or precise to total in the database",
+        ));
+    }
+    Ok(Some(scale))
+}
+
+/// Turns foreign key enforcement off for as long as it is alive.
+///
+/// Enforcement is turned back on when it is dropped, even if the work done
+/// in between failed or panicked, so the connection is never left without it.
+struct ForeignKeysOff<'a> {
+    conn: &'a mut Connection,
+    restored: bool,
+}
+
+impl<'a> ForeignKeysOff<'a> {
+    fn new(conn: &'a mut Connection) -> BeansResult<Self> {
+        conn.execute("PRAGMA foreign_keys = OFF", [])
+            .map_err(|e| BeansError::database(format!("Failed to disable foreign keys: {}", e)))?;
+
+        Ok(Self {
+            conn,
+            restored: false,
+        })
+    }
+
+    /// Turns enforcement back on, reporting a failure to do so.
+    fn restore(&mut self) -> BeansResult<()> {
+        self.conn
+            .execute("PRAGMA foreign_keys = ON", [])
+            .map_err(|e| BeansError::database(format!("Failed to enable foreign keys: {}", e)))?;
+        self.restored = true;
+
+        Ok(())
+    }
+}
+
+impl Drop for ForeignKeysOff<'_> {
+    fn drop(&mut self) {
+        if !self.restored {
+            // Nothing to report the error to; a later restore would fail the same way
+            let _ = self.conn.execute("PRAGMA foreign_keys = ON", []);
+        }
+    }
+}
+
 /// SQLite implementation of the Repository trait.
 #[derive(Debug)]
 pub struct SQLiteRepository {
@@ -117,6 +272,262 @@ impl SQLiteRepository {
         Ok(())
     }
 
+    /// Inserts an entry and its tags within the given transaction.
+    fn insert_entry(&self, tx: &Transaction, entry: &LedgerEntry) -> BeansResult<()> {
+        let insert_query = sql::Insert::new()
+            .insert_into("entries (id, date, name, currency, amount, amount_value, description, entry_type, created_at, updated_at)")
+            .values("(?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+            .as_string();
+
+        tx.execute(
+            &insert_query,
+            params![
+                entry.id().to_string(),
+                entry.date().to_rfc3339(),
+                entry.name(),
+                entry.currency_code(),
+                entry.amount().to_string(),
+                entry.amount().to_f64(),
+                entry.description(),
+                format!("{:?}", entry.entry_type()),
+                entry.created_at().to_rfc3339(),
+                entry.updated_at().to_rfc3339(),
+            ],
+        )
+        .map_err(|e| BeansError::database(format!("Failed to insert entry: {}", e)))?;
+
+        // Convert HashSet<Tag> to Vec<Tag> for save_tags
+        let tags_vec: Vec<Tag> = entry.tags().iter().cloned().collect();
+
+        // Save tags and metadata
+        self.save_tags(tx, &entry.id(), &tags_vec)?;
+        self.save_metadata(tx, &entry.id(), entry.metadata())
+    }
+
+    /// Computes and stores the monthly summaries for every month, or only
+    /// for `month` (formatted `%Y-%m-01`) if given.
+    fn store_monthly_summaries(tx: &Transaction, month: Option<&str>) -> BeansResult<()> {
+        let month_clause = "strftime('%Y-%m-01', date) = ?1";
+
+        let mut scale_select = sql::Select::new()
+            .select(&format!(
+                "MAX({}), MAX(ABS(amount_value))",
+                AMOUNT_SCALE_EXPR
+            ))
+            .from("entries");
+        if month.is_some() {
+            scale_select = scale_select.where_clause(month_clause);
+        }
+        let Some(scale) = amount_sum_scale(
+            tx,
+            &scale_select.as_string(),
+            rusqlite::params_from_iter(month),
+        )?
+        else {
+            return Ok(());
+        };
+
+        let mut select = sql::Select::new()
+            .select("strftime('%Y-%m-01', date) AS month, currency, entry_type")
+            .select(&format!("{}, COUNT(*)", amount_units_sum(scale)))
+            .from("entries");
+        if month.is_some() {
+            select = select.where_clause(month_clause);
+        }
+        let query = select.group_by("month, currency, entry_type").as_string();
+        let insert = sql::Insert::new()
+            .insert_into("monthly_summaries (month, currency, entry_type, total, count)")
+            .values("(?1, ?2, ?3, ?4, ?5)")
+            .as_string();
+
+        let mut stmt = tx
+            .prepare(&query)
+            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(month), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?,
+                    row.get::<_, i64>(4)?,
+                ))
+            })
+            .map_err(|e| BeansError::database(format!("Failed to query monthly totals: {}", e)))?;
+
+        for row in rows {
+            let (month, currency, entry_type, units, count) = row.map_err(|e| {
+                BeansError::database(format!("Failed to read monthly total: {}", e))
+            })?;
+            let total = Decimal::new(units, scale);
+            tx.execute(
+                &insert,
+                params![month, currency, entry_type, total.to_string(), count],
+            )
+            .map_err(|e| BeansError::database(format!("Failed to store monthly summary: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Recomputes the monthly summaries of the months whose entries changed
+    /// since they were last computed.
+    fn refresh_stale_monthly_summaries(conn: &mut Connection) -> BeansResult<()> {
+        let tx = conn
+            .transaction()
+            .map_err(|e| BeansError::database(format!("Failed to start transaction: {}", e)))?;
+
+        let months = {
+            let mut stmt = tx
+                .prepare("SELECT month FROM monthly_summaries_stale")
+                .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+            let months = stmt
+                .query_map([], |row| row.get::<_, String>(0))
+                .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+                .map_err(|e| {
+                    BeansError::database(format!("Failed to query stale months: {}", e))
+                })?;
+            months
+        };
+        if months.is_empty() {
+            return Ok(());
+        }
+
+        for month in &months {
+            tx.execute(
+                "DELETE FROM monthly_summaries WHERE month = ?1",
+                params![month],
+            )
+            .map_err(|e| {
+                BeansError::database(format!("Failed to clear monthly summaries: {}", e))
+            })?;
+            Self::store_monthly_summaries(&tx, Some(month))?;
+        }
+        tx.execute("DELETE FROM monthly_summaries_stale", [])
+            .map_err(|e| BeansError::database(format!("Failed to clear stale months: {}", e)))?;
+
+        tx.commit()
+            .map_err(|e| BeansError::database(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Returns the earliest and latest entry dates, or `None` if there are no entries.
+    fn query_date_span(conn: &Connection) -> BeansResult<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+        let span_query = sql::Select::new()
+            .select("MIN(date), MAX(date)")
+            .from("entries")
+            .as_string();
+
+        let (min_date, max_date): (Option<String>, Option<String>) = conn
+            .query_row(&span_query, [], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| BeansError::database(format!("Failed to query date span: {}", e)))?;
+
+        let parse_date = |date: &str| {
+            DateTime::parse_from_rfc3339(date)
+                .map(|d| d.with_timezone(&Utc))
+                .map_err(|e| BeansError::database(format!("Invalid date in database: {}", e)))
+        };
+
+        match (min_date, max_date) {
+            (Some(min), Some(max)) => Ok(Some((parse_date(&min)?, parse_date(&max)?))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Inserts entries in one transaction, then commits only if
+    /// `PRAGMA foreign_key_check` reports no violations.
+    fn insert_and_check_foreign_keys(
+        &self,
+        conn: &mut Connection,
+        entries: &[LedgerEntry],
+    ) -> BeansResult<()> {
+        let tx = conn
+            .transaction()
+            .map_err(|e| BeansError::database(format!("Failed to start transaction: {}", e)))?;
+
+        for entry in entries {
+            self.insert_entry(&tx, entry)?;
+        }
+
+        let violations = {
+            let mut stmt = tx.prepare("PRAGMA foreign_key_check").map_err(|e| {
+                BeansError::database(format!("Failed to prepare foreign key check: {}", e))
+            })?;
+            let rows = stmt.query_map([], |_| Ok(())).map_err(|e| {
+                BeansError::database(format!("Failed to check foreign keys: {}", e))
+            })?;
+            rows.count()
+        };
+
+        if violations > 0 {
+            return Err(BeansError::database(format!(
+                "Foreign key check failed with {} violation(s)",
+                violations
+            )));
+        }
+
+        tx.commit()
+            .map_err(|e| BeansError::database(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Saves the metadata for an entry, replacing any existing pairs.
+    fn save_metadata(
+        &self,
+        tx: &Transaction,
+        entry_id: &Uuid,
+        metadata: &HashMap<String, String>,
+    ) -> BeansResult<()> {
+        let delete_query = sql::Delete::new()
+            .delete_from("entry_metadata")
+            .where_clause("entry_id = ?")
+            .as_string();
+
+        tx.execute(&delete_query, params![entry_id.to_string()])
+            .map_err(|e| BeansError::database(format!("Failed to delete metadata: {}", e)))?;
+
+        let insert_query = sql::Insert::new()
+            .insert_into("entry_metadata (entry_id, key, value)")
+            .values("(?, ?, ?)")
+            .as_string();
+
+        for (key, value) in metadata {
+            tx.execute(&insert_query, params![entry_id.to_string(), key, value])
+                .map_err(|e| BeansError::database(format!("Failed to insert metadata: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Loads the metadata for an entry.
+    fn load_metadata(
+        &self,
+        tx: &Transaction,
+        entry_id: &Uuid,
+    ) -> BeansResult<HashMap<String, String>> {
+        let select_query = sql::Select::new()
+            .select("key, value")
+            .from("entry_metadata")
+            .where_clause("entry_id = ?")
+            .as_string();
+
+        let mut stmt = tx.prepare(&select_query).map_err(|e| {
+            BeansError::database(format!("Failed to prepare metadata query: {}", e))
+        })?;
+
+        let metadata = stmt
+            .query_map(params![entry_id.to_string()], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .map_err(|e| BeansError::database(format!("Failed to query metadata: {}", e)))?
+            .collect::<Result<HashMap<String, String>, _>>()
+            .map_err(|e| BeansError::database(format!("Failed to read metadata: {}", e)))?;
+
+        Ok(metadata)
+    }
+
     /// Loads the tags for an entry.
     fn load_tags(&self, tx: &Transaction, entry_id: &Uuid) -> BeansResult<Vec<Tag>> {
         let select_query = sql::Select::new()
@@ -169,10 +580,13 @@ impl SQLiteRepository {
         let name: String = row.get(2)?;
 
         let currency_code: String = row.get(3)?;
-        // Use the helper function to create a static currency
-        let currency = rusty_money::iso::find(&currency_code).ok_or(
-            rusqlite::Error::InvalidColumnType(3, "Invalid amount".to_string(), Type::Text),
-        )?;
+        let currency = find_iso_currency(&currency_code).ok_or_else(|| {
+            rusqlite::Error::InvalidColumnType(
+                3,
+                format!("Invalid or unknown currency '{}'", currency_code),
+                Type::Text,
+            )
+        })?;
         let amount_str: String = row.get(4)?;
         let amount = Decimal::from_str_exact(&amount_str).map_err(|_| {
             rusqlite::Error::InvalidColumnType(4, "Invalid amount".to_string(), Type::Text)
@@ -194,14 +608,14 @@ impl SQLiteRepository {
         };
 
         let created_at_str: String = row.get(7)?;
-        let _created_at = DateTime::parse_from_rfc3339(&created_at_str)
+        let created_at = DateTime::parse_from_rfc3339(&created_at_str)
             .map_err(|_| {
                 rusqlite::Error::InvalidColumnType(7, "Invalid created_at".to_string(), Type::Text)
             })?
             .with_timezone(&Utc);
 
         let updated_at_str: String = row.get(8)?;
-        let _updated_at = DateTime::parse_from_rfc3339(&updated_at_str)
+        let updated_at = DateTime::parse_from_rfc3339(&updated_at_str)
             .map_err(|_| {
                 rusqlite::Error::InvalidColumnType(8, "Invalid updated_at".to_string(), Type::Text)
             })?
@@ -213,6 +627,10 @@ impl SQLiteRepository {
             Err(_) => Vec::new(), // Fallback to empty tags on error
         };
 
+        let metadata = self
+            .load_metadata(tx, &id)
+            .map_err(|e| rusqlite::Error::InvalidColumnType(0, e.to_string(), Type::Text))?;
+
         // Build the entry
         let mut builder = LedgerEntryBuilder::new()
             .id(id)
@@ -220,7 +638,9 @@ impl SQLiteRepository {
             .name(name)
             .currency_code(currency.iso_alpha_code.to_owned())
             .amount(amount) // Add the amount to the builder
-            .entry_type(entry_type);
+            .entry_type(entry_type)
+            .created_at(created_at)
+            .updated_at(updated_at);
 
         if let Some(desc) = description {
             builder = builder.description(desc);
@@ -230,6 +650,10 @@ impl SQLiteRepository {
             builder = builder.tag(tag);
         }
 
+        for (key, value) in metadata {
+            builder = builder.metadata(key, value);
+        }
+
         match builder.build() {
             Ok(entry) => Ok(entry),
             Err(e) => Err(rusqlite::Error::InvalidColumnType(
@@ -240,17 +664,105 @@ impl SQLiteRepository {
         }
     }
 
+    /// Reads the entries matching the filter, keeping rows that fail to parse
+    /// as errors instead of aborting.
+    fn read_entries(
+        &self,
+        filter: &EntryFilter,
+    ) -> BeansResult<Vec<Result<LedgerEntry, RowError>>> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn
+            .transaction()
+            .map_err(|e| BeansError::database(format!("Failed to start transaction: {}", e)))?;
+
+        // Build the filtered query
+        let (mut select, params) = self.build_filtered_query(filter);
+
+        // Add ORDER BY, using insertion order to break ties within the same instant
+        select = match filter.sort {
+            SortField::Date => select.order_by("date DESC, rowid DESC"),
+            SortField::CreatedAt => select.order_by("created_at DESC, rowid DESC"),
+        };
+
+        // Add limit and offset if specified
+        // SQLite requires LIMIT when using OFFSET
+        if let Some(limit) = filter.limit {
+            select = select.limit(&limit.to_string());
+        } else if filter.offset.is_some() {
+            // If offset is specified but limit is not, use a large limit
+            select = select.limit("18446744073709551615"); // SQLite max LIMIT value (2^64-1)
+        }
+
+        if let Some(offset) = filter.offset {
+            select = select.offset(&offset.to_string());
+        }
+
+        let query = select.as_string();
+
+        // Prepare and execute the query
+        let mut stmt = tx
+            .prepare(&query)
+            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+
+        let param_refs: Vec<&dyn rusqlite::ToSql> = params
+            .iter()
+            .map(|p| p.as_ref() as &dyn rusqlite::ToSql)
+            .collect();
+
+        let mut rows = stmt
+            .query(rusqlite::params_from_iter(param_refs.iter()))
+            .map_err(|e| BeansError::database(format!("Failed to execute query: {}", e)))?;
+
+        let mut entries = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .map_err(|e| BeansError::database(format!("Failed to read entry: {}", e)))?
+        {
+            entries.push(self.row_to_entry(&tx, row).map_err(|e| RowError {
+                id: row.get(0).ok(),
+                message: e.to_string(),
+            }));
+        }
+
+        Ok(entries)
+    }
+
     /// Builds a SELECT query with filters applied.
     fn build_filtered_query(
         &self,
         filter: &EntryFilter,
     ) -> (sql::Select, Vec<Box<dyn rusqlite::ToSql>>) {
-        let mut select = sql::Select::new()
+        let select = sql::Select::new()
             .select(
                 "id, date, name, currency, amount, description, entry_type, created_at, updated_at",
             )
             .from("entries");
 
+        Self::apply_filter(select, filter)
+    }
+
+    /// Returns the scale at which the amounts of entries matching the filter
+    /// can be summed exactly, or `None` if no entry matches.
+    fn filtered_amount_scale(conn: &Connection, filter: &EntryFilter) -> BeansResult<Option<u32>> {
+        let select = sql::Select::new()
+            .select(&format!(
+                "MAX({}), MAX(ABS(amount_value))",
+                AMOUNT_SCALE_EXPR
+            ))
+            .from("entries");
+        let (select, params) = Self::apply_filter(select, filter);
+        amount_sum_scale(
+            conn,
+            &select.as_string(),
+            rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+        )
+    }
+
+    /// Adds the filter's conditions to a query over the entries table.
+    fn apply_filter(
+        mut select: sql::Select,
+        filter: &EntryFilter,
+    ) -> (sql::Select, Vec<Box<dyn rusqlite::ToSql>>) {
         let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
         if let Some(start_date) = filter.start_date {
@@ -273,6 +785,16 @@ impl SQLiteRepository {
             params.push(Box::new(currency.clone()));
         }
 
+        if let Some(name) = &filter.name_contains {
+            select = select.where_clause(NAME_CONTAINS_CLAUSE);
+            params.push(Box::new(like_pattern(name)));
+        }
+
+        if let Some(amount) = filter.exact_amount {
+            select = select.where_clause(EXACT_AMOUNT_CLAUSE);
+            params.extend(exact_amount_params(amount));
+        }
+
         // Handle tags filter if there are any tags
         if !filter.tags.is_empty() {
             let placeholders = vec!["?"; filter.tags.len()].join(", ");
@@ -297,6 +819,12 @@ impl SQLiteRepository {
             params.push(Box::new(filter.tags.len() as i64));
         }
 
+        for (key, value) in &filter.metadata {
+            select = select.where_clause(METADATA_SUBQUERY);
+            params.push(Box::new(key.clone()));
+            params.push(Box::new(value.clone()));
+        }
+
         (select, params)
     }
 }
@@ -308,33 +836,7 @@ impl Repository for SQLiteRepository {
             .transaction()
             .map_err(|e| BeansError::database(format!("Failed to start transaction: {}", e)))?;
 
-        // Insert the entry
-        let insert_query = sql::Insert::new()
-            .insert_into("entries (id, date, name, currency, amount, description, entry_type, created_at, updated_at)")
-            .values("(?, ?, ?, ?, ?, ?, ?, ?, ?)")
-            .as_string();
-
-        tx.execute(
-            &insert_query,
-            params![
-                entry.id().to_string(),
-                entry.date().to_rfc3339(),
-                entry.name(),
-                entry.currency_code(),
-                entry.amount().to_string(),
-                entry.description(),
-                format!("{:?}", entry.entry_type()),
-                entry.created_at().to_rfc3339(),
-                entry.updated_at().to_rfc3339(),
-            ],
-        )
-        .map_err(|e| BeansError::database(format!("Failed to insert entry: {}", e)))?;
-
-        // Convert HashSet<Tag> to Vec<Tag> for save_tags
-        let tags_vec: Vec<Tag> = entry.tags().iter().cloned().collect();
-
-        // Save tags
-        self.save_tags(&tx, &entry.id(), &tags_vec)?;
+        self.insert_entry(&tx, entry)?;
 
         // Commit the transaction
         tx.commit()
@@ -343,26 +845,90 @@ impl Repository for SQLiteRepository {
         Ok(())
     }
 
-    fn get(&self, id: Uuid) -> BeansResult<LedgerEntry> {
+    fn create_many(&self, entries: &[LedgerEntry]) -> BeansResult<()> {
         let mut conn = self.conn.lock().unwrap();
         let tx = conn
             .transaction()
             .map_err(|e| BeansError::database(format!("Failed to start transaction: {}", e)))?;
 
-        let select_query = sql::Select::new()
-            .select(
-                "id, date, name, currency, amount, description, entry_type, created_at, updated_at",
-            )
-            .from("entries")
-            .where_clause("id = ?")
-            .as_string();
+        // Any failure drops the transaction, rolling back the whole batch
+        for entry in entries {
+            self.insert_entry(&tx, entry)?;
+        }
 
-        let mut stmt = tx
-            .prepare(&select_query)
-            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+        tx.commit()
+            .map_err(|e| BeansError::database(format!("Failed to commit transaction: {}", e)))?;
 
-        let entry = stmt
-            .query_row(params![id.to_string()], |row| self.row_to_entry(&tx, row))
+        Ok(())
+    }
+
+    fn create_many_deferred(&self, entries: &[LedgerEntry]) -> BeansResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+
+        // The pragma is a no-op inside a transaction, so toggle it around one
+        let mut foreign_keys_off = ForeignKeysOff::new(&mut conn)?;
+        let result = self.insert_and_check_foreign_keys(foreign_keys_off.conn, entries);
+        let restored = foreign_keys_off.restore();
+
+        // A failed batch is the more useful error to report
+        result?;
+        restored
+    }
+
+    fn replace(&self, ids: &[Uuid], entries: &[LedgerEntry]) -> BeansResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn
+            .transaction()
+            .map_err(|e| BeansError::database(format!("Failed to start transaction: {}", e)))?;
+
+        let delete_query = sql::Delete::new()
+            .delete_from("entries")
+            .where_clause("id = ?")
+            .as_string();
+
+        // Any failure drops the transaction, leaving the ledger untouched
+        for id in ids {
+            let deleted = tx
+                .execute(&delete_query, params![id.to_string()])
+                .map_err(|e| BeansError::database(format!("Failed to delete entry: {}", e)))?;
+            if deleted == 0 {
+                return Err(BeansError::not_found(format!(
+                    "Entry with ID {} not found",
+                    id
+                )));
+            }
+        }
+
+        for entry in entries {
+            self.insert_entry(&tx, entry)?;
+        }
+
+        tx.commit()
+            .map_err(|e| BeansError::database(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn get(&self, id: Uuid) -> BeansResult<LedgerEntry> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn
+            .transaction()
+            .map_err(|e| BeansError::database(format!("Failed to start transaction: {}", e)))?;
+
+        let select_query = sql::Select::new()
+            .select(
+                "id, date, name, currency, amount, description, entry_type, created_at, updated_at",
+            )
+            .from("entries")
+            .where_clause("id = ?")
+            .as_string();
+
+        let mut stmt = tx
+            .prepare(&select_query)
+            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+
+        let entry = stmt
+            .query_row(params![id.to_string()], |row| self.row_to_entry(&tx, row))
             .map_err(|e| match e {
                 rusqlite::Error::QueryReturnedNoRows => {
                     BeansError::not_found(format!("Entry with ID {} not found", id))
@@ -400,7 +966,7 @@ impl Repository for SQLiteRepository {
         // Update the entry
         let update_query = sql::Update::new()
             .update("entries")
-            .set("date = ?, name = ?, currency = ?, amount = ?, description = ?, entry_type = ?, updated_at = ?")
+            .set("date = ?, name = ?, currency = ?, amount = ?, amount_value = ?, description = ?, entry_type = ?, updated_at = ?")
             .where_clause("id = ?")
             .as_string();
 
@@ -411,6 +977,7 @@ impl Repository for SQLiteRepository {
                 entry.name(),
                 entry.currency_code(),
                 entry.amount().to_string(),
+                entry.amount().to_f64(),
                 entry.description(),
                 format!("{:?}", entry.entry_type()),
                 entry.updated_at().to_rfc3339(),
@@ -422,8 +989,9 @@ impl Repository for SQLiteRepository {
         // Convert HashSet<Tag> to Vec<Tag> for save_tags
         let tags_vec: Vec<Tag> = entry.tags().iter().cloned().collect();
 
-        // Save tags
+        // Save tags and metadata
         self.save_tags(&tx, &entry.id(), &tags_vec)?;
+        self.save_metadata(&tx, &entry.id(), entry.metadata())?;
 
         // Commit the transaction
         tx.commit()
@@ -466,34 +1034,210 @@ impl Repository for SQLiteRepository {
     }
 
     fn list(&self, filter: &EntryFilter) -> BeansResult<Vec<LedgerEntry>> {
+        self.read_entries(filter)?
+            .into_iter()
+            .map(|entry| {
+                entry.map_err(|e| {
+                    BeansError::database(format!("Failed to read entry: {}", e.message))
+                })
+            })
+            .collect()
+    }
+
+    fn list_lenient(&self, filter: &EntryFilter) -> BeansResult<(Vec<LedgerEntry>, Vec<RowError>)> {
+        let mut entries = Vec::new();
+        let mut errors = Vec::new();
+        for entry in self.read_entries(filter)? {
+            match entry {
+                Ok(entry) => entries.push(entry),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        Ok((entries, errors))
+    }
+
+    fn amount_total(&self, filter: &EntryFilter) -> BeansResult<(Decimal, usize)> {
+        let conn = self.conn.lock().unwrap();
+
+        let Some(scale) = Self::filtered_amount_scale(&conn, filter)? else {
+            return Ok((Decimal::ZERO, 0));
+        };
+
+        let select = sql::Select::new()
+            .select(&format!("{}, COUNT(*)", amount_units_sum(scale)))
+            .from("entries");
+        let (select, params) = Self::apply_filter(select, filter);
+        let (units, count): (i64, i64) = conn
+            .query_row(
+                &select.as_string(),
+                rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| BeansError::database(format!("Failed to total amounts: {}", e)))?;
+
+        Ok((Decimal::new(units, scale), count as usize))
+    }
+
+    fn daily_totals(&self, filter: &EntryFilter) -> BeansResult<Vec<DailyTotal>> {
+        let conn = self.conn.lock().unwrap();
+
+        let Some(scale) = Self::filtered_amount_scale(&conn, filter)? else {
+            return Ok(Vec::new());
+        };
+
+        let select = sql::Select::new()
+            .select("date(date) AS day, entry_type")
+            .select(&format!("{}, COUNT(*)", amount_units_sum(scale)))
+            .from("entries");
+        let (select, params) = Self::apply_filter(select, filter);
+        let query = select
+            .group_by("day, entry_type")
+            .order_by("day, entry_type")
+            .as_string();
+
+        let mut stmt = conn
+            .prepare(&query)
+            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+        let rows = stmt
+            .query_map(
+                rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, i64>(2)?,
+                        row.get::<_, i64>(3)?,
+                    ))
+                },
+            )
+            .map_err(|e| BeansError::database(format!("Failed to query daily totals: {}", e)))?;
+
+        let mut totals = Vec::new();
+        for row in rows {
+            let (day, entry_type, units, count) = row
+                .map_err(|e| BeansError::database(format!("Failed to read daily total: {}", e)))?;
+            let day = NaiveDate::parse_from_str(&day, "%Y-%m-%d")
+                .map_err(|e| BeansError::database(format!("Invalid date in database: {}", e)))?;
+            totals.push(DailyTotal {
+                day,
+                entry_type: parse_entry_type(&entry_type)?,
+                total: Decimal::new(units, scale),
+                count: count as usize,
+            });
+        }
+
+        Ok(totals)
+    }
+
+    fn rebuild_monthly_summaries(&self) -> BeansResult<()> {
         let mut conn = self.conn.lock().unwrap();
         let tx = conn
             .transaction()
             .map_err(|e| BeansError::database(format!("Failed to start transaction: {}", e)))?;
 
-        // Build the filtered query
-        let (mut select, params) = self.build_filtered_query(filter);
+        tx.execute_batch(
+            "DELETE FROM monthly_summaries;
+             DELETE FROM monthly_summaries_stale;
+             DELETE FROM monthly_summaries_built;",
+        )
+        .map_err(|e| BeansError::database(format!("Failed to clear monthly summaries: {}", e)))?;
+        Self::store_monthly_summaries(&tx, None)?;
+        tx.execute(
+            "INSERT INTO monthly_summaries_built (built_at) VALUES (?1)",
+            params![Utc::now().to_rfc3339()],
+        )
+        .map_err(|e| BeansError::database(format!("Failed to mark monthly summaries: {}", e)))?;
 
-        // Add ORDER BY
-        select = select.order_by("date DESC");
+        tx.commit()
+            .map_err(|e| BeansError::database(format!("Failed to commit transaction: {}", e)))?;
 
-        // Add limit and offset if specified
-        // SQLite requires LIMIT when using OFFSET
-        if let Some(limit) = filter.limit {
-            select = select.limit(&limit.to_string());
-        } else if filter.offset.is_some() {
-            // If offset is specified but limit is not, use a large limit
-            select = select.limit("18446744073709551615"); // SQLite max LIMIT value (2^64-1)
+        Ok(())
+    }
+
+    fn monthly_summaries(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> BeansResult<Option<Vec<MonthlySummary>>> {
+        let mut conn = self.conn.lock().unwrap();
+
+        let built: bool = conn
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM monthly_summaries_built)",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| {
+                BeansError::database(format!("Failed to check monthly summaries: {}", e))
+            })?;
+        if !built {
+            return Ok(None);
         }
+        Self::refresh_stale_monthly_summaries(&mut conn)?;
+
+        let query = sql::Select::new()
+            .select("month, currency, entry_type, total, count")
+            .from("monthly_summaries")
+            .where_clause("month >= ?1")
+            .where_clause("month <= ?2")
+            .order_by("month, currency, entry_type")
+            .as_string();
+        let mut stmt = conn
+            .prepare(&query)
+            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+        let rows = stmt
+            .query_map(
+                params![
+                    start.format("%Y-%m-01").to_string(),
+                    end.format("%Y-%m-01").to_string()
+                ],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, i64>(4)?,
+                    ))
+                },
+            )
+            .map_err(|e| {
+                BeansError::database(format!("Failed to query monthly summaries: {}", e))
+            })?;
 
-        if let Some(offset) = filter.offset {
-            select = select.offset(&offset.to_string());
+        let mut summaries = Vec::new();
+        for row in rows {
+            let (month, currency, entry_type, total, count) = row.map_err(|e| {
+                BeansError::database(format!("Failed to read monthly summary: {}", e))
+            })?;
+            summaries.push(MonthlySummary {
+                month: NaiveDate::parse_from_str(&month, "%Y-%m-%d").map_err(|e| {
+                    BeansError::database(format!("Invalid month in database: {}", e))
+                })?,
+                currency,
+                entry_type: parse_entry_type(&entry_type)?,
+                total: Decimal::from_str_exact(&total).map_err(|e| {
+                    BeansError::database(format!("Invalid amount in database: {}", e))
+                })?,
+                count: count as usize,
+            });
         }
 
-        let query = select.as_string();
+        Ok(Some(summaries))
+    }
+
+    fn count(&self, filter: &EntryFilter) -> BeansResult<usize> {
+        let conn = self.conn.lock().unwrap();
+
+        let (count_select, params) = Self::apply_filter(
+            sql::Select::new().select("COUNT(*)").from("entries"),
+            filter,
+        );
+        let query = count_select.as_string();
 
         // Prepare and execute the query
-        let mut stmt = tx
+        let mut stmt = conn
             .prepare(&query)
             .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
 
@@ -502,84 +1246,404 @@ impl Repository for SQLiteRepository {
             .map(|p| p.as_ref() as &dyn rusqlite::ToSql)
             .collect();
 
-        let rows = stmt
-            .query(rusqlite::params_from_iter(param_refs.iter()))
-            .map_err(|e| BeansError::database(format!("Failed to execute query: {}", e)))?;
+        let count: i64 = stmt
+            .query_row(rusqlite::params_from_iter(param_refs.iter()), |row| {
+                row.get(0)
+            })
+            .map_err(|e| BeansError::database(format!("Failed to count entries: {}", e)))?;
 
-        let mut entries = Vec::new();
-        for row_result in rows.mapped(|row| self.row_to_entry(&tx, row)) {
-            match row_result {
-                Ok(entry) => entries.push(entry),
-                Err(e) => return Err(BeansError::database(format!("Failed to read entry: {}", e))),
+        Ok(count as usize)
+    }
+
+    fn date_span(&self) -> BeansResult<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+        let conn = self.conn.lock().unwrap();
+        Self::query_date_span(&conn)
+    }
+
+    fn ids_with_prefix(&self, prefix: &str) -> BeansResult<Vec<Uuid>> {
+        let conn = self.conn.lock().unwrap();
+
+        let query = sql::Select::new()
+            .select("id")
+            .from("entries")
+            .where_clause("id >= ?")
+            .where_clause("id < ?")
+            .order_by("id")
+            .as_string();
+
+        let mut stmt = conn
+            .prepare(&query)
+            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+        let ids = stmt
+            // A range rather than a string function, so the primary key index
+            // is used; every ID with the prefix sorts below prefix + U+10FFFF
+            .query_map(params![prefix, format!("{}{}", prefix, char::MAX)], |row| {
+                row.get::<_, String>(0)
+            })
+            .map_err(|e| BeansError::database(format!("Failed to query entry IDs: {}", e)))?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| BeansError::database(format!("Failed to read entry ID: {}", e)))?;
+
+        ids.iter()
+            .map(|id| {
+                Uuid::parse_str(id).map_err(|_| {
+                    BeansError::database(format!("Invalid entry ID in database: {}", id))
+                })
+            })
+            .collect()
+    }
+
+    fn stats(&self) -> BeansResult<LedgerStats> {
+        let conn = self.conn.lock().unwrap();
+
+        // Entry count and date span
+        let count_query = sql::Select::new()
+            .select("COUNT(*)")
+            .from("entries")
+            .as_string();
+
+        let entry_count: i64 = conn
+            .query_row(&count_query, [], |row| row.get(0))
+            .map_err(|e| BeansError::database(format!("Failed to count entries: {}", e)))?;
+
+        let date_span = Self::query_date_span(&conn)?;
+
+        // Distinct currencies
+        let currencies_query = sql::Select::new()
+            .select("DISTINCT currency")
+            .from("entries")
+            .order_by("currency")
+            .as_string();
+
+        let mut stmt = conn
+            .prepare(&currencies_query)
+            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+        let currencies = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| BeansError::database(format!("Failed to query currencies: {}", e)))?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| BeansError::database(format!("Failed to read currency: {}", e)))?;
+
+        // Distinct tags in use
+        let tags_query = sql::Select::new()
+            .select("DISTINCT t.name")
+            .from("tags t")
+            .inner_join("entry_tags et ON t.id = et.tag_id")
+            .order_by("t.name")
+            .as_string();
+
+        let mut stmt = conn
+            .prepare(&tags_query)
+            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+        let tags = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| BeansError::database(format!("Failed to query tags: {}", e)))?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| BeansError::database(format!("Failed to read tag: {}", e)))?;
+
+        // Net per currency. Amounts are stored as text, so they are summed
+        // here to keep decimal precision.
+        let amounts_query = sql::Select::new()
+            .select("currency, entry_type, amount")
+            .from("entries")
+            .as_string();
+
+        let mut stmt = conn
+            .prepare(&amounts_query)
+            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .map_err(|e| BeansError::database(format!("Failed to query amounts: {}", e)))?;
+
+        let mut net_by_currency: HashMap<String, Decimal> = HashMap::new();
+        for row in rows {
+            let (currency, entry_type, amount) =
+                row.map_err(|e| BeansError::database(format!("Failed to read amount: {}", e)))?;
+            let amount = Decimal::from_str_exact(&amount)
+                .map_err(|e| BeansError::database(format!("Invalid amount in database: {}", e)))?;
+
+            let net = net_by_currency.entry(currency).or_insert(Decimal::ZERO);
+            match entry_type.as_str() {
+                "Income" => *net += amount,
+                _ => *net -= amount,
             }
         }
 
-        Ok(entries)
+        Ok(LedgerStats {
+            entry_count: entry_count as usize,
+            date_span,
+            currencies,
+            tags,
+            net_by_currency,
+        })
     }
 
-    fn count(&self, filter: &EntryFilter) -> BeansResult<usize> {
+    fn validate(&self) -> BeansResult<Vec<ValidationIssue>> {
         let conn = self.conn.lock().unwrap();
+        let mut issues = Vec::new();
+        let mut issue = |entry_id: &str, kind: IssueKind, message: String| {
+            issues.push(ValidationIssue {
+                entry_id: entry_id.to_string(),
+                kind,
+                message,
+            })
+        };
 
-        // Build the filtered query but change SELECT to COUNT(*)
-        let (_, params) = self.build_filtered_query(filter);
+        // Fields of every entry
+        let entries_query = sql::Select::new()
+            .select("id, date, currency, amount, entry_type")
+            .from("entries")
+            .order_by("id")
+            .as_string();
+
+        let mut stmt = conn
+            .prepare(&entries_query)
+            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })
+            .map_err(|e| BeansError::database(format!("Failed to query entries: {}", e)))?;
 
-        // We need to replace the SELECT clause with COUNT(*)
-        // Since sql_query_builder doesn't have a direct way to do this,
-        // we'll build a new query using the filter conditions
-        let mut count_select = sql::Select::new().select("COUNT(*)").from("entries");
+        for row in rows {
+            let (id, date, currency, amount, entry_type) =
+                row.map_err(|e| BeansError::database(format!("Failed to read entry: {}", e)))?;
 
-        // Re-apply the same filters
-        if let Some(_) = filter.start_date {
-            count_select = count_select.where_clause("date >= ?");
+            if Uuid::parse_str(&id).is_err() {
+                issue(&id, IssueKind::InvalidId, format!("Invalid ID '{}'", id));
+            }
+            if DateTime::parse_from_rfc3339(&date).is_err() {
+                issue(
+                    &id,
+                    IssueKind::InvalidDate,
+                    format!("Invalid date '{}'", date),
+                );
+            }
+            if find_iso_currency(&currency).is_none() {
+                issue(
+                    &id,
+                    IssueKind::InvalidCurrency,
+                    format!("Invalid or unknown currency '{}'", currency),
+                );
+            }
+            match Decimal::from_str_exact(&amount) {
+                Ok(value) if value <= Decimal::ZERO => issue(
+                    &id,
+                    IssueKind::NonPositiveAmount,
+                    format!("Amount {} is not positive", amount),
+                ),
+                Ok(_) => {}
+                Err(_) => issue(
+                    &id,
+                    IssueKind::InvalidAmount,
+                    format!("Invalid amount '{}'", amount),
+                ),
+            }
+            if !matches!(entry_type.as_str(), "Income" | "Expense") {
+                issue(
+                    &id,
+                    IssueKind::InvalidEntryType,
+                    format!("Invalid entry type '{}'", entry_type),
+                );
+            }
         }
 
-        if let Some(_) = filter.end_date {
-            count_select = count_select.where_clause("date <= ?");
-        }
+        // Links to tags and entries that don't exist
+        let dangling_query = "
+            SELECT et.entry_id, 'tag ' || et.tag_id, 0 FROM entry_tags et
+            WHERE NOT EXISTS (SELECT 1 FROM tags t WHERE t.id = et.tag_id)
+            UNION ALL
+            SELECT et.entry_id, 'tag link', 1 FROM entry_tags et
+            WHERE NOT EXISTS (SELECT 1 FROM entries e WHERE e.id = et.entry_id)
+            UNION ALL
+            SELECT em.entry_id, 'metadata key ' || em.key, 1 FROM entry_metadata em
+            WHERE NOT EXISTS (SELECT 1 FROM entries e WHERE e.id = em.entry_id)
+            ORDER BY 1";
 
-        if let Some(_) = &filter.entry_type {
-            count_select = count_select.where_clause("entry_type = ?");
+        let mut stmt = conn
+            .prepare(dangling_query)
+            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, bool>(2)?,
+                ))
+            })
+            .map_err(|e| BeansError::database(format!("Failed to query links: {}", e)))?;
+
+        for row in rows {
+            let (id, what, missing_entry) =
+                row.map_err(|e| BeansError::database(format!("Failed to read link: {}", e)))?;
+            if missing_entry {
+                issue(
+                    &id,
+                    IssueKind::MissingEntry,
+                    format!("The {} belongs to a missing entry", what),
+                );
+            } else {
+                issue(
+                    &id,
+                    IssueKind::MissingTag,
+                    format!("Linked to missing {}", what),
+                );
+            }
         }
 
-        if let Some(_) = &filter.currency {
-            count_select = count_select.where_clause("currency = ?");
-        }
+        Ok(issues)
+    }
 
-        // Handle tags filter if there are any tags
-        if !filter.tags.is_empty() {
-            let placeholders = vec!["?"; filter.tags.len()].join(", ");
-            let tag_subquery = format!(
-                "id IN (
-                    SELECT entry_id FROM entry_tags
-                    JOIN tags ON entry_tags.tag_id = tags.id
-                    WHERE tags.name IN ({})
-                    GROUP BY entry_id
-                    HAVING COUNT(DISTINCT tags.name) = ?
-                )",
-                placeholders
-            );
+    fn reindex(&self) -> BeansResult<()> {
+        let conn = self.conn.lock().unwrap();
 
-            count_select = count_select.where_clause(&tag_subquery);
-        }
+        // REINDEX without arguments rebuilds every index in the database,
+        // which is harmless on a freshly created ledger.
+        conn.execute_batch("REINDEX")
+            .map_err(|e| BeansError::database(format!("Failed to reindex database: {}", e)))?;
 
-        let query = count_select.as_string();
+        Ok(())
+    }
+
+    fn save_filter_preset(&self, name: &str, filter: &EntryFilter) -> BeansResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let filter_json = serde_json::to_string(filter)
+            .map_err(|e| BeansError::Json(format!("Failed to serialize filter: {}", e)))?;
+
+        // Saving under an existing name overwrites the previous preset
+        let insert_query = sql::Insert::new()
+            .raw("INSERT OR REPLACE INTO filter_presets (name, filter, updated_at)")
+            .values("(?, ?, ?)")
+            .as_string();
+
+        conn.execute(
+            &insert_query,
+            params![name, filter_json, Utc::now().to_rfc3339()],
+        )
+        .map_err(|e| BeansError::database(format!("Failed to save filter preset: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn load_filter_preset(&self, name: &str) -> BeansResult<EntryFilter> {
+        let conn = self.conn.lock().unwrap();
+
+        let select_query = sql::Select::new()
+            .select("filter")
+            .from("filter_presets")
+            .where_clause("name = ?")
+            .as_string();
+
+        let filter_json: String = conn
+            .query_row(&select_query, params![name], |row| row.get(0))
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => {
+                    BeansError::not_found(format!("Filter preset '{}' not found", name))
+                }
+                _ => BeansError::database(format!("Failed to load filter preset: {}", e)),
+            })?;
+
+        serde_json::from_str(&filter_json)
+            .map_err(|e| BeansError::Json(format!("Failed to parse filter preset: {}", e)))
+    }
+
+    fn list_filter_presets(&self) -> BeansResult<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+
+        let select_query = sql::Select::new()
+            .select("name")
+            .from("filter_presets")
+            .order_by("name")
+            .as_string();
 
-        // Prepare and execute the query
         let mut stmt = conn
-            .prepare(&query)
+            .prepare(&select_query)
             .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
 
-        let param_refs: Vec<&dyn rusqlite::ToSql> = params
-            .iter()
-            .map(|p| p.as_ref() as &dyn rusqlite::ToSql)
-            .collect();
+        let names = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| BeansError::database(format!("Failed to query filter presets: {}", e)))?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| BeansError::database(format!("Failed to read filter preset: {}", e)))?;
 
-        let count: i64 = stmt
-            .query_row(rusqlite::params_from_iter(param_refs.iter()), |row| {
-                row.get(0)
-            })
-            .map_err(|e| BeansError::database(format!("Failed to count entries: {}", e)))?;
+        Ok(names)
+    }
 
-        Ok(count as usize)
+    fn save_template(&self, name: &str, template: &EntryTemplate) -> BeansResult<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let template_json = serde_json::to_string(template)
+            .map_err(|e| BeansError::Json(format!("Failed to serialize template: {}", e)))?;
+
+        // Saving under an existing name overwrites the previous template
+        let insert_query = sql::Insert::new()
+            .raw("INSERT OR REPLACE INTO templates (name, template, updated_at)")
+            .values("(?, ?, ?)")
+            .as_string();
+
+        conn.execute(
+            &insert_query,
+            params![name, template_json, Utc::now().to_rfc3339()],
+        )
+        .map_err(|e| BeansError::database(format!("Failed to save template: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn load_template(&self, name: &str) -> BeansResult<EntryTemplate> {
+        let conn = self.conn.lock().unwrap();
+
+        let select_query = sql::Select::new()
+            .select("template")
+            .from("templates")
+            .where_clause("name = ?")
+            .as_string();
+
+        let template_json: String = conn
+            .query_row(&select_query, params![name], |row| row.get(0))
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => {
+                    BeansError::not_found(format!("Template '{}' not found", name))
+                }
+                _ => BeansError::database(format!("Failed to load template: {}", e)),
+            })?;
+
+        serde_json::from_str(&template_json)
+            .map_err(|e| BeansError::Json(format!("Failed to parse template: {}", e)))
+    }
+
+    fn list_templates(&self) -> BeansResult<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+
+        let select_query = sql::Select::new()
+            .select("name")
+            .from("templates")
+            .order_by("name")
+            .as_string();
+
+        let mut stmt = conn
+            .prepare(&select_query)
+            .map_err(|e| BeansError::database(format!("Failed to prepare query: {}", e)))?;
+
+        let names = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| BeansError::database(format!("Failed to query templates: {}", e)))?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| BeansError::database(format!("Failed to read template: {}", e)))?;
+
+        Ok(names)
     }
 }
@@ -3,12 +3,17 @@
 //! This module defines all error types used throughout the library,
 //! providing clear and actionable error messages.
 
+use serde::{Deserialize, Serialize, Serializer};
 use thiserror::Error;
 
 /// Result type alias for Beans operations.
 pub type BeansResult<T> = Result<T, BeansError>;
 
 /// Main error type for the Beans library.
+///
+/// Errors serialize as an [`ErrorInfo`] object, e.g.
+/// `{"kind": "NotFound", "message": "Entry not found: ..."}`, so frontends
+/// can receive them as structured data.
 #[derive(Error, Debug)]
 pub enum BeansError {
     /// Database-related errors from SQLite.
@@ -59,6 +64,10 @@ pub enum BeansError {
     #[error("Invalid date range: start date must be before end date")]
     InvalidDateRange,
 
+    /// A report would have more periods than allowed.
+    #[error("Report range too large: {buckets} periods requested, at most {max} allowed")]
+    RangeTooLarge { buckets: usize, max: usize },
+
     /// Generic error for other cases.
     #[error("Operation failed: {0}")]
     Other(String),
@@ -97,4 +106,57 @@ impl BeansError {
     pub fn other(msg: impl Into<String>) -> Self {
         Self::Other(msg.into())
     }
+
+    /// Returns a stable identifier for the kind of error.
+    ///
+    /// Unlike the display message, the kind never changes between releases,
+    /// so frontends can branch on it (e.g. to show a specific dialog).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Database(_) | Self::DatabaseCustom(_) => "Database",
+            Self::Io(_) => "Io",
+            Self::Validation(_) => "Validation",
+            Self::Currency(_) => "Currency",
+            Self::Network(_) => "Network",
+            Self::Json(_) => "Json",
+            Self::LedgerNotFound(_) => "LedgerNotFound",
+            Self::InvalidLedgerFormat(_) => "InvalidLedgerFormat",
+            Self::NotFound(_) => "NotFound",
+            Self::ExchangeRateUnavailable { .. } => "ExchangeRateUnavailable",
+            Self::InvalidDateRange => "InvalidDateRange",
+            Self::RangeTooLarge { .. } => "RangeTooLarge",
+            Self::Other(_) => "Other",
+            Self::NotImplemented(_) => "NotImplemented",
+            Self::ConversionError(_) => "Conversion",
+        }
+    }
+}
+
+/// Serializable summary of a [`BeansError`].
+///
+/// The wrapped SQLite, I/O and network errors can't be serialized, so an
+/// error is represented by its stable [`kind`](BeansError::kind) and its
+/// display message. Frontends should branch on, or localize by, the kind
+/// and treat the message as detail.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorInfo {
+    /// Stable identifier for the kind of error, see [`BeansError::kind`].
+    pub kind: String,
+    /// Human-readable description of the error.
+    pub message: String,
+}
+
+impl From<&BeansError> for ErrorInfo {
+    fn from(error: &BeansError) -> Self {
+        Self {
+            kind: error.kind().to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl Serialize for BeansError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ErrorInfo::from(self).serialize(serializer)
+    }
 }
@@ -0,0 +1,139 @@
+//! Plain, string-based entry data for frontends.
+
+use crate::error::{BeansError, BeansResult};
+use crate::models::{EntryType, LedgerEntry, LedgerEntryBuilder, Tag};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Entry fields as a frontend collects them, before any parsing.
+///
+/// Converting to a [`LedgerEntry`] with `LedgerEntry::try_from` parses and
+/// validates every field, so all frontends share the same rules and errors.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntryData {
+    /// Entry ID, or `None` for a new entry.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Date as `YYYY-MM-DD` or an RFC 3339 timestamp.
+    ///
+    /// A date-only value is placed at the time of day given on conversion,
+    /// midnight UTC for `LedgerEntry::try_from` or the ledger's default time
+    /// of day for `LedgerManager::entry_from_data`. If it is today's date,
+    /// the time is capped at now so the entry isn't in the future.
+    pub date: String,
+    /// Name/title of the transaction.
+    pub name: String,
+    /// Currency of the transaction (ISO Code, any case).
+    pub currency_code: String,
+    /// Amount of the transaction as a decimal string.
+    pub amount: String,
+    /// Optional description; blank descriptions are dropped.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Type of the transaction, `income` or `expense`.
+    pub entry_type: String,
+    /// Tag names for the transaction.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl EntryData {
+    /// Converts to a [`LedgerEntry`], placing date-only values at the given
+    /// UTC time of day.
+    ///
+    /// `LedgerEntry::try_from` uses midnight. A later time such as noon keeps
+    /// date-only entries on the same calendar day when viewed from timezones
+    /// behind UTC.
+    pub fn into_entry(self, default_time_of_day: NaiveTime) -> BeansResult<LedgerEntry> {
+        self.to_builder(default_time_of_day)?.build()
+    }
+
+    /// Parses every field into a builder, placing date-only values at the
+    /// given UTC time of day.
+    pub(crate) fn to_builder(
+        &self,
+        default_time_of_day: NaiveTime,
+    ) -> BeansResult<LedgerEntryBuilder> {
+        let amount = Decimal::from_str(self.amount.trim())
+            .map_err(|_| BeansError::validation(format!("Invalid amount: {}", self.amount)))?;
+        let entry_type = EntryType::from_str(self.entry_type.trim())?;
+        let tags = self
+            .tags
+            .iter()
+            .map(Tag::new)
+            .collect::<BeansResult<Vec<_>>>()?;
+
+        let mut builder = LedgerEntryBuilder::new()
+            .date(EntryData::parse_date(&self.date, default_time_of_day)?)
+            .name(self.name.trim())
+            .currency_code(self.currency_code.trim().to_uppercase())
+            .amount(amount)
+            .entry_type(entry_type)
+            .tags(tags);
+
+        if let Some(id) = &self.id {
+            let id = Uuid::parse_str(id.trim())
+                .map_err(|_| BeansError::validation(format!("Invalid entry ID: {}", id)))?;
+            builder = builder.id(id);
+        }
+
+        if let Some(description) = self.description.as_ref().filter(|d| !d.trim().is_empty()) {
+            builder = builder.description(description);
+        }
+
+        Ok(builder)
+    }
+
+    /// Parses a date given as `YYYY-MM-DD` or as an RFC 3339 timestamp.
+    ///
+    /// Date-only values are placed at `time_of_day` UTC. For today's date
+    /// that time may not have come yet, so it is capped at the current time;
+    /// otherwise entries made for today would be rejected as future-dated.
+    fn parse_date(date: &str, time_of_day: NaiveTime) -> BeansResult<DateTime<Utc>> {
+        let date = date.trim();
+        if let Ok(day) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            let date = day.and_time(time_of_day).and_utc();
+            let now = Utc::now();
+            if day == now.date_naive() {
+                return Ok(date.min(now));
+            }
+            return Ok(date);
+        }
+
+        DateTime::parse_from_rfc3339(date)
+            .map(|d| d.with_timezone(&Utc))
+            .map_err(|_| BeansError::validation(format!("Invalid date: {}", date)))
+    }
+}
+
+impl TryFrom<EntryData> for LedgerEntry {
+    type Error = BeansError;
+
+    fn try_from(data: EntryData) -> BeansResult<Self> {
+        data.into_entry(NaiveTime::MIN)
+    }
+}
+
+impl From<&LedgerEntry> for EntryData {
+    fn from(entry: &LedgerEntry) -> Self {
+        let tags = entry
+            .sorted_tags()
+            .iter()
+            .map(|t| t.name().to_string())
+            .collect();
+
+        Self {
+            id: Some(entry.id().to_string()),
+            date: entry.date().to_rfc3339(),
+            name: entry.name().to_string(),
+            currency_code: entry.currency_code(),
+            amount: entry.amount().to_string(),
+            description: entry.description().map(str::to_string),
+            entry_type: entry.entry_type().to_string(),
+            tags,
+        }
+    }
+}
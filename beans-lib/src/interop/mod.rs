@@ -0,0 +1,5 @@
+//! Plain data types shared by frontends and the library.
+
+mod entry_data;
+
+pub use entry_data::EntryData;
@@ -0,0 +1,168 @@
+//! Types describing the outcome of bulk operations on a ledger, and the
+//! input rows accepted by imports.
+
+use crate::error::BeansResult;
+use crate::models::{EntryType, LedgerEntry, LedgerEntryBuilder, Tag};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A row that could not be added during a bulk operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportFailure {
+    /// Position of the row in the input, starting at 0.
+    pub index: usize,
+    /// Reason the row was rejected.
+    pub message: String,
+}
+
+/// A tag that was changed to make it valid during an import.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SanitizedTag {
+    /// Position of the row in the input, starting at 0.
+    pub index: usize,
+    /// Tag as given in the input.
+    pub original: String,
+    /// Tag the entry was given instead.
+    pub tag: Tag,
+}
+
+/// Summary of a bulk operation, with the outcome of every row.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportSummary {
+    /// IDs of the entries that were added, in input order.
+    pub added: Vec<Uuid>,
+    /// Rows that were rejected.
+    pub failed: Vec<ImportFailure>,
+    /// Positions of the rows that were intentionally skipped.
+    pub skipped: Vec<usize>,
+    /// Tags that were rewritten under [`TagPolicy::Sanitize`].
+    pub sanitized_tags: Vec<SanitizedTag>,
+}
+
+impl ImportSummary {
+    /// Returns the total number of rows processed.
+    pub fn total(&self) -> usize {
+        self.added.len() + self.failed.len() + self.skipped.len()
+    }
+
+    /// Returns true if no row was rejected.
+    ///
+    /// Skipped rows don't count as failures.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// How imports handle tags that aren't valid tag names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TagPolicy {
+    /// Reject the row.
+    #[default]
+    Strict,
+    /// Rewrite the tag with [`Tag::sanitize`] and keep the row.
+    Sanitize,
+}
+
+/// Options controlling how rows are imported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportOptions {
+    /// Skip rows with a zero amount instead of rejecting them.
+    ///
+    /// Bank exports often include zero-amount memo rows. Defaults to true.
+    pub skip_zero_amounts: bool,
+    /// Check referential integrity once after the batch instead of on every insert.
+    ///
+    /// Speeds up large imports. Defaults to false.
+    pub defer_foreign_keys: bool,
+    /// What to do with invalid tags, such as `Food & Drink`.
+    ///
+    /// Defaults to [`TagPolicy::Strict`].
+    pub tag_policy: TagPolicy,
+    /// Tags added to every imported entry, on top of its own, e.g. a batch
+    /// marker like `import-2024-06` to find or remove the batch later.
+    ///
+    /// Defaults to none.
+    pub extra_tags: Vec<Tag>,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            skip_zero_amounts: true,
+            defer_foreign_keys: false,
+            tag_policy: TagPolicy::Strict,
+            extra_tags: Vec::new(),
+        }
+    }
+}
+
+/// A raw row to import into a ledger.
+///
+/// Unlike [`LedgerEntry`], a record is not validated until it is imported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportRecord {
+    /// Date and time of the transaction.
+    pub date: DateTime<Utc>,
+    /// Name/title of the transaction.
+    pub name: String,
+    /// Currency of the transaction (ISO Code).
+    pub currency_code: String,
+    /// Amount of the transaction.
+    pub amount: Decimal,
+    /// Optional description of the transaction.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Tag names for the transaction.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Type of the transaction.
+    pub entry_type: EntryType,
+}
+
+impl ImportRecord {
+    /// Builds a ledger entry from this record.
+    pub fn to_entry(&self) -> BeansResult<LedgerEntry> {
+        self.to_entry_with_policy(TagPolicy::Strict)
+            .map(|(entry, _)| entry)
+    }
+
+    /// Builds a ledger entry from this record, handling invalid tags
+    /// according to `tag_policy`.
+    ///
+    /// Also returns every tag that was rewritten, paired with its original
+    /// text.
+    pub fn to_entry_with_policy(
+        &self,
+        tag_policy: TagPolicy,
+    ) -> BeansResult<(LedgerEntry, Vec<(String, Tag)>)> {
+        let mut tags = Vec::with_capacity(self.tags.len());
+        let mut sanitized = Vec::new();
+        for name in &self.tags {
+            match (Tag::new(name), tag_policy) {
+                (Ok(tag), _) => tags.push(tag),
+                (Err(_), TagPolicy::Sanitize) => {
+                    let tag = Tag::sanitize(name)?;
+                    sanitized.push((name.clone(), tag.clone()));
+                    tags.push(tag);
+                }
+                (Err(e), TagPolicy::Strict) => return Err(e),
+            }
+        }
+
+        let mut builder = LedgerEntryBuilder::new()
+            .date(self.date)
+            .name(self.name.clone())
+            .currency_code(self.currency_code.clone())
+            .amount(self.amount)
+            .entry_type(self.entry_type)
+            .tags(tags);
+
+        if let Some(ref description) = self.description {
+            builder = builder.description(description.clone());
+        }
+
+        Ok((builder.build()?, sanitized))
+    }
+}
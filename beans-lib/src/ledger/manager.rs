@@ -3,19 +3,68 @@
 //! The LedgerManager provides the business logic layer for the Beans application.
 //! It handles file operations, validation, and delegates persistence to the Repository.
 
-use crate::database::{initialize_schema, EntryFilter, Repository, SQLiteRepository};
+use crate::currency::CurrencyConverter;
+use crate::database::{
+    initialize_schema, is_beans_header, DailyTotal, EntryFilter, LedgerStats, MonthlySummary,
+    Repository, RowError, SQLiteRepository, SortField, ValidationIssue,
+};
 use crate::error::{BeansError, BeansResult};
-use crate::models::LedgerEntry;
-use chrono::Utc;
+use crate::interop::EntryData;
+use crate::ledger::{
+    ImportFailure, ImportOptions, ImportRecord, ImportSummary, ReviewReport, SanitizedTag,
+    UndoToken, Warning,
+};
+use crate::models::currency::{find_iso_currency, is_supported_currency};
+use crate::models::{
+    Currency, EntryTemplate, EntryType, LedgerEntry, LedgerEntryBuilder, Tag, TemplateOverrides,
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::Read;
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use uuid::Uuid;
 
+/// How precisely entry dates are stored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DatePrecision {
+    /// Store the full date and time (the default).
+    #[default]
+    DateTime,
+    /// Store only the date, normalized to midnight UTC.
+    DateOnly,
+}
+
+impl DatePrecision {
+    /// Normalizes a date to this precision.
+    pub fn apply(self, date: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            DatePrecision::DateTime => date,
+            DatePrecision::DateOnly => date.date_naive().and_time(NaiveTime::MIN).and_utc(),
+        }
+    }
+}
+
+/// Most missing occurrences [`LedgerManager::detect_missing_recurring`]
+/// reports, so an old entry with a short cadence can't produce an
+/// unbounded list.
+pub const MAX_MISSING_RECURRING: usize = 1000;
+
 /// Manages ledger operations.
 #[derive(Debug)]
 pub struct LedgerManager {
     /// The underlying repository for data persistence.
     repository: Box<dyn Repository>,
+    /// Counter incremented on every mutation made through the manager.
+    revision: AtomicU64,
+    /// Precision applied to entry dates on write.
+    date_precision: DatePrecision,
+    /// UTC time given to entries created from a date without a time.
+    default_time_of_day: NaiveTime,
+    /// Whether updates may change an entry between income and expense.
+    allow_type_change: bool,
 }
 
 impl LedgerManager {
@@ -54,9 +103,125 @@ impl LedgerManager {
         initialize_schema(&conn)?;
         drop(conn);
 
-        Ok(Self {
-            repository: Box::new(repository),
-        })
+        Ok(Self::with_repository(Box::new(repository)))
+    }
+
+    /// Returns true if the file at `path` is a beans ledger.
+    ///
+    /// Only the file header is read; the database is not opened. Files that
+    /// don't exist, can't be read or are plain SQLite databases return false.
+    pub fn is_beans_file<P: AsRef<Path>>(path: P) -> bool {
+        let mut header = [0u8; 100];
+        fs::File::open(path)
+            .and_then(|mut file| file.read_exact(&mut header))
+            .map(|_| is_beans_header(&header))
+            .unwrap_or(false)
+    }
+
+    /// Opens a ledger like [`open`](Self::open), on tokio's blocking thread
+    /// pool.
+    ///
+    /// Opening a file and initializing its schema is synchronous SQLite
+    /// work, which would otherwise stall the async runtime while it runs.
+    pub async fn open_async<P: AsRef<Path>>(path: P) -> BeansResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        tokio::task::spawn_blocking(move || Self::open(path))
+            .await
+            .map_err(|e| BeansError::Other(format!("Ledger open task failed: {}", e)))?
+    }
+
+    /// Creates a ledger backed by the given repository.
+    ///
+    /// The repository is expected to be ready for use (e.g. with its schema
+    /// initialized).
+    pub fn with_repository(repository: Box<dyn Repository>) -> Self {
+        Self {
+            repository,
+            revision: AtomicU64::new(0),
+            date_precision: DatePrecision::default(),
+            default_time_of_day: NaiveTime::MIN,
+            allow_type_change: true,
+        }
+    }
+
+    /// Sets the precision applied to entry dates when they are written.
+    ///
+    /// With [`DatePrecision::DateOnly`], dates of added and updated entries
+    /// are normalized to midnight UTC. Existing entries are not changed.
+    pub fn with_date_precision(mut self, precision: DatePrecision) -> Self {
+        self.date_precision = precision;
+        self
+    }
+
+    /// Returns the precision applied to entry dates when they are written.
+    pub fn date_precision(&self) -> DatePrecision {
+        self.date_precision
+    }
+
+    /// Sets the UTC time of day used for entries created from a date without
+    /// a time, see [`entry_from_data`](Self::entry_from_data).
+    ///
+    /// Defaults to midnight. Noon keeps such entries on the intended day in
+    /// every timezone within twelve hours of UTC. [`DatePrecision::DateOnly`]
+    /// still normalizes stored dates to midnight.
+    pub fn with_default_time_of_day(mut self, time: NaiveTime) -> Self {
+        self.default_time_of_day = time;
+        self
+    }
+
+    /// Returns the UTC time of day used for date-only entries.
+    pub fn default_time_of_day(&self) -> NaiveTime {
+        self.default_time_of_day
+    }
+
+    /// Sets whether [`update_entry`](Self::update_entry) may change an
+    /// entry's type.
+    ///
+    /// Switching between income and expense flips the entry's sign in every
+    /// report, so workflows that never expect it can lock it. Allowed by
+    /// default.
+    pub fn with_allow_type_change(mut self, allow: bool) -> Self {
+        self.allow_type_change = allow;
+        self
+    }
+
+    /// Converts frontend entry data to an entry, placing date-only values at
+    /// the ledger's default time of day. The entry is not added.
+    pub fn entry_from_data(&self, data: EntryData) -> BeansResult<LedgerEntry> {
+        data.into_entry(self.default_time_of_day)
+    }
+
+    /// Converts frontend entry data for an edit of `existing` to the
+    /// updated entry. The entry is not saved.
+    ///
+    /// The result keeps the existing entry's ID, creation time and metadata,
+    /// which the form data doesn't carry. If the data gives only a date and
+    /// it is the entry's current day, the entry keeps its time of day instead
+    /// of moving to the default time.
+    pub fn entry_from_data_for_edit(
+        &self,
+        existing: &LedgerEntry,
+        data: EntryData,
+    ) -> BeansResult<LedgerEntry> {
+        let same_day = NaiveDate::parse_from_str(data.date.trim(), "%Y-%m-%d")
+            .is_ok_and(|day| day == existing.date().date_naive());
+        let mut builder = data
+            .to_builder(self.default_time_of_day)?
+            .id(existing.id())
+            .created_at(existing.created_at());
+        if same_day {
+            builder = builder.date(existing.date());
+        }
+        for (key, value) in existing.metadata() {
+            builder = builder.metadata(key, value);
+        }
+
+        builder.build()
+    }
+
+    /// Returns a copy of the entry with its date normalized to the ledger's precision.
+    fn normalize_date(&self, entry: &LedgerEntry) -> LedgerEntry {
+        entry.with_date(self.date_precision.apply(entry.date()))
     }
 
     /// Creates an in-memory ledger for testing.
@@ -68,9 +233,31 @@ impl LedgerManager {
         let conn = repository.conn.lock().unwrap();
         initialize_schema(&conn)?;
         drop(conn);
-        Ok(Self {
-            repository: Box::new(repository),
-        })
+        Ok(Self::with_repository(Box::new(repository)))
+    }
+
+    /// Returns the ledger's revision.
+    ///
+    /// The revision changes every time entries are added, updated or deleted
+    /// through this manager, so it can be used to invalidate derived data
+    /// such as cached reports.
+    pub fn revision(&self) -> u64 {
+        self.revision.load(Ordering::SeqCst)
+    }
+
+    /// Discards any state derived from the ledger's contents.
+    ///
+    /// Call this after the underlying storage was modified without going
+    /// through this manager (e.g. with raw SQL). Entries are always read from
+    /// storage, but derived data such as cached reports is keyed on the
+    /// ledger's revision and would otherwise keep serving stale results.
+    pub fn refresh(&self) {
+        self.bump_revision();
+    }
+
+    /// Marks the ledger as changed.
+    fn bump_revision(&self) {
+        self.revision.fetch_add(1, Ordering::SeqCst);
     }
 
     /// Adds a new entry to the ledger.
@@ -80,34 +267,470 @@ impl LedgerManager {
         // Validate the entry (additional business logic validation can be added here)
         self.validate_entry(entry)?;
 
-        // Create the entry in the repository
-        self.repository.create(entry)?;
+        self.create_validated(entry)
+    }
+
+    /// Stores an entry that has already passed validation.
+    fn create_validated(&self, entry: &LedgerEntry) -> BeansResult<Uuid> {
+        self.repository.create(&self.normalize_date(entry))?;
+        self.bump_revision();
 
         Ok(entry.id())
     }
 
+    /// Adds a new entry to the ledger like [`add_entry`](Self::add_entry),
+    /// also returning warnings about anything suspicious in it.
+    ///
+    /// Warnings are computed against the entries already in the ledger,
+    /// e.g. an amount far above the average for one of its tags, and never
+    /// prevent the entry from being added. Entries failing validation, such
+    /// as future-dated ones, are still rejected. See [`Warning`] for the
+    /// checks.
+    pub fn add_entry_with_warnings(
+        &self,
+        entry: &LedgerEntry,
+    ) -> BeansResult<(Uuid, Vec<Warning>)> {
+        self.validate_entry(entry)?;
+
+        let mut warnings = Vec::new();
+        for tag in entry.sorted_tags() {
+            let filter = EntryFilter {
+                entry_type: Some(entry.entry_type()),
+                currency: Some(entry.currency_code()),
+                tags: vec![tag.name().to_string()],
+                ..Default::default()
+            };
+            let (total, count) = self.repository.amount_total(&filter)?;
+            warnings.extend(Warning::amount_anomaly(entry, tag.name(), total, count));
+        }
+        warnings.extend(Warning::weekend_payroll(entry));
+
+        Ok((self.create_validated(entry)?, warnings))
+    }
+
+    /// Adds several entries to the ledger at once.
+    ///
+    /// Each entry is validated individually; rejected entries are reported in
+    /// the returned summary and don't prevent the others from being added.
+    /// The valid entries are written in a single transaction, so a storage
+    /// failure leaves the ledger unchanged and is returned as an error.
+    pub fn add_entries(&self, entries: &[LedgerEntry]) -> BeansResult<ImportSummary> {
+        let rows = entries.iter().cloned().enumerate().collect();
+        self.add_rows(rows, ImportSummary::default(), false)
+    }
+
+    /// Imports raw records into the ledger.
+    ///
+    /// Records that can't be turned into a valid entry are reported in the
+    /// returned summary and don't prevent the others from being added. Rows
+    /// with a zero amount are skipped or rejected, and invalid tags rejected
+    /// or sanitized, depending on the options. Any extra tags in the options
+    /// are added to every imported entry.
+    pub fn import_records(
+        &self,
+        records: &[ImportRecord],
+        options: &ImportOptions,
+    ) -> BeansResult<ImportSummary> {
+        let mut summary = ImportSummary::default();
+        let mut rows = Vec::with_capacity(records.len());
+
+        for (index, record) in records.iter().enumerate() {
+            if options.skip_zero_amounts && record.amount.is_zero() {
+                summary.skipped.push(index);
+                continue;
+            }
+
+            let converted =
+                record
+                    .to_entry_with_policy(options.tag_policy)
+                    .and_then(|(entry, sanitized)| {
+                        if options.extra_tags.is_empty() {
+                            return Ok((entry, sanitized));
+                        }
+                        let entry = LedgerEntryBuilder::from_entry(&entry)
+                            .tags(options.extra_tags.iter().cloned())
+                            .build()?;
+                        Ok((entry, sanitized))
+                    });
+
+            match converted {
+                Ok((entry, sanitized)) => {
+                    rows.push((index, entry));
+                    summary
+                        .sanitized_tags
+                        .extend(sanitized.into_iter().map(|(original, tag)| SanitizedTag {
+                            index,
+                            original,
+                            tag,
+                        }));
+                }
+                Err(e) => summary.failed.push(ImportFailure {
+                    index,
+                    message: e.to_string(),
+                }),
+            }
+        }
+
+        self.add_rows(rows, summary, options.defer_foreign_keys)
+    }
+
+    /// Imports records from a JSON array.
+    ///
+    /// See [`import_records`](Self::import_records).
+    pub fn import_json(&self, json: &str, options: &ImportOptions) -> BeansResult<ImportSummary> {
+        let records: Vec<ImportRecord> = serde_json::from_str(json)
+            .map_err(|e| BeansError::Json(format!("Failed to parse import records: {}", e)))?;
+
+        self.import_records(&records, options)
+    }
+
+    /// Validates and adds entries, each tagged with its position in the input.
+    ///
+    /// Rejected entries are added to the summary's failures. The valid
+    /// entries are written in a single transaction, with foreign keys checked
+    /// once at the end if `defer_foreign_keys` is set.
+    fn add_rows(
+        &self,
+        rows: Vec<(usize, LedgerEntry)>,
+        mut summary: ImportSummary,
+        defer_foreign_keys: bool,
+    ) -> BeansResult<ImportSummary> {
+        let mut valid = Vec::with_capacity(rows.len());
+
+        for (index, entry) in rows {
+            match self.validate_entry(&entry) {
+                Ok(()) => valid.push(self.normalize_date(&entry)),
+                Err(e) => summary.failed.push(ImportFailure {
+                    index,
+                    message: e.to_string(),
+                }),
+            }
+        }
+
+        if defer_foreign_keys {
+            self.repository.create_many_deferred(&valid)?;
+        } else {
+            self.repository.create_many(&valid)?;
+        }
+        self.bump_revision();
+        summary.added = valid.iter().map(|entry| entry.id()).collect();
+        summary.failed.sort_by_key(|failure| failure.index);
+
+        Ok(summary)
+    }
+
     /// Retrieves an entry by its ID.
     pub fn get_entry(&self, id: Uuid) -> BeansResult<LedgerEntry> {
         self.repository.get(id)
     }
 
+    /// Gets the entry whose ID starts with the given prefix, such as a
+    /// [`short_id`](LedgerEntry::short_id).
+    ///
+    /// The prefix is case-insensitive and may be given with or without the
+    /// hyphens of the UUID's hyphenated form. If more than one entry matches,
+    /// a validation error lists the candidates so a longer prefix can be used.
+    pub fn get_by_prefix(&self, prefix: &str) -> BeansResult<LedgerEntry> {
+        let digits = prefix.trim().to_lowercase().replace('-', "");
+        if digits.is_empty() || digits.len() > 32 || !digits.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(BeansError::validation(format!(
+                "Invalid entry ID prefix: {}",
+                prefix.trim()
+            )));
+        }
+
+        // IDs are stored hyphenated, 8-4-4-4-12
+        let mut prefix = String::with_capacity(36);
+        for (i, c) in digits.chars().enumerate() {
+            if matches!(i, 8 | 12 | 16 | 20) {
+                prefix.push('-');
+            }
+            prefix.push(c);
+        }
+
+        let ids = self.repository.ids_with_prefix(&prefix)?;
+        match ids.as_slice() {
+            [] => Err(BeansError::not_found(format!(
+                "No entry with ID starting with {}",
+                prefix
+            ))),
+            [id] => self.repository.get(*id),
+            _ => Err(BeansError::validation(format!(
+                "Entry ID prefix {} is ambiguous, it matches: {}",
+                prefix,
+                ids.iter()
+                    .map(Uuid::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
     /// Updates an existing entry.
     ///
-    /// This will fail if the entry doesn't exist or if the entry is invalid.
+    /// This will fail if the entry doesn't exist or if the entry is invalid,
+    /// or if it changes the entry's type while that is disallowed, see
+    /// [`with_allow_type_change`](Self::with_allow_type_change).
     pub fn update_entry(&self, entry: &LedgerEntry) -> BeansResult<()> {
         // Validate the entry
         self.validate_entry(entry)?;
 
+        if !self.allow_type_change {
+            let existing = self.repository.get(entry.id())?;
+            if existing.entry_type() != entry.entry_type() {
+                return Err(BeansError::validation(format!(
+                    "Changing an entry from {} to {} is not allowed",
+                    existing.entry_type(),
+                    entry.entry_type()
+                )));
+            }
+        }
+
         // Update the entry with the current timestamp
-        let updated_entry = entry.with_updated_at(Utc::now());
+        let updated_entry = self.normalize_date(entry).with_updated_at(Utc::now());
 
         // Update the entry in the repository
-        self.repository.update(&updated_entry)
+        self.repository.update(&updated_entry)?;
+        self.bump_revision();
+
+        Ok(())
     }
 
     /// Deletes an entry by its ID.
     pub fn delete_entry(&self, id: Uuid) -> BeansResult<()> {
-        self.repository.delete(id)
+        self.repository.delete(id)?;
+        self.bump_revision();
+
+        Ok(())
+    }
+
+    /// Adds an entry like [`add_entry`](Self::add_entry), returning a token
+    /// that [`undo`](Self::undo) uses to remove it again.
+    pub fn add_entry_undoable(&self, entry: &LedgerEntry) -> BeansResult<UndoToken> {
+        let id = self.add_entry(entry)?;
+
+        Ok(UndoToken::Added(self.repository.get(id)?))
+    }
+
+    /// Updates an entry like [`update_entry`](Self::update_entry), returning
+    /// a token that [`undo`](Self::undo) uses to restore its previous state.
+    pub fn update_entry_undoable(&self, entry: &LedgerEntry) -> BeansResult<UndoToken> {
+        let previous = self.repository.get(entry.id())?;
+        self.update_entry(entry)?;
+        let current = self.repository.get(entry.id())?;
+
+        Ok(UndoToken::Updated {
+            previous: Box::new(previous),
+            current: Box::new(current),
+        })
+    }
+
+    /// Deletes an entry like [`delete_entry`](Self::delete_entry), returning
+    /// a token that [`undo`](Self::undo) uses to restore it.
+    pub fn delete_entry_undoable(&self, id: Uuid) -> BeansResult<UndoToken> {
+        let previous = self.repository.get(id)?;
+        self.delete_entry(id)?;
+
+        Ok(UndoToken::Deleted(previous))
+    }
+
+    /// Reverses the change recorded in an undo token.
+    ///
+    /// Restored entries get back their recorded state, including their
+    /// timestamps, and are validated and normalized like any other write.
+    ///
+    /// The entry must still be as the change left it. If it was changed
+    /// again since, e.g. edited after the update being undone, re-created
+    /// after being deleted or deleted after being added, a validation error
+    /// is returned and nothing is changed, so later edits are never
+    /// overwritten.
+    pub fn undo(&self, token: UndoToken) -> BeansResult<()> {
+        let stored = match self.repository.get(token.entry_id()) {
+            Ok(entry) => Some(entry),
+            Err(BeansError::NotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+        if stored.as_ref() != token.stored_after() {
+            return Err(BeansError::validation(format!(
+                "Entry {} has changed since, so the change can't be undone",
+                token.entry_id()
+            )));
+        }
+
+        match token {
+            UndoToken::Added(entry) => self.repository.delete(entry.id())?,
+            UndoToken::Updated { previous, .. } => {
+                self.validate_entry(&previous)?;
+                self.repository.update(&self.normalize_date(&previous))?
+            }
+            UndoToken::Deleted(previous) => {
+                self.validate_entry(&previous)?;
+                self.repository.create(&self.normalize_date(&previous))?
+            }
+        }
+        self.bump_revision();
+
+        Ok(())
+    }
+
+    /// Splits an entry into several parts, each with its own amount and tags.
+    ///
+    /// The parts keep the original's date, name, currency, type, description
+    /// and metadata, and their amounts must add up to the original amount.
+    /// Each part must be positive and no more precise than the currency's
+    /// minor unit, or than the original amount if that is more precise. The
+    /// parts are validated like added entries; the original is deleted and
+    /// the parts are created in one transaction. Returns the new entries, in
+    /// the order of `parts`.
+    pub fn split_entry(
+        &self,
+        id: Uuid,
+        parts: Vec<(Decimal, Vec<Tag>)>,
+    ) -> BeansResult<Vec<LedgerEntry>> {
+        if parts.is_empty() {
+            return Err(BeansError::validation(
+                "An entry must be split into at least one part",
+            ));
+        }
+
+        let original = self.repository.get(id)?;
+        let max_scale = find_iso_currency(&original.currency_code())
+            .map_or(0, |currency| currency.exponent)
+            .max(original.amount().normalize().scale());
+        for (amount, _) in &parts {
+            if *amount <= Decimal::ZERO {
+                return Err(BeansError::validation(format!(
+                    "Split part amounts must be positive, got {}",
+                    amount
+                )));
+            }
+            if amount.normalize().scale() > max_scale {
+                return Err(BeansError::validation(format!(
+                    "Split part amount {} is more precise than {} allows",
+                    amount,
+                    original.currency_code()
+                )));
+            }
+        }
+
+        let total: Decimal = parts.iter().map(|(amount, _)| *amount).sum();
+        if total != original.amount() {
+            return Err(BeansError::validation(format!(
+                "Split parts add up to {}, but the entry amount is {}",
+                total,
+                original.amount()
+            )));
+        }
+
+        let entries = parts
+            .into_iter()
+            .map(|(amount, tags)| {
+                let mut builder = LedgerEntryBuilder::new()
+                    .date(original.date())
+                    .name(original.name())
+                    .currency_code(original.currency_code())
+                    .amount(amount)
+                    .entry_type(original.entry_type())
+                    .tags(tags);
+                if let Some(description) = original.description() {
+                    builder = builder.description(description);
+                }
+                for (key, value) in original.metadata() {
+                    builder = builder.metadata(key, value);
+                }
+                let part = builder.build()?;
+                self.validate_entry(&part)?;
+                Ok(self.normalize_date(&part))
+            })
+            .collect::<BeansResult<Vec<_>>>()?;
+
+        self.repository.replace(&[id], &entries)?;
+        self.bump_revision();
+
+        Ok(entries)
+    }
+
+    /// Merges several entries into a single consolidated entry.
+    ///
+    /// The entries must share a currency and type. The merged entry sums
+    /// their amounts, takes the earliest date, joins their distinct names and
+    /// descriptions, and carries the union of their tags and metadata (the
+    /// first entry wins if a metadata key conflicts). The merged entry is
+    /// validated and its date normalized like an added entry; the originals
+    /// are deleted and it is created in one transaction.
+    pub fn merge_entries(&self, ids: &[Uuid]) -> BeansResult<LedgerEntry> {
+        let unique: HashSet<&Uuid> = ids.iter().collect();
+        if ids.len() < 2 || unique.len() != ids.len() {
+            return Err(BeansError::validation(
+                "Merging needs at least two distinct entries",
+            ));
+        }
+
+        let entries = ids
+            .iter()
+            .map(|id| self.repository.get(*id))
+            .collect::<BeansResult<Vec<_>>>()?;
+
+        let first = &entries[0];
+        if let Some(other) = entries
+            .iter()
+            .find(|e| e.currency_code() != first.currency_code())
+        {
+            return Err(BeansError::validation(format!(
+                "Cannot merge entries in different currencies: {} and {}",
+                first.currency_code(),
+                other.currency_code()
+            )));
+        }
+        if let Some(other) = entries
+            .iter()
+            .find(|e| e.entry_type() != first.entry_type())
+        {
+            return Err(BeansError::validation(format!(
+                "Cannot merge entries of different types: {} and {}",
+                first.entry_type(),
+                other.entry_type()
+            )));
+        }
+
+        let mut names: Vec<&str> = Vec::new();
+        let mut descriptions: Vec<&str> = Vec::new();
+        for entry in &entries {
+            if !names.contains(&entry.name()) {
+                names.push(entry.name());
+            }
+            if let Some(description) = entry.description() {
+                if !descriptions.contains(&description) {
+                    descriptions.push(description);
+                }
+            }
+        }
+
+        let mut builder = LedgerEntryBuilder::new()
+            .date(entries.iter().map(|e| e.date()).min().unwrap())
+            .name(names.join(" + "))
+            .currency_code(first.currency_code())
+            .amount(entries.iter().map(|e| e.amount()).sum())
+            .entry_type(first.entry_type())
+            .tags(entries.iter().flat_map(|e| e.tags().iter().cloned()));
+        if !descriptions.is_empty() {
+            builder = builder.description(descriptions.join("; "));
+        }
+        for entry in entries.iter().rev() {
+            for (key, value) in entry.metadata() {
+                builder = builder.metadata(key, value);
+            }
+        }
+        let merged = builder.build()?;
+        self.validate_entry(&merged)?;
+        let merged = self.normalize_date(&merged);
+
+        self.repository
+            .replace(ids, std::slice::from_ref(&merged))?;
+        self.bump_revision();
+
+        Ok(merged)
     }
 
     /// Lists entries matching the given filter.
@@ -115,17 +738,428 @@ impl LedgerManager {
         self.repository.list(filter)
     }
 
+    /// Lists entries matching the given filter, reporting unreadable rows
+    /// separately instead of failing.
+    ///
+    /// Use this to show what can still be read from a partially corrupt ledger.
+    pub fn list_entries_lenient(
+        &self,
+        filter: &EntryFilter,
+    ) -> BeansResult<(Vec<LedgerEntry>, Vec<RowError>)> {
+        self.repository.list_lenient(filter)
+    }
+
     /// Counts entries matching the given filter.
     pub fn count_entries(&self, filter: &EntryFilter) -> BeansResult<usize> {
         self.repository.count(filter)
     }
 
+    /// Sums matching entries per UTC day and entry type, without reading
+    /// the entries themselves.
+    pub fn daily_totals(&self, filter: &EntryFilter) -> BeansResult<Vec<DailyTotal>> {
+        self.repository.daily_totals(filter)
+    }
+
+    /// Recomputes the stored per-month income and expense totals for each
+    /// currency.
+    ///
+    /// Reports over whole months read these totals instead of every entry,
+    /// which keeps dashboards fast on large ledgers. After the first build
+    /// the storage tracks which months change as entries are added, updated
+    /// or deleted, and recomputes just those months when the cache is next
+    /// read.
+    pub fn rebuild_summary_cache(&self) -> BeansResult<()> {
+        self.repository.rebuild_monthly_summaries()
+    }
+
+    /// Returns the cached monthly totals for the months from `start` to
+    /// `end`, or `None` if the cache was never built.
+    ///
+    /// See [`rebuild_summary_cache`](Self::rebuild_summary_cache).
+    pub fn monthly_summaries(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> BeansResult<Option<Vec<MonthlySummary>>> {
+        self.repository.monthly_summaries(start, end)
+    }
+
+    /// Lists all entries with the given tag.
+    ///
+    /// The tag name is matched case-insensitively.
+    pub fn entries_with_tag(&self, tag: &str) -> BeansResult<Vec<LedgerEntry>> {
+        let filter = EntryFilter {
+            tags: vec![tag.trim().to_lowercase()],
+            ..Default::default()
+        };
+        self.repository.list(&filter)
+    }
+
+    /// Returns the entries with the largest amounts, regardless of type.
+    ///
+    /// Amounts are stored unsigned, with the entry type carrying the sign, so
+    /// this ranks by magnitude: a 500 expense comes before a 200 income.
+    /// Entries with equal amounts are ordered by date, newest first. The
+    /// filter's own `limit` and `offset` are ignored.
+    pub fn largest_by_abs(
+        &self,
+        filter: &EntryFilter,
+        limit: usize,
+    ) -> BeansResult<Vec<LedgerEntry>> {
+        let filter = EntryFilter {
+            limit: None,
+            offset: None,
+            ..filter.clone()
+        };
+
+        let mut entries = self.repository.list(&filter)?;
+        entries.sort_by(|a, b| {
+            b.signed_amount()
+                .abs()
+                .cmp(&a.signed_amount().abs())
+                .then_with(|| b.date().cmp(&a.date()))
+        });
+        entries.truncate(limit);
+
+        Ok(entries)
+    }
+
+    /// Returns the `n` entries most recently added to the ledger.
+    ///
+    /// This follows data-entry order rather than transaction dates, so an
+    /// entry backdated to last year still shows up first if it was just added.
+    pub fn recently_added(&self, n: usize) -> BeansResult<Vec<LedgerEntry>> {
+        let filter = EntryFilter {
+            sort: SortField::CreatedAt,
+            limit: Some(n),
+            ..Default::default()
+        };
+        self.repository.list(&filter)
+    }
+
     /// Gets all entries in the ledger.
     pub fn get_all_entries(&self) -> BeansResult<Vec<LedgerEntry>> {
         let filter = EntryFilter::default();
         self.repository.list(&filter)
     }
 
+    /// Returns the dates of the earliest and latest entries.
+    ///
+    /// Returns `None` for an empty ledger.
+    pub fn date_span(&self) -> BeansResult<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+        self.repository.date_span()
+    }
+
+    /// Finds where an expected recurring entry, such as a monthly salary, is
+    /// missing.
+    ///
+    /// Entries named `name` (ignoring case) are taken in date order. Whenever
+    /// the next one arrives more than `tolerance` after the expected date,
+    /// one cadence after the previous one, the expected date is reported and
+    /// the next is expected one cadence later. Gaps after the latest entry
+    /// are checked up to now. The dates are approximate: they follow the
+    /// cadence from the last entry seen, not the calendar. Returns an empty
+    /// list if there are no such entries.
+    ///
+    /// The cadence must be at least a day. At most
+    /// [`MAX_MISSING_RECURRING`] dates are reported, the earliest first.
+    pub fn detect_missing_recurring(
+        &self,
+        name: &str,
+        expected_cadence: Duration,
+        tolerance: Duration,
+    ) -> BeansResult<Vec<DateTime<Utc>>> {
+        if expected_cadence < Duration::days(1) {
+            return Err(BeansError::validation(
+                "Expected cadence must be at least one day",
+            ));
+        }
+        if tolerance < Duration::zero() {
+            return Err(BeansError::validation("Tolerance must not be negative"));
+        }
+
+        let filter = EntryFilter {
+            name_contains: Some(name.to_string()),
+            ..Default::default()
+        };
+        let name = name.to_lowercase();
+        let mut dates: Vec<DateTime<Utc>> = self
+            .repository
+            .list(&filter)?
+            .iter()
+            .filter(|entry| entry.name().to_lowercase() == name)
+            .map(|entry| entry.date())
+            .collect();
+        dates.sort();
+
+        let mut missing = Vec::new();
+        let Some(&first) = dates.first() else {
+            return Ok(missing);
+        };
+        let mut expected = first + expected_cadence;
+        // Checking up to now also reports a missing latest occurrence
+        for date in dates.into_iter().skip(1).chain(std::iter::once(Utc::now())) {
+            while date - expected > tolerance {
+                if missing.len() == MAX_MISSING_RECURRING {
+                    return Ok(missing);
+                }
+                missing.push(expected);
+                expected += expected_cadence;
+            }
+            expected = date + expected_cadence;
+        }
+
+        Ok(missing)
+    }
+
+    /// Finds entries whose currency is unusual for their tags.
+    ///
+    /// This is a heuristic to catch data-entry mistakes, such as a single EUR
+    /// entry among USD `salary` entries. For each tag, the dominant currency is
+    /// the one used by more than half of the entries with that tag; an entry
+    /// is flagged if its currency differs from the dominant currency of any of
+    /// its tags. Tags without a clear majority and untagged entries are never
+    /// flagged, so legitimately multi-currency categories produce no noise.
+    pub fn currency_anomalies(&self) -> BeansResult<Vec<LedgerEntry>> {
+        Ok(Self::find_currency_anomalies(&self.get_all_entries()?))
+    }
+
+    /// Finds entries needing cleanup: untagged entries, entries without a
+    /// description, suspicious amounts and currency anomalies.
+    ///
+    /// This combines several checks into one report, e.g. for a cleanup view.
+    /// See [`ReviewReport`] for what each check looks for.
+    pub fn needs_review(&self) -> BeansResult<ReviewReport> {
+        let entries = self.get_all_entries()?;
+        let matching = |check: fn(&LedgerEntry) -> bool| -> Vec<LedgerEntry> {
+            entries.iter().filter(|e| check(e)).cloned().collect()
+        };
+
+        Ok(ReviewReport {
+            untagged: matching(|e| e.tags().is_empty()),
+            missing_description: matching(|e| e.description().is_none_or(|d| d.trim().is_empty())),
+            suspicious_amounts: matching(ReviewReport::has_suspicious_amount),
+            currency_anomalies: Self::find_currency_anomalies(&entries),
+        })
+    }
+
+    /// Returns the entries whose currency differs from the dominant currency
+    /// of any of their tags.
+    fn find_currency_anomalies(entries: &[LedgerEntry]) -> Vec<LedgerEntry> {
+        // Count currency usage per tag
+        let mut usage: HashMap<&str, HashMap<String, usize>> = HashMap::new();
+        for entry in entries {
+            for tag in entry.tags() {
+                *usage
+                    .entry(tag.name())
+                    .or_default()
+                    .entry(entry.currency_code())
+                    .or_insert(0) += 1;
+            }
+        }
+
+        // Keep only tags with a strict majority currency
+        let dominant: HashMap<&str, &str> = usage
+            .iter()
+            .filter_map(|(tag, counts)| {
+                let total: usize = counts.values().sum();
+                counts
+                    .iter()
+                    .find(|(_, count)| **count * 2 > total)
+                    .map(|(currency, _)| (*tag, currency.as_str()))
+            })
+            .collect();
+
+        entries
+            .iter()
+            .filter(|entry| {
+                entry.tags().iter().any(|tag| {
+                    dominant
+                        .get(tag.name())
+                        .is_some_and(|currency| *currency != entry.currency_code())
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns an overview of the ledger: entry count, date span, currencies,
+    /// tags and net amount per currency.
+    pub fn ledger_stats(&self) -> BeansResult<LedgerStats> {
+        self.repository.stats()
+    }
+
+    /// Creates an in-memory copy of the ledger with every amount converted to
+    /// the target currency.
+    ///
+    /// The original ledger is not modified. Entries that can't be converted
+    /// (e.g. because no exchange rate is available) are left out of the copy
+    /// and reported in the returned summary, indexed by their position in
+    /// [`get_all_entries`](Self::get_all_entries).
+    pub async fn to_currency(
+        &self,
+        converter: &CurrencyConverter,
+        target: &str,
+    ) -> BeansResult<(LedgerManager, ImportSummary)> {
+        if !is_supported_currency(target) {
+            return Err(BeansError::validation(format!(
+                "Unsupported currency code: '{}'",
+                target
+            )));
+        }
+
+        let target_currency = Currency::new(Decimal::ZERO, target)?;
+        let mut summary = ImportSummary::default();
+        let mut rows = Vec::new();
+
+        for (index, entry) in self.get_all_entries()?.iter().enumerate() {
+            let result = match entry.currency() {
+                Ok(currency) => converter.convert_amount(&currency, &target_currency).await,
+                Err(e) => Err(e),
+            };
+
+            let entry = result.and_then(|amount| {
+                LedgerEntryBuilder::from_entry(entry)
+                    .currency_code(target.to_string())
+                    .amount(*amount.amount())
+                    .build()
+            });
+
+            match entry {
+                Ok(entry) => rows.push((index, entry)),
+                Err(e) => summary.failed.push(ImportFailure {
+                    index,
+                    message: e.to_string(),
+                }),
+            }
+        }
+
+        let ledger = LedgerManager::in_memory()?;
+        let summary = ledger.add_rows(rows, summary, false)?;
+
+        Ok((ledger, summary))
+    }
+
+    /// Returns the ledger's net amount (income - expenses) with every entry
+    /// converted to the target currency, e.g. net worth in a home currency.
+    ///
+    /// Only the target's currency code is used. If any entry can't be
+    /// converted, the error lists every entry that failed instead of
+    /// returning a partial total.
+    pub async fn net_in(
+        &self,
+        converter: &CurrencyConverter,
+        target: &Currency<'_>,
+    ) -> BeansResult<Decimal> {
+        let mut net = Decimal::ZERO;
+        let mut failures = Vec::new();
+
+        for entry in self.get_all_entries()? {
+            let result = match entry.currency() {
+                Ok(currency) => converter.convert_amount(&currency, target).await,
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(amount) => match entry.entry_type() {
+                    EntryType::Income => net += *amount.amount(),
+                    EntryType::Expense => net -= *amount.amount(),
+                },
+                Err(e) => failures.push(format!("{} ({}): {}", entry.name(), entry.id(), e)),
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(BeansError::Currency(format!(
+                "Failed to convert {} entries to {}: {}",
+                failures.len(),
+                target.code(),
+                failures.join("; ")
+            )));
+        }
+
+        Ok(net)
+    }
+
+    /// Saves a filter as a named preset.
+    ///
+    /// Saving under a name that already exists overwrites the previous preset.
+    pub fn save_filter_preset(&self, name: &str, filter: &EntryFilter) -> BeansResult<()> {
+        if name.trim().is_empty() {
+            return Err(BeansError::validation("Filter preset name cannot be empty"));
+        }
+
+        self.repository.save_filter_preset(name.trim(), filter)
+    }
+
+    /// Loads a named filter preset.
+    ///
+    /// Returns a not found error if no preset exists with that name.
+    pub fn load_filter_preset(&self, name: &str) -> BeansResult<EntryFilter> {
+        self.repository.load_filter_preset(name.trim())
+    }
+
+    /// Lists the names of all saved filter presets.
+    pub fn list_filter_presets(&self) -> BeansResult<Vec<String>> {
+        self.repository.list_filter_presets()
+    }
+
+    /// Saves an entry template under the given name.
+    ///
+    /// Saving under a name that already exists overwrites the previous template.
+    pub fn save_template(&self, name: &str, template: &EntryTemplate) -> BeansResult<()> {
+        if name.trim().is_empty() {
+            return Err(BeansError::validation("Template name cannot be empty"));
+        }
+
+        self.repository.save_template(name.trim(), template)
+    }
+
+    /// Loads a named entry template.
+    pub fn load_template(&self, name: &str) -> BeansResult<EntryTemplate> {
+        self.repository.load_template(name.trim())
+    }
+
+    /// Lists the names of all saved entry templates.
+    pub fn list_templates(&self) -> BeansResult<Vec<String>> {
+        self.repository.list_templates()
+    }
+
+    /// Creates and adds an entry from a named template.
+    ///
+    /// The overrides replace the template's values; the date defaults to now.
+    /// Returns the entry that was added to the ledger.
+    pub fn create_from_template(
+        &self,
+        name: &str,
+        overrides: &TemplateOverrides,
+    ) -> BeansResult<LedgerEntry> {
+        let template = self.load_template(name)?;
+        let entry = template.to_builder(overrides).build()?;
+
+        self.add_entry(&entry)?;
+
+        Ok(entry)
+    }
+
+    /// Rebuilds the ledger's indexes.
+    ///
+    /// This is a maintenance operation, useful after bulk changes or after a
+    /// migration adds new indexes. It is safe to call on an empty ledger.
+    pub fn reindex(&self) -> BeansResult<()> {
+        self.repository.reindex()
+    }
+
+    /// Checks all stored data against the rules entries are validated with,
+    /// returning every problem found.
+    ///
+    /// Entries added through the library always pass; this finds rows that
+    /// bypassed it, such as data edited with raw SQL or written by older
+    /// versions. Unlike listing, a bad row doesn't stop the check.
+    pub fn validate(&self) -> BeansResult<Vec<ValidationIssue>> {
+        self.repository.validate()
+    }
+
     /// Validates an entry according to business rules.
     ///
     /// This is separate from the model validation and can include additional
@@ -134,6 +1168,13 @@ impl LedgerManager {
         // Basic validation is already done in the LedgerEntry::build method
         // Additional business logic validation can be added here
 
+        if !is_supported_currency(&entry.currency_code()) {
+            return Err(BeansError::validation(format!(
+                "Unsupported currency code: '{}'",
+                entry.currency_code()
+            )));
+        }
+
         // For example, we could check if the entry date is in the future
         let now = Utc::now();
         if entry.date() > now {
@@ -144,5 +1185,4 @@ impl LedgerManager {
 
         Ok(())
     }
-
 }
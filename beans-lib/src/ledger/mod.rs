@@ -1,5 +1,15 @@
 //! Ledger management module.
 
+mod import;
 mod manager;
+mod review;
+mod undo;
+mod warning;
 
-pub use manager::LedgerManager;
+pub use import::{
+    ImportFailure, ImportOptions, ImportRecord, ImportSummary, SanitizedTag, TagPolicy,
+};
+pub use manager::{DatePrecision, LedgerManager, MAX_MISSING_RECURRING};
+pub use review::ReviewReport;
+pub use undo::UndoToken;
+pub use warning::Warning;
@@ -0,0 +1,42 @@
+//! Data-quality report over a ledger's entries.
+
+use crate::models::currency::find_iso_currency;
+use crate::models::LedgerEntry;
+use serde::{Deserialize, Serialize};
+
+/// Entries that likely need attention, grouped by problem.
+///
+/// An entry appears once in every group whose check it fails.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReviewReport {
+    /// Entries without any tags.
+    pub untagged: Vec<LedgerEntry>,
+    /// Entries without a description, or with a blank one.
+    pub missing_description: Vec<LedgerEntry>,
+    /// Entries whose amount is not positive, or is more precise than its
+    /// currency allows (e.g. `10.005` USD).
+    pub suspicious_amounts: Vec<LedgerEntry>,
+    /// Entries whose currency is unusual for their tags.
+    pub currency_anomalies: Vec<LedgerEntry>,
+}
+
+impl ReviewReport {
+    /// Returns true if no entry needs review.
+    pub fn is_empty(&self) -> bool {
+        self.untagged.is_empty()
+            && self.missing_description.is_empty()
+            && self.suspicious_amounts.is_empty()
+            && self.currency_anomalies.is_empty()
+    }
+
+    /// Returns true if the entry's amount is suspicious, see [`suspicious_amounts`](Self::suspicious_amounts).
+    pub(crate) fn has_suspicious_amount(entry: &LedgerEntry) -> bool {
+        let amount = entry.amount();
+        if amount <= rust_decimal::Decimal::ZERO {
+            return true;
+        }
+
+        find_iso_currency(&entry.currency_code())
+            .is_some_and(|currency| amount.normalize().scale() > currency.exponent)
+    }
+}
@@ -0,0 +1,45 @@
+//! Tokens for reversing changes made through the ledger manager.
+
+use crate::models::LedgerEntry;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What a change wrote and what it replaced, so that it can be reversed
+/// with [`LedgerManager::undo`](crate::ledger::LedgerManager::undo).
+///
+/// The entry as the change left it is recorded too, so that undoing can
+/// tell whether the entry was changed again in the meantime.
+#[must_use = "an undo token is needed to reverse the change"]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UndoToken {
+    /// An entry was added, as stored; undoing deletes it.
+    Added(LedgerEntry),
+    /// An entry was updated; undoing restores its previous state.
+    Updated {
+        /// The entry before the update.
+        previous: Box<LedgerEntry>,
+        /// The entry as the update stored it.
+        current: Box<LedgerEntry>,
+    },
+    /// An entry was deleted; undoing restores it.
+    Deleted(LedgerEntry),
+}
+
+impl UndoToken {
+    /// Returns the ID of the entry the change applied to.
+    pub fn entry_id(&self) -> Uuid {
+        match self {
+            UndoToken::Added(entry) | UndoToken::Deleted(entry) => entry.id(),
+            UndoToken::Updated { previous, .. } => previous.id(),
+        }
+    }
+
+    /// Returns the entry as the change left it, or `None` if it deleted it.
+    pub(crate) fn stored_after(&self) -> Option<&LedgerEntry> {
+        match self {
+            UndoToken::Added(entry) => Some(entry),
+            UndoToken::Updated { current, .. } => Some(current),
+            UndoToken::Deleted(_) => None,
+        }
+    }
+}
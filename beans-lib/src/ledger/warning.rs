@@ -0,0 +1,91 @@
+//! Non-fatal warnings about entries being added to a ledger.
+
+use crate::models::{EntryType, LedgerEntry};
+use chrono::{DateTime, Datelike, Utc, Weekday};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How many times the tag's average an amount must exceed to be flagged.
+const AMOUNT_ANOMALY_FACTOR: u32 = 3;
+
+/// Fewest earlier entries with a tag needed before its average is trusted.
+const MIN_HISTORY: usize = 3;
+
+/// Tags marking an income entry as payroll.
+const PAYROLL_TAGS: [&str; 2] = ["salary", "payroll"];
+
+/// A suspicious but valid condition found when adding an entry.
+///
+/// Warnings never block the entry; they are returned so the caller can ask
+/// the user to double-check it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Warning {
+    /// The amount is more than three times the average of at least three
+    /// earlier entries with the same tag, type and currency.
+    AmountAnomaly {
+        /// The tag whose history the amount was compared against.
+        tag: String,
+        /// The entry's amount.
+        amount: Decimal,
+        /// Average amount of the earlier entries with the tag.
+        average: Decimal,
+    },
+    /// A payroll income entry, tagged `salary` or `payroll`, falls on a
+    /// Saturday or Sunday.
+    WeekendPayroll(DateTime<Utc>),
+}
+
+impl Warning {
+    /// Checks the entry's amount against the `total` of the `count` earlier
+    /// entries sharing `tag`.
+    pub(crate) fn amount_anomaly(
+        entry: &LedgerEntry,
+        tag: &str,
+        total: Decimal,
+        count: usize,
+    ) -> Option<Self> {
+        if count < MIN_HISTORY {
+            return None;
+        }
+
+        let average = total / Decimal::from(count);
+        (entry.amount() > average * Decimal::from(AMOUNT_ANOMALY_FACTOR)).then(|| {
+            Warning::AmountAnomaly {
+                tag: tag.to_string(),
+                amount: entry.amount(),
+                average,
+            }
+        })
+    }
+
+    /// Checks whether the entry is payroll dated on a weekend.
+    pub(crate) fn weekend_payroll(entry: &LedgerEntry) -> Option<Self> {
+        let date = entry.date();
+        (entry.entry_type() == EntryType::Income
+            && matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+            && entry.has_any_tag(PAYROLL_TAGS))
+        .then_some(Warning::WeekendPayroll(date))
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::AmountAnomaly {
+                tag,
+                amount,
+                average,
+            } => write!(
+                f,
+                "Amount {} is far above the average of {} for tag '{}'",
+                amount,
+                average.round_dp(2),
+                tag
+            ),
+            Warning::WeekendPayroll(date) => {
+                write!(f, "Payroll dated {} falls on a weekend", date.date_naive())
+            }
+        }
+    }
+}
@@ -12,10 +12,12 @@
 //! The library is organized into several modules:
 //!
 //! - `models`: Core domain models (Currency, Tag, LedgerEntry, EntryType)
+//! - `interop`: Plain data types exchanged with frontends
 //! - `database`: Repository pattern for SQLite persistence
 //! - `ledger`: High-level ledger management API
 //! - `currency`: Exchange rate conversion and caching
 //! - `reporting`: Time series data and report generation
+//! - `render`: Plain-text rendering for terminal tools
 //! - `error`: Error types and handling
 //!
 
@@ -23,8 +25,10 @@
 pub mod currency;
 pub mod database;
 pub mod error;
+pub mod interop;
 pub mod ledger;
 pub mod models;
+pub mod render;
 pub mod reporting;
 
 // Prelude for convenient imports
@@ -39,10 +43,13 @@ pub mod prelude {
 
     // Re-export core types
     pub use crate::currency::{CurrencyConverter, ExchangeRateCache};
-    pub use crate::database::{EntryFilter, Repository};
-    pub use crate::error::{BeansError, BeansResult};
-    pub use crate::ledger::LedgerManager;
-    pub use crate::models::{Currency, EntryType, LedgerEntry, LedgerEntryBuilder, Tag};
+    pub use crate::database::{EntryFilter, LedgerStats, Repository, RowError, SortField};
+    pub use crate::error::{BeansError, BeansResult, ErrorInfo};
+    pub use crate::interop::EntryData;
+    pub use crate::ledger::{DatePrecision, LedgerManager, UndoToken};
+    pub use crate::models::{
+        Currency, EntryTemplate, EntryType, LedgerEntry, LedgerEntryBuilder, Tag, TemplateOverrides,
+    };
     pub use crate::reporting::{
         IncomeExpenseReport, PeriodSummary, ReportGenerator, TimePeriod, TimeSeriesData,
         TimeSeriesPoint,
@@ -55,6 +62,6 @@ pub mod prelude {
 }
 
 // Re-export commonly used types at the crate root
-pub use error::{BeansError, BeansResult};
+pub use error::{BeansError, BeansResult, ErrorInfo};
 pub use ledger::LedgerManager;
 pub use models::{Currency, EntryType, LedgerEntry, LedgerEntryBuilder, Tag};
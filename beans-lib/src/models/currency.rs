@@ -10,13 +10,58 @@ use rusty_money::{
 
 use crate::{BeansError, BeansResult};
 
+/// ISO 4217 codes of every currency the library can represent.
+const SUPPORTED_CURRENCIES: &[&str] = &[
+    "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD", "BDT",
+    "BGN", "BHD", "BIF", "BMD", "BND", "BOB", "BRL", "BSD", "BTN", "BWP", "BYN", "BYR", "BZD",
+    "CAD", "CDF", "CHF", "CLF", "CLP", "CNY", "COP", "CRC", "CUC", "CUP", "CVE", "CZK", "DJF",
+    "DKK", "DOP", "DZD", "EGP", "ERN", "ETB", "EUR", "FJD", "FKP", "GBP", "GEL", "GHS", "GIP",
+    "GMD", "GNF", "GTQ", "GYD", "HKD", "HNL", "HRK", "HTG", "HUF", "IDR", "ILS", "INR", "IQD",
+    "IRR", "ISK", "JMD", "JOD", "JPY", "KES", "KGS", "KHR", "KMF", "KPW", "KRW", "KWD", "KYD",
+    "KZT", "LAK", "LBP", "LKR", "LRD", "LSL", "LYD", "MAD", "MDL", "MGA", "MKD", "MMK", "MNT",
+    "MOP", "MRU", "MUR", "MVR", "MWK", "MXN", "MYR", "MZN", "NAD", "NGN", "NIO", "NOK", "NPR",
+    "NZD", "OMR", "PAB", "PEN", "PGK", "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD", "RUB",
+    "RWF", "SAR", "SBD", "SCR", "SDG", "SEK", "SGD", "SHP", "SKK", "SLL", "SOS", "SRD", "SSP",
+    "STD", "STN", "SVC", "SYP", "SZL", "THB", "TJS", "TMT", "TND", "TOP", "TRY", "TTD", "TWD",
+    "TZS", "UAH", "UGX", "USD", "UYU", "UYW", "UZS", "VES", "VND", "VUV", "WST", "XAF", "XAG",
+    "XAU", "XBA", "XBB", "XBC", "XBD", "XCD", "XDR", "XOF", "XPD", "XPF", "XPT", "XTS", "YER",
+    "ZAR", "ZMK", "ZMW", "ZWL",
+];
+
+/// Returns the ISO codes of all currencies supported by the library, sorted alphabetically.
+///
+/// Frontends should populate currency pickers from this list rather than
+/// hardcoding their own.
+pub fn supported_currencies() -> &'static [&'static str] {
+    SUPPORTED_CURRENCIES
+}
+
+/// Returns true if the given ISO code is a supported currency.
+///
+/// The check is case-sensitive, codes are expected in uppercase.
+pub fn is_supported_currency(code: &str) -> bool {
+    SUPPORTED_CURRENCIES.binary_search(&code).is_ok()
+}
+
+/// Looks up the ISO definition of a supported currency.
+///
+/// All currency lookups in the library go through here, so a code is either
+/// accepted everywhere or rejected everywhere, regardless of what rusty_money
+/// happens to know about.
+pub(crate) fn find_iso_currency(code: &str) -> Option<&'static IsoCurrency> {
+    if !is_supported_currency(code) {
+        return None;
+    }
+    iso::find(code)
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Currency<'a>(Money<'a, IsoCurrency>);
 
 impl<'a> Currency<'a> {
     pub fn new(amount: Decimal, currency_code: &str) -> BeansResult<Self> {
-        let code =
-            iso::find(currency_code).ok_or(BeansError::Currency(currency_code.to_owned()))?;
+        let code = find_iso_currency(currency_code)
+            .ok_or(BeansError::Currency(currency_code.to_owned()))?;
         let m = Money::from_decimal(amount, code);
         Ok(Self(m))
     }
@@ -28,6 +73,15 @@ impl<'a> Currency<'a> {
     pub fn amount(&self) -> &Decimal {
         self.0.amount()
     }
+
+    /// Returns true if both amounts are in the same currency and differ by at
+    /// most `epsilon`.
+    ///
+    /// Useful for comparing converted amounts, which rarely match exactly
+    /// after multiplying by an exchange rate.
+    pub fn approx_eq(&self, other: &Currency<'_>, epsilon: Decimal) -> bool {
+        self.code() == other.code() && (self.amount() - other.amount()).abs() <= epsilon
+    }
 }
 
 impl<'a> Display for Currency<'a> {
@@ -1,12 +1,14 @@
 //! Ledger entry model for representing financial transactions.
 
 use crate::error::{BeansError, BeansResult};
+use crate::interop::EntryData;
+use crate::models::currency::is_supported_currency;
 use crate::models::{Currency, Tag};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveTime, Utc};
 use rust_decimal::Decimal;
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::str::FromStr;
 use uuid::Uuid;
@@ -74,6 +76,9 @@ pub struct LedgerEntry {
     description: Option<String>,
     /// Tags for categorizing the transaction.
     tags: HashSet<Tag>,
+    /// Arbitrary key/value metadata, e.g. an invoice number.
+    #[serde(default)]
+    metadata: HashMap<String, String>,
     /// Type of the transaction (income or expense).
     entry_type: EntryType,
     /// Date and time the entry was created.
@@ -110,6 +115,44 @@ impl LedgerEntry {
         self.amount
     }
 
+    /// Validates the fields of an entry without building it.
+    ///
+    /// Applies the same rules as [`LedgerEntryBuilder::build`]: the name must
+    /// not be empty, the currency must be a supported ISO code and the amount
+    /// must be positive. This lets frontends report errors as the user types.
+    pub fn validate_fields(
+        name: &str,
+        currency_code: &str,
+        amount: Decimal,
+        _entry_type: EntryType,
+    ) -> BeansResult<()> {
+        if name.trim().is_empty() {
+            return Err(BeansError::validation("Entry name cannot be empty"));
+        }
+
+        if !is_supported_currency(currency_code) {
+            return Err(BeansError::validation(format!(
+                "Unsupported currency code: '{}'",
+                currency_code
+            )));
+        }
+
+        // Validate amount is positive
+        if amount <= Decimal::ZERO {
+            return Err(BeansError::validation("Entry amount must be positive"));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the amount with its sign: positive for income, negative for expenses.
+    pub fn signed_amount(&self) -> Decimal {
+        match self.entry_type {
+            EntryType::Income => self.amount,
+            EntryType::Expense => -self.amount,
+        }
+    }
+
     /// Returns the description of the transaction, if any.
     pub fn description(&self) -> Option<&str> {
         self.description.as_deref()
@@ -120,6 +163,20 @@ impl LedgerEntry {
         &self.tags
     }
 
+    /// Returns the tags for the transaction, sorted by name.
+    ///
+    /// Use this to display tags; [`tags`](Self::tags) has no stable order.
+    pub fn sorted_tags(&self) -> Vec<&Tag> {
+        let mut tags: Vec<&Tag> = self.tags.iter().collect();
+        tags.sort_by(|a, b| a.name().cmp(b.name()));
+        tags
+    }
+
+    /// Returns the metadata attached to the transaction.
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
     /// Returns the type of the transaction.
     pub fn entry_type(&self) -> EntryType {
         self.entry_type
@@ -144,6 +201,13 @@ impl LedgerEntry {
         entry
     }
 
+    /// Returns a copy of this entry with a different transaction date.
+    pub fn with_date(&self, date: DateTime<Utc>) -> Self {
+        let mut entry = self.clone();
+        entry.date = date;
+        entry
+    }
+
     /// Returns true if this entry has the specified tag.
     pub fn has_tag(&self, tag_name: &str) -> bool {
         let normalized = tag_name.trim().to_lowercase();
@@ -173,6 +237,16 @@ impl LedgerEntry {
         let c = Currency::new(self.amount, &self.currency_code);
         return c;
     }
+
+    /// Returns a short code for referring to this entry, the first 8 hex
+    /// characters of its ID.
+    ///
+    /// Short codes are not guaranteed to be unique; resolve them with
+    /// `LedgerManager::get_by_prefix`, which rejects ambiguous prefixes.
+    pub fn short_id(&self) -> String {
+        self.id.simple().to_string()[..8].to_string()
+    }
+
     /// Returns a summary string of this entry.
     ///
     /// Format: "[date] [name] ([currency] [amount]) [tags]"
@@ -180,12 +254,10 @@ impl LedgerEntry {
         let tags_str = if self.tags.is_empty() {
             String::new()
         } else {
-            let mut tags: Vec<_> = self.tags.iter().collect();
-            tags.sort_by(|a, b| a.name().cmp(b.name()));
-
             format!(
                 " [{}]",
-                tags.iter()
+                self.sorted_tags()
+                    .iter()
                     .map(|tag| tag.name())
                     .collect::<Vec<_>>()
                     .join(", ")
@@ -219,6 +291,7 @@ pub struct LedgerEntryBuilder {
     amount: Option<Decimal>,
     description: Option<String>,
     tags: HashSet<Tag>,
+    metadata: HashMap<String, String>,
     entry_type: Option<EntryType>,
     created_at: Option<DateTime<Utc>>,
     updated_at: Option<DateTime<Utc>>,
@@ -297,6 +370,15 @@ impl LedgerEntryBuilder {
         self
     }
 
+    /// Adds a metadata key/value pair to the transaction.
+    ///
+    /// Keys are trimmed; setting the same key twice keeps the last value.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata
+            .insert(key.into().trim().to_string(), value.into());
+        self
+    }
+
     /// Sets the type of the transaction (income or expense).
     ///
     /// This field is required.
@@ -325,10 +407,6 @@ impl LedgerEntryBuilder {
             .name
             .ok_or_else(|| BeansError::validation("Entry name is required"))?;
 
-        if name.trim().is_empty() {
-            return Err(BeansError::validation("Entry name cannot be empty"));
-        }
-
         let currency_code = self
             .currency_code
             .ok_or_else(|| BeansError::validation("Entry currency is required"))?;
@@ -337,15 +415,16 @@ impl LedgerEntryBuilder {
             .amount
             .ok_or_else(|| BeansError::validation("Entry amount is required"))?;
 
-        // Validate amount is positive
-        if amount <= Decimal::ZERO {
-            return Err(BeansError::validation("Entry amount must be positive"));
-        }
-
         let entry_type = self
             .entry_type
             .ok_or_else(|| BeansError::validation("Entry type is required"))?;
 
+        LedgerEntry::validate_fields(&name, &currency_code, amount, entry_type)?;
+
+        if self.metadata.keys().any(|key| key.is_empty()) {
+            return Err(BeansError::validation("Metadata keys cannot be empty"));
+        }
+
         Ok(LedgerEntry {
             id: self.id.unwrap_or_else(Uuid::new_v4),
             date: self.date.unwrap_or_else(Utc::now),
@@ -354,6 +433,7 @@ impl LedgerEntryBuilder {
             amount,
             description: self.description,
             tags: self.tags,
+            metadata: self.metadata,
             entry_type,
             created_at: self.created_at.unwrap_or(now),
             updated_at: self.updated_at.unwrap_or(now),
@@ -372,9 +452,20 @@ impl LedgerEntryBuilder {
             amount: Some(entry.amount),
             description: entry.description.clone(),
             tags: entry.tags.clone(),
+            metadata: entry.metadata.clone(),
             entry_type: Some(entry.entry_type),
             created_at: Some(entry.created_at),
             updated_at: Some(entry.updated_at),
         }
     }
+
+    /// Creates a builder from frontend entry data, parsing its date, amount,
+    /// type, tags and ID.
+    ///
+    /// Date-only values are placed at midnight UTC. Parse errors are returned
+    /// here; the remaining checks, such as an unknown currency, happen when
+    /// the entry is built, so fields can still be adjusted first.
+    pub fn from_entry_data(data: &EntryData) -> BeansResult<Self> {
+        data.to_builder(NaiveTime::MIN)
+    }
 }
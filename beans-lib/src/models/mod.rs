@@ -2,6 +2,8 @@
 pub mod currency;
 pub mod entry;
 mod tag;
+pub mod template;
 pub use currency::Currency;
 pub use entry::{EntryType, LedgerEntry, LedgerEntryBuilder};
 pub use tag::Tag;
+pub use template::{EntryTemplate, TemplateOverrides};
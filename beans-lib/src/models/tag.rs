@@ -76,6 +76,40 @@ impl Tag {
         Ok(Self { name })
     }
 
+    /// Creates a tag from free-form text, fixing up what [`Tag::new`] would
+    /// reject.
+    ///
+    /// Runs of spaces and other disallowed characters become a single
+    /// hyphen, leading and trailing hyphens are dropped, and overly long
+    /// names are truncated. Fails only if nothing usable is left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beans_lib::models::Tag;
+    ///
+    /// assert_eq!(Tag::sanitize("Food & Drink").unwrap().name(), "food-drink");
+    /// assert!(Tag::sanitize("#!?").is_err());
+    /// ```
+    pub fn sanitize(name: impl AsRef<str>) -> BeansResult<Self> {
+        let mut sanitized = String::new();
+        for c in name.as_ref().trim().to_lowercase().chars() {
+            if c.is_alphanumeric() || c == '_' {
+                if sanitized.len() + c.len_utf8() > MAX_TAG_LENGTH {
+                    break;
+                }
+                sanitized.push(c);
+            } else if !sanitized.is_empty() && !sanitized.ends_with('-') {
+                if sanitized.len() + 1 > MAX_TAG_LENGTH {
+                    break;
+                }
+                sanitized.push('-');
+            }
+        }
+
+        Self::new(sanitized.trim_end_matches('-'))
+    }
+
     /// Returns the tag name.
     pub fn name(&self) -> &str {
         &self.name
@@ -0,0 +1,60 @@
+//! Entry templates for quickly adding common transactions.
+
+use crate::models::{EntryType, LedgerEntryBuilder, Tag};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// A reusable set of entry fields, e.g. "Coffee" as a $5 expense tagged `coffee`.
+///
+/// Templates hold everything an entry needs except its date, which is set
+/// when the template is used. The amount is optional so that templates can
+/// also describe transactions whose amount varies.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntryTemplate {
+    /// Name given to entries created from this template.
+    pub name: String,
+    /// Currency of the transaction (ISO Code).
+    pub currency_code: String,
+    /// Default amount, if any.
+    pub amount: Option<Decimal>,
+    /// Optional description of the transaction.
+    pub description: Option<String>,
+    /// Tags applied to entries created from this template.
+    pub tags: Vec<Tag>,
+    /// Type of the transaction (income or expense).
+    pub entry_type: EntryType,
+}
+
+/// Values that replace a template's defaults when creating an entry.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateOverrides {
+    /// Date of the transaction, defaults to now.
+    pub date: Option<DateTime<Utc>>,
+    /// Amount of the transaction, replacing the template's amount.
+    pub amount: Option<Decimal>,
+    /// Description of the transaction, replacing the template's description.
+    pub description: Option<String>,
+}
+
+impl EntryTemplate {
+    /// Returns a builder pre-populated with this template and the given overrides.
+    pub fn to_builder(&self, overrides: &TemplateOverrides) -> LedgerEntryBuilder {
+        let mut builder = LedgerEntryBuilder::new()
+            .name(self.name.clone())
+            .currency_code(self.currency_code.clone())
+            .entry_type(self.entry_type)
+            .tags(self.tags.iter().cloned())
+            .date(overrides.date.unwrap_or_else(Utc::now));
+
+        if let Some(amount) = overrides.amount.or(self.amount) {
+            builder = builder.amount(amount);
+        }
+
+        if let Some(description) = overrides.description.as_ref().or(self.description.as_ref()) {
+            builder = builder.description(description.clone());
+        }
+
+        builder
+    }
+}
@@ -0,0 +1,5 @@
+//! Plain-text rendering of ledger data for terminal tools.
+
+mod table;
+
+pub use table::entries_table;
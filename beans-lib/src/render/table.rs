@@ -0,0 +1,104 @@
+//! Fixed-width table of ledger entries.
+
+use crate::models::LedgerEntry;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Widest a name may be before it is truncated.
+const MAX_NAME_WIDTH: usize = 24;
+
+/// Space between columns.
+const COLUMN_GAP: &str = "  ";
+
+/// Renders entries as an aligned table with a header row.
+///
+/// Columns are date, name, amount, currency, type and tags. Names wider than
+/// 24 columns are truncated with an ellipsis, amounts are right-aligned and
+/// tags are sorted and comma-separated. Widths are measured in terminal
+/// columns, so wide characters such as CJK ideographs count double.
+pub fn entries_table(entries: &[LedgerEntry]) -> String {
+    let header = ["Date", "Name", "Amount", "Currency", "Type", "Tags"].map(String::from);
+    let rows: Vec<[String; 6]> = entries
+        .iter()
+        .map(|entry| {
+            let tags: Vec<&str> = entry.sorted_tags().iter().map(|t| t.name()).collect();
+            [
+                entry.date().format("%Y-%m-%d").to_string(),
+                truncate(entry.name(), MAX_NAME_WIDTH),
+                entry.amount().to_string(),
+                entry.currency_code(),
+                entry.entry_type().to_string(),
+                tags.join(", "),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.each_ref().map(|h| display_width(h));
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(display_width(cell));
+        }
+    }
+
+    let mut table = String::new();
+    push_row(&mut table, &header, &widths);
+    let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    push_line(&mut table, &rule.join(COLUMN_GAP));
+    for row in &rows {
+        push_row(&mut table, row, &widths);
+    }
+    table
+}
+
+/// Appends one padded row; the amount column is right-aligned.
+fn push_row(table: &mut String, cells: &[String; 6], widths: &[usize; 6]) {
+    let mut line = String::new();
+    for (i, (cell, width)) in cells.iter().zip(widths).enumerate() {
+        if i > 0 {
+            line.push_str(COLUMN_GAP);
+        }
+        let padding = " ".repeat(width - display_width(cell));
+        if i == 2 {
+            line.push_str(&padding);
+            line.push_str(cell);
+        } else {
+            line.push_str(cell);
+            line.push_str(&padding);
+        }
+    }
+    push_line(table, &line);
+}
+
+/// Appends a line without trailing padding.
+fn push_line(table: &mut String, line: &str) {
+    table.push_str(line.trim_end());
+    table.push('\n');
+}
+
+/// Shortens `text` to at most `max` columns, ending it with an ellipsis if
+/// anything was cut.
+fn truncate(text: &str, max: usize) -> String {
+    if display_width(text) <= max {
+        return text.to_string();
+    }
+
+    let mut truncated = String::new();
+    let mut width = 0;
+    for c in text.chars() {
+        let w = UnicodeWidthChar::width(c).unwrap_or(0);
+        if width + w + 1 > max {
+            break;
+        }
+        truncated.push(c);
+        width += w;
+    }
+    truncated.push('…');
+    truncated
+}
+
+/// Returns the number of terminal columns `text` occupies.
+///
+/// Combining marks and other zero-width characters take none; East Asian
+/// wide characters and emoji take two.
+fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
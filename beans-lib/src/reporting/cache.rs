@@ -0,0 +1,75 @@
+//! In-memory cache for generated reports.
+
+use crate::models::EntryType;
+use crate::reporting::types::{IncomeExpenseReport, TimePeriod};
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+
+/// Inputs identifying an income/expense report.
+///
+/// Holds the arguments, with the end date as clamped to now when future
+/// entries are excluded, plus every generator setting that changes the
+/// result, since clones of a generator share its cache.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct ReportKey {
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub period: TimePeriod,
+    pub month_anchor_day: u32,
+    pub include_future: bool,
+    pub round_conversions: bool,
+    pub sql_aggregation: bool,
+    pub target_currency: Option<String>,
+    pub tags: Vec<String>,
+    pub entry_type: Option<EntryType>,
+}
+
+/// Least-recently-used cache of income/expense reports.
+///
+/// Every report is stored along with the ledger revision it was generated
+/// from; a lookup with a different revision is a miss, so any mutation of
+/// the ledger invalidates all cached reports.
+#[derive(Debug)]
+pub(crate) struct ReportCache {
+    capacity: usize,
+    entries: VecDeque<(ReportKey, u64, IncomeExpenseReport)>,
+}
+
+impl ReportCache {
+    /// Creates a cache holding at most `capacity` reports.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the cached report for the key, if it was generated at the given revision.
+    pub fn get(&mut self, key: &ReportKey, revision: u64) -> Option<IncomeExpenseReport> {
+        let position = self.entries.iter().position(|(k, _, _)| k == key)?;
+        let (key, cached_revision, report) = self.entries.remove(position)?;
+
+        if cached_revision != revision {
+            // Stale report, drop it
+            return None;
+        }
+
+        // Mark as most recently used
+        self.entries
+            .push_back((key, cached_revision, report.clone()));
+        Some(report)
+    }
+
+    /// Stores a report, evicting the least recently used one if the cache is full.
+    pub fn put(&mut self, key: ReportKey, revision: u64, report: IncomeExpenseReport) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.entries.retain(|(k, _, _)| k != &key);
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((key, revision, report));
+    }
+}
@@ -4,28 +4,61 @@ use crate::currency::CurrencyConverter;
 use crate::database::EntryFilter;
 use crate::error::{BeansError, BeansResult};
 use crate::ledger::LedgerManager;
-use crate::models::{Currency, EntryType};
+use crate::models::currency::find_iso_currency;
+use crate::models::{Currency, EntryType, LedgerEntry, Tag};
+use crate::reporting::cache::{ReportCache, ReportKey};
 use crate::reporting::types::{
-    ExportFormat, IncomeExpenseReport, PeriodSummary, TaggedReport, TimePeriod, TimeSeriesData,
-    TimeSeriesPoint,
+    ExportFormat, IncomeExpenseReport, LedgerComparison, PeriodSummary, Projection, TagBreakdown,
+    TaggedReport, TimePeriod, TimeSeriesData, TimeSeriesPoint,
 };
-use chrono::{DateTime, Datelike, Duration, Utc};
-use rust_decimal::Decimal;
-use std::collections::HashMap;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Datelike, Duration, Months, NaiveTime, SubsecRound, Utc};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
 
 /// Generates reports from ledger data.
 #[derive(Debug, Clone)]
 pub struct ReportGenerator<'a> {
     ledger: &'a LedgerManager,
     converter: Option<CurrencyConverter>,
+    cache: Option<Arc<Mutex<ReportCache>>>,
+    month_anchor_day: u32,
+    date_format: Option<String>,
+    include_future: bool,
+    signed_amounts: bool,
+    max_buckets: usize,
+    display_scale: Option<u32>,
+    sql_aggregation: bool,
+    round_conversions: bool,
+    symbol_override: Option<String>,
 }
 
+/// Number of entries read at a time by [`ReportGenerator::export_entries_json`].
+const EXPORT_PAGE_SIZE: usize = 500;
+
+/// Default limit on the number of periods in a report, enough for about
+/// 27 years of daily data.
+const DEFAULT_MAX_BUCKETS: usize = 10_000;
+
 impl<'a> ReportGenerator<'a> {
     /// Creates a new report generator for the given ledger.
     pub fn new(ledger: &'a LedgerManager) -> Self {
         Self {
             ledger,
             converter: None,
+            cache: None,
+            month_anchor_day: 1,
+            date_format: None,
+            include_future: true,
+            signed_amounts: false,
+            max_buckets: DEFAULT_MAX_BUCKETS,
+            display_scale: None,
+            sql_aggregation: false,
+            round_conversions: false,
+            symbol_override: None,
         }
     }
 
@@ -35,7 +68,128 @@ impl<'a> ReportGenerator<'a> {
         self
     }
 
+    /// Sets the most periods a report may span.
+    ///
+    /// Reports whose range and period would need more buckets fail with
+    /// [`BeansError::RangeTooLarge`] before any entries are read, so a
+    /// mistyped range can't stall the caller. Defaults to 10,000.
+    pub fn with_max_buckets(mut self, max_buckets: usize) -> Self {
+        self.max_buckets = max_buckets;
+        self
+    }
+
+    /// Enables caching of income/expense reports.
+    ///
+    /// Up to `capacity` reports are kept, evicting the least recently used
+    /// one when full. Cached reports are discarded as soon as the ledger is
+    /// modified through its `LedgerManager`.
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = Some(Arc::new(Mutex::new(ReportCache::new(capacity))));
+        self
+    }
+
+    /// Sets the day of the month on which monthly buckets start.
+    ///
+    /// With an anchor day of 15, each monthly bucket runs from the 15th to
+    /// the 14th of the following month. Only days 1 to 28 are accepted so
+    /// that every month contains the anchor day.
+    pub fn with_month_anchor_day(mut self, day: u32) -> BeansResult<Self> {
+        if !(1..=28).contains(&day) {
+            return Err(BeansError::validation(format!(
+                "Month anchor day must be between 1 and 28, got {}",
+                day
+            )));
+        }
+
+        self.month_anchor_day = day;
+        Ok(self)
+    }
+
+    /// Sets the `strftime`-style format used for dates in CSV exports,
+    /// e.g. `%d/%m/%Y` or `%Y-%m-%d`.
+    ///
+    /// By default dates are written in RFC 3339 format. Returns a validation
+    /// error if the format string contains an invalid specifier.
+    pub fn with_date_format(mut self, format: impl Into<String>) -> BeansResult<Self> {
+        let format = format.into();
+        if StrftimeItems::new(&format).any(|item| item == Item::Error) {
+            return Err(BeansError::validation(format!(
+                "Invalid date format: '{}'",
+                format
+            )));
+        }
+
+        self.date_format = Some(format);
+        Ok(self)
+    }
+
+    /// Sets whether entries dated in the future are included in reports.
+    ///
+    /// Defaults to true. When disabled, report end dates are clamped to the
+    /// current time, so scheduled payments don't inflate "to date" figures.
+    pub fn with_include_future(mut self, include_future: bool) -> Self {
+        self.include_future = include_future;
+        self
+    }
+
+    /// Sets whether entry amounts in CSV exports carry their sign.
+    ///
+    /// Defaults to false, where every amount is positive and the `Type`
+    /// column tells income from expenses. When enabled, expenses are written
+    /// as negative amounts, for tools that expect a signed ledger.
+    pub fn with_signed_amounts(mut self, signed_amounts: bool) -> Self {
+        self.signed_amounts = signed_amounts;
+        self
+    }
+
+    /// Sets the number of decimal places amounts are rounded to in CSV
+    /// exports.
+    ///
+    /// Only the exported text is rounded: reports returned by the generator
+    /// and JSON exports keep full precision, so totals can still be
+    /// reconciled exactly. By default amounts are written unrounded.
+    pub fn with_display_scale(mut self, scale: u32) -> Self {
+        self.display_scale = Some(scale);
+        self
+    }
+
+    /// Sets whether each converted amount is rounded to the target
+    /// currency's minor unit before it is added to a total.
+    ///
+    /// By default converted amounts keep full precision and only formatted
+    /// output is rounded, so a total of many conversions can differ by a few
+    /// cents from the sum of the rounded amounts shown per entry. Enable
+    /// this when totals must add up from what each entry would be charged.
+    pub fn with_round_conversions(mut self, round_conversions: bool) -> Self {
+        self.round_conversions = round_conversions;
+        self
+    }
+
+    /// Sets the currency symbol used in formatted amounts, e.g. `US$` to
+    /// tell US dollars from other dollars in reports shared across regions.
+    ///
+    /// Only human-readable text such as [`format_summary`](Self::format_summary)
+    /// uses the symbol. Exports keep plain amounts and ISO currency codes.
+    pub fn with_symbol_override(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol_override = Some(symbol.into());
+        self
+    }
+
+    /// Sets whether income/expense reports without currency conversion
+    /// are summed by the database.
+    ///
+    /// When enabled, entries are totalled per day in SQL and only the daily
+    /// totals are read, which keeps memory flat for ledgers with many
+    /// entries per day. Results are the same either way. Defaults to false.
+    pub fn with_sql_aggregation(mut self, sql_aggregation: bool) -> Self {
+        self.sql_aggregation = sql_aggregation;
+        self
+    }
+
     /// Generates an income vs expense report for the given period.
+    ///
+    /// When `entry_type` is set, only entries of that type are included and
+    /// the other series is all zeros.
     pub async fn income_expense_report(
         &self,
         start_date: DateTime<Utc>,
@@ -43,12 +197,69 @@ impl<'a> ReportGenerator<'a> {
         period: TimePeriod,
         target_currency: Option<Currency<'_>>,
         tags: Option<Vec<String>>,
+        entry_type: Option<EntryType>,
     ) -> BeansResult<IncomeExpenseReport> {
         // Validate date range
         if start_date > end_date {
             return Err(BeansError::InvalidDateRange);
         }
 
+        let end_date = self.effective_end_date(end_date);
+        self.check_bucket_count(start_date, end_date, period)?;
+
+        // Serve the report from the cache if the ledger hasn't changed. The
+        // key holds the clamped end date, so a range reaching past now is
+        // regenerated as time moves on.
+        let revision = self.ledger.revision();
+        let cache_key = ReportKey {
+            start_date,
+            end_date,
+            period,
+            month_anchor_day: self.month_anchor_day,
+            include_future: self.include_future,
+            round_conversions: self.round_conversions,
+            sql_aggregation: self.sql_aggregation,
+            target_currency: target_currency.as_ref().map(|c| c.code().to_string()),
+            tags: tags.clone().unwrap_or_default(),
+            entry_type,
+        };
+        if let Some(ref cache) = self.cache {
+            if let Some(report) = cache.lock().unwrap().get(&cache_key, revision) {
+                return Ok(report);
+            }
+        }
+
+        // Without conversion, totals can come from the ledger's monthly
+        // summary cache or be summed by the database
+        if target_currency.is_none() {
+            let report = match self.summary_cache_report(
+                start_date,
+                end_date,
+                period,
+                tags.as_deref(),
+                entry_type,
+            )? {
+                Some(report) => Some(report),
+                None if self.sql_aggregation => Some(self.aggregated_income_expense_report(
+                    start_date,
+                    end_date,
+                    period,
+                    tags.clone(),
+                    entry_type,
+                )?),
+                None => None,
+            };
+            if let Some(report) = report {
+                if let Some(ref cache) = self.cache {
+                    cache
+                        .lock()
+                        .unwrap()
+                        .put(cache_key, revision, report.clone());
+                }
+                return Ok(report);
+            }
+        }
+
         // Create filters for income and expenses
         let income_filter = EntryFilter {
             start_date: Some(start_date),
@@ -66,12 +277,20 @@ impl<'a> ReportGenerator<'a> {
             ..Default::default()
         };
 
-        // Get all entries
-        let income_entries = self.ledger.list_entries(&income_filter)?;
-        let expense_entries = self.ledger.list_entries(&expense_filter)?;
+        // Get all entries, leaving out the series that wasn't requested
+        let income_entries = if entry_type != Some(EntryType::Expense) {
+            self.ledger.list_entries(&income_filter)?
+        } else {
+            Vec::new()
+        };
+        let expense_entries = if entry_type != Some(EntryType::Income) {
+            self.ledger.list_entries(&expense_filter)?
+        } else {
+            Vec::new()
+        };
 
-        // Generate time series data
-        let income_series = self
+        // Generate time series data, noting every exchange rate applied
+        let (income_series, mut rates_used) = self
             .generate_time_series(
                 "Income",
                 &income_entries,
@@ -82,7 +301,7 @@ impl<'a> ReportGenerator<'a> {
             )
             .await?;
 
-        let expense_series = self
+        let (expense_series, expense_rates) = self
             .generate_time_series(
                 "Expenses",
                 &expense_entries,
@@ -93,6 +312,8 @@ impl<'a> ReportGenerator<'a> {
             )
             .await?;
 
+        rates_used.extend(expense_rates);
+
         // Calculate overall summary
         let total_income: Decimal = income_series.points.iter().map(|p| p.value).sum();
         let total_expenses: Decimal = expense_series.points.iter().map(|p| p.value).sum();
@@ -103,11 +324,42 @@ impl<'a> ReportGenerator<'a> {
             net: total_income - total_expenses,
         };
 
-        Ok(IncomeExpenseReport {
+        let report = IncomeExpenseReport {
             income_series,
             expense_series,
             summary,
-        })
+            converted: !rates_used.is_empty(),
+            rates_used: rates_used
+                .into_iter()
+                .map(|((from, to), rate)| (from, to, rate))
+                .collect(),
+        };
+
+        if let Some(ref cache) = self.cache {
+            cache
+                .lock()
+                .unwrap()
+                .put(cache_key, revision, report.clone());
+        }
+
+        Ok(report)
+    }
+
+    /// Generates an income vs expense report covering every entry in the ledger.
+    ///
+    /// The range runs from the earliest to the latest entry date. An empty
+    /// ledger produces an empty report for the current period.
+    pub async fn full_range_report(&self, period: TimePeriod) -> BeansResult<IncomeExpenseReport> {
+        let (start_date, end_date) = match self.ledger.date_span()? {
+            Some(span) => span,
+            None => {
+                let now = Utc::now();
+                (now, now)
+            }
+        };
+
+        self.income_expense_report(start_date, end_date, period, None, None, None)
+            .await
     }
 
     /// Calculates a summary for the given period.
@@ -122,6 +374,7 @@ impl<'a> ReportGenerator<'a> {
         if start_date > end_date {
             return Err(BeansError::InvalidDateRange);
         }
+        let end_date = self.effective_end_date(end_date);
 
         // Create filters
         let filter = EntryFilter {
@@ -158,6 +411,88 @@ impl<'a> ReportGenerator<'a> {
         })
     }
 
+    /// Summarizes two ledgers over the same period, e.g. business and
+    /// personal books, and adds their totals together.
+    ///
+    /// Both ledgers are read with this generator's settings, including its
+    /// converter, so amounts are converted to `target_currency` the same way
+    /// as in [`period_summary`](Self::period_summary). The generator's own
+    /// ledger is not read.
+    pub async fn compare_ledgers(
+        &self,
+        first: &LedgerManager,
+        second: &LedgerManager,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        target_currency: Option<Currency<'_>>,
+    ) -> BeansResult<LedgerComparison> {
+        let first = self
+            .for_ledger(first)
+            .period_summary(start_date, end_date, target_currency.clone(), None)
+            .await?;
+        let second = self
+            .for_ledger(second)
+            .period_summary(start_date, end_date, target_currency, None)
+            .await?;
+        let income = first.income + second.income;
+        let expenses = first.expenses + second.expenses;
+
+        Ok(LedgerComparison {
+            combined: PeriodSummary {
+                income,
+                expenses,
+                net: income - expenses,
+            },
+            first,
+            second,
+        })
+    }
+
+    /// Projects a period's totals from its pace so far, e.g. where this
+    /// month will land based on spending up to today.
+    ///
+    /// Totals from `start_date` to `as_of` are extrapolated linearly to
+    /// `period_end`: the projection assumes money keeps coming in and going
+    /// out at the same average rate per second. It knows nothing about
+    /// recurring bills or paydays, so a salary received on the 1st will be
+    /// projected to repeat. `as_of` must fall at least a millisecond after
+    /// `start_date` and no later than `period_end`.
+    pub async fn project_period(
+        &self,
+        start_date: DateTime<Utc>,
+        as_of: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        target_currency: Option<Currency<'_>>,
+    ) -> BeansResult<Projection> {
+        if as_of <= start_date || as_of > period_end {
+            return Err(BeansError::InvalidDateRange);
+        }
+
+        // Millisecond precision, so a run shorter than that has no pace
+        let elapsed = Decimal::from((as_of - start_date).num_milliseconds());
+        if elapsed.is_zero() {
+            return Err(BeansError::InvalidDateRange);
+        }
+        let total = Decimal::from((period_end - start_date).num_milliseconds());
+        let ratio = total / elapsed;
+
+        let actual = self
+            .period_summary(start_date, as_of, target_currency, None)
+            .await?;
+        let scale = |amount: Decimal| amount * ratio;
+        let income = scale(actual.income);
+        let expenses = scale(actual.expenses);
+
+        Ok(Projection {
+            actual,
+            projected: PeriodSummary {
+                income,
+                expenses,
+                net: income - expenses,
+            },
+        })
+    }
+
     /// Generates a report grouped by tags.
     pub async fn tagged_report(
         &self,
@@ -169,6 +504,7 @@ impl<'a> ReportGenerator<'a> {
         if start_date > end_date {
             return Err(BeansError::InvalidDateRange);
         }
+        let end_date = self.effective_end_date(end_date);
 
         // Create filter
         let filter = EntryFilter {
@@ -183,6 +519,7 @@ impl<'a> ReportGenerator<'a> {
         // Group by tags
         let mut income_by_tag: HashMap<String, Decimal> = HashMap::new();
         let mut expenses_by_tag: HashMap<String, Decimal> = HashMap::new();
+        let mut count_by_tag: HashMap<String, usize> = HashMap::new();
         let mut total_income = Decimal::ZERO;
         let mut total_expenses = Decimal::ZERO;
 
@@ -198,14 +535,8 @@ impl<'a> ReportGenerator<'a> {
                 EntryType::Expense => total_expenses += amount,
             }
 
-            // If entry has no tags, use "Untagged"
-            let tags: Vec<String> = if entry.tags().is_empty() {
-                vec!["Untagged".to_string()]
-            } else {
-                entry.tags().iter().map(|t| t.name().to_string()).collect()
-            };
-
-            for tag in tags {
+            for tag in Self::report_tags(&entry) {
+                *count_by_tag.entry(tag.clone()).or_insert(0) += 1;
                 match entry.entry_type() {
                     EntryType::Income => {
                         *income_by_tag.entry(tag.clone()).or_insert(Decimal::ZERO) += amount;
@@ -225,16 +556,26 @@ impl<'a> ReportGenerator<'a> {
             .cloned()
             .collect();
 
+        let mut per_tag = Vec::with_capacity(all_tags.len());
         for tag in all_tags {
             let income = income_by_tag.get(&tag).copied().unwrap_or(Decimal::ZERO);
             let expenses = expenses_by_tag.get(&tag).copied().unwrap_or(Decimal::ZERO);
-            net_by_tag.insert(tag, income - expenses);
+            net_by_tag.insert(tag.clone(), income - expenses);
+            per_tag.push(TagBreakdown {
+                count: count_by_tag.get(&tag).copied().unwrap_or(0),
+                tag,
+                income,
+                expenses,
+                net: income - expenses,
+            });
         }
+        TagBreakdown::sort(&mut per_tag);
 
         Ok(TaggedReport {
             income_by_tag,
             expenses_by_tag,
             net_by_tag,
+            per_tag,
             summary: PeriodSummary {
                 income: total_income,
                 expenses: total_expenses,
@@ -243,6 +584,261 @@ impl<'a> ReportGenerator<'a> {
         })
     }
 
+    /// Returns a tag's net amount (income - expenses) per period.
+    ///
+    /// Useful for tracking a category over time, including tags with both
+    /// income and expenses such as `reimbursable`. Periods without entries
+    /// have a value of zero.
+    pub async fn tag_net_series(
+        &self,
+        tag: &str,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        period: TimePeriod,
+        target_currency: Option<Currency<'_>>,
+    ) -> BeansResult<TimeSeriesData> {
+        let tag = Tag::new(tag)?;
+        let report = self
+            .income_expense_report(
+                start_date,
+                end_date,
+                period,
+                target_currency,
+                Some(vec![tag.name().to_string()]),
+                None,
+            )
+            .await?;
+
+        Ok(TimeSeriesData {
+            name: tag.name().to_string(),
+            ..report.net_series()
+        })
+    }
+
+    /// Breaks each period bucket down by tag, e.g. for a stacked bar chart.
+    ///
+    /// Each bucket maps tag names to the net amount (income - expenses) of
+    /// the entries in that period. An entry with several tags contributes its
+    /// full amount to each of them, so the amounts in a bucket can add up to
+    /// more than the period's net. Entries without tags are counted under
+    /// "Untagged". Buckets without entries have an empty map.
+    ///
+    /// Amounts are converted to `target_currency` if given. Without one, the
+    /// entries must all share a currency, since amounts in different
+    /// currencies can't be added up; otherwise a validation error is returned.
+    pub async fn period_tag_matrix(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        period: TimePeriod,
+        target_currency: Option<Currency<'_>>,
+    ) -> BeansResult<Vec<(DateTime<Utc>, HashMap<String, Decimal>)>> {
+        if start_date > end_date {
+            return Err(BeansError::InvalidDateRange);
+        }
+        let end_date = self.effective_end_date(end_date);
+        self.check_bucket_count(start_date, end_date, period)?;
+
+        let filter = EntryFilter {
+            start_date: Some(start_date),
+            end_date: Some(end_date),
+            ..Default::default()
+        };
+        let entries = self.ledger.list_entries(&filter)?;
+        if target_currency.is_none() {
+            if let Some(first) = entries.first() {
+                if entries
+                    .iter()
+                    .any(|entry| entry.currency_code() != first.currency_code())
+                {
+                    return Err(BeansError::validation(
+                        "Entries use several currencies; a target currency is required",
+                    ));
+                }
+            }
+        }
+
+        let mut matrix: Vec<(DateTime<Utc>, HashMap<String, Decimal>)> = self
+            .generate_time_buckets(start_date, end_date, period)
+            .into_iter()
+            .map(|bucket| (bucket, HashMap::new()))
+            .collect();
+        let positions: HashMap<DateTime<Utc>, usize> = matrix
+            .iter()
+            .enumerate()
+            .map(|(position, (bucket, _))| (*bucket, position))
+            .collect();
+
+        for entry in entries {
+            let bucket = self.get_bucket_for_date(entry.date(), start_date, period);
+            let Some(&position) = positions.get(&bucket) else {
+                continue;
+            };
+
+            let amount = match target_currency {
+                Some(ref target) => self.convert_amount(&entry.currency()?, target).await?,
+                None => entry.amount(),
+            };
+            let net = match entry.entry_type() {
+                EntryType::Income => amount,
+                EntryType::Expense => -amount,
+            };
+            let by_tag = &mut matrix[position].1;
+            for tag in Self::report_tags(&entry) {
+                *by_tag.entry(tag).or_insert(Decimal::ZERO) += net;
+            }
+        }
+
+        Ok(matrix)
+    }
+
+    /// Writes the entries matching the filter as a JSON array, one entry at
+    /// a time.
+    ///
+    /// Entries are read a page at a time and serialized straight into the
+    /// writer, so memory use stays bounded however large
+    /// the ledger is. The filter's sort, limit and offset are respected.
+    /// Returns the number of entries written.
+    pub fn export_entries_json<W: Write>(
+        &self,
+        filter: &EntryFilter,
+        mut writer: W,
+    ) -> BeansResult<usize> {
+        writer.write_all(b"[")?;
+
+        let mut written = 0;
+        let mut offset = filter.offset.unwrap_or(0);
+        loop {
+            let page_size = match filter.limit {
+                Some(limit) => (limit - written).min(EXPORT_PAGE_SIZE),
+                None => EXPORT_PAGE_SIZE,
+            };
+            if page_size == 0 {
+                break;
+            }
+
+            let page = self.ledger.list_entries(&EntryFilter {
+                limit: Some(page_size),
+                offset: Some(offset),
+                ..filter.clone()
+            })?;
+
+            for entry in &page {
+                writer.write_all(if written == 0 { b"\n" } else { b",\n" })?;
+                serde_json::to_writer(&mut writer, entry)
+                    .map_err(|e| BeansError::Json(format!("Failed to serialize entry: {}", e)))?;
+                written += 1;
+            }
+
+            if page.len() < page_size {
+                break;
+            }
+            offset += page.len();
+        }
+
+        writer.write_all(if written == 0 { b"]\n" } else { b"\n]\n" })?;
+
+        Ok(written)
+    }
+
+    /// Exports everything for the given range in one document: the matching
+    /// entries, the period summary and the breakdown by tag.
+    ///
+    /// JSON output is a single object with `entries`, `summary` and `by_tag`
+    /// keys. CSV output contains one section per part, separated by blank lines.
+    pub async fn export_full<W: Write>(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        format: ExportFormat,
+        mut writer: W,
+    ) -> BeansResult<()> {
+        let filter = EntryFilter {
+            start_date: Some(start_date),
+            end_date: Some(self.effective_end_date(end_date)),
+            ..Default::default()
+        };
+        let entries = self.ledger.list_entries(&filter)?;
+        let summary = self
+            .period_summary(start_date, end_date, None, None)
+            .await?;
+        let by_tag = self.tagged_report(start_date, end_date, None).await?;
+
+        let output = match format {
+            ExportFormat::Json => self.export_to_json(&FullExport {
+                entries: &entries,
+                summary: &summary,
+                by_tag: &by_tag,
+            })?,
+            ExportFormat::Csv => {
+                let mut csv = String::from("Date,Name,Type,Amount,Currency,Tags,Description\n");
+                for entry in &entries {
+                    let tags: Vec<&str> = entry.sorted_tags().iter().map(|t| t.name()).collect();
+
+                    csv.push_str(&format!(
+                        "{},{},{},{},{},{},{}\n",
+                        self.format_date(entry.date()),
+                        csv_field(entry.name()),
+                        entry.entry_type(),
+                        self.export_amount(entry),
+                        entry.currency_code(),
+                        csv_field(&tags.join(";")),
+                        csv_field(entry.description().unwrap_or_default()),
+                    ));
+                }
+
+                csv.push('\n');
+                csv.push_str(&self.export_tagged_to_csv(&by_tag)?);
+                csv
+            }
+        };
+
+        writer.write_all(output.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Formats a summary on one line in the given currency, like
+    /// [`PeriodSummary::display`], using the symbol override if set.
+    pub fn format_summary(&self, summary: &PeriodSummary, currency_code: &str) -> String {
+        summary.display_with_symbol(currency_code, self.symbol_override.as_deref())
+    }
+
+    /// Exports just a period summary, without any line items.
+    ///
+    /// The summary doesn't record its currency, so pass the code it was
+    /// computed in. JSON output is a single object with `currency`, `income`,
+    /// `expenses` and `net` keys; CSV output is a header and one data row.
+    ///
+    /// Income and expenses are always written as positive totals. A negative
+    /// net is written with a leading `-`, never in parentheses, so
+    /// spreadsheets import it as a number.
+    pub fn export_summary<W: Write>(
+        &self,
+        summary: &PeriodSummary,
+        currency_code: &str,
+        format: ExportFormat,
+        mut writer: W,
+    ) -> BeansResult<()> {
+        let output = match format {
+            ExportFormat::Json => self.export_to_json(&SummaryExport {
+                currency: currency_code,
+                summary,
+            })?,
+            ExportFormat::Csv => format!(
+                "Currency,Income,Expenses,Net\n{},{},{},{}\n",
+                csv_field(currency_code),
+                self.display(summary.income),
+                self.display(summary.expenses),
+                self.display(summary.net)
+            ),
+        };
+
+        writer.write_all(output.as_bytes())?;
+
+        Ok(())
+    }
+
     /// Exports an income/expense report to the specified format.
     pub fn export_income_expense_report(
         &self,
@@ -256,6 +852,9 @@ impl<'a> ReportGenerator<'a> {
     }
 
     /// Exports a tagged report to the specified format.
+    ///
+    /// As with [`export_summary`](Self::export_summary), CSV income and
+    /// expense columns are positive and negative nets carry a leading `-`.
     pub fn export_tagged_report(
         &self,
         report: &TaggedReport,
@@ -267,9 +866,33 @@ impl<'a> ReportGenerator<'a> {
         }
     }
 
+    /// Exports an income/expense report together with a tag breakdown, as a
+    /// single document.
+    ///
+    /// CSV output is the income/expense CSV followed by a blank line and the
+    /// tag section with its own header. JSON output is the income/expense
+    /// report with an added `by_tag` key.
+    pub fn export_income_expense_report_with_tags(
+        &self,
+        report: &IncomeExpenseReport,
+        by_tag: &TaggedReport,
+        format: ExportFormat,
+    ) -> BeansResult<String> {
+        match format {
+            ExportFormat::Json => self.export_to_json(&CombinedExport { report, by_tag }),
+            ExportFormat::Csv => {
+                let mut csv = self.export_income_expense_to_csv(report)?;
+                csv.push('\n');
+                csv.push_str(&self.export_tagged_to_csv(by_tag)?);
+                Ok(csv)
+            }
+        }
+    }
+
     // Private helper methods
 
-    /// Generates time series data from entries.
+    /// Generates time series data from entries, along with the exchange rates
+    /// used to convert them.
     async fn generate_time_series(
         &self,
         name: &str,
@@ -278,43 +901,254 @@ impl<'a> ReportGenerator<'a> {
         end_date: DateTime<Utc>,
         period: TimePeriod,
         target_currency: Option<&Currency<'_>>,
-    ) -> BeansResult<TimeSeriesData> {
-        // Generate all time buckets
-        let buckets = self.generate_time_buckets(start_date, end_date, period);
-
+    ) -> BeansResult<(TimeSeriesData, ExchangeRates)> {
         // Aggregate entries into buckets
-        let mut bucket_values: HashMap<DateTime<Utc>, Decimal> = HashMap::new();
+        let mut bucket_values: HashMap<DateTime<Utc>, (Decimal, usize)> = HashMap::new();
+        let mut rates_used = ExchangeRates::new();
 
         for entry in entries {
-            let bucket = self.get_bucket_for_date(entry.date(), period);
+            let bucket = self.get_bucket_for_date(entry.date(), start_date, period);
             let amount = if let Some(target_curr) = target_currency {
-                self.convert_amount(&entry.currency()?, target_curr).await?
+                let (amount, rate) = self
+                    .convert_with_rate(&entry.currency()?, target_curr)
+                    .await?;
+                if let Some(rate) = rate {
+                    rates_used.insert(
+                        (entry.currency_code(), target_curr.code().to_string()),
+                        rate,
+                    );
+                }
+                amount
             } else {
                 entry.amount()
             };
 
-            *bucket_values.entry(bucket).or_insert(Decimal::ZERO) += amount;
+            let (value, count) = bucket_values.entry(bucket).or_insert((Decimal::ZERO, 0));
+            *value += amount;
+            *count += 1;
+        }
+
+        let series = self.build_series(name, &bucket_values, start_date, end_date, period);
+
+        Ok((series, rates_used))
+    }
+
+    /// Builds an income/expense report from per-day totals summed by the
+    /// database, without reading individual entries.
+    fn aggregated_income_expense_report(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        period: TimePeriod,
+        tags: Option<Vec<String>>,
+        entry_type: Option<EntryType>,
+    ) -> BeansResult<IncomeExpenseReport> {
+        let filter = EntryFilter {
+            start_date: Some(start_date),
+            end_date: Some(end_date),
+            entry_type,
+            tags: tags.unwrap_or_default(),
+            ..Default::default()
+        };
+
+        // Days fall entirely within one bucket, so daily totals can be
+        // summed into buckets of any period
+        let mut income_buckets: HashMap<DateTime<Utc>, (Decimal, usize)> = HashMap::new();
+        let mut expense_buckets: HashMap<DateTime<Utc>, (Decimal, usize)> = HashMap::new();
+        for total in self.ledger.daily_totals(&filter)? {
+            let bucket = self.get_bucket_for_date(
+                total.day.and_time(NaiveTime::MIN).and_utc(),
+                start_date,
+                period,
+            );
+            let buckets = match total.entry_type {
+                EntryType::Income => &mut income_buckets,
+                EntryType::Expense => &mut expense_buckets,
+            };
+            let (value, count) = buckets.entry(bucket).or_insert((Decimal::ZERO, 0));
+            *value += total.total;
+            *count += total.count;
+        }
+
+        Ok(self.report_from_buckets(
+            &income_buckets,
+            &expense_buckets,
+            start_date,
+            end_date,
+            period,
+        ))
+    }
+
+    /// Builds a monthly report from the ledger's cached monthly totals, or
+    /// returns `None` if the cache is empty or doesn't fit the report.
+    ///
+    /// The cache holds whole calendar months, so it is only used for
+    /// untagged monthly reports anchored on the 1st that start at midnight
+    /// on the 1st of a month and end in the last second of a month.
+    fn summary_cache_report(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        period: TimePeriod,
+        tags: Option<&[String]>,
+        entry_type: Option<EntryType>,
+    ) -> BeansResult<Option<IncomeExpenseReport>> {
+        let is_month_start = |date: DateTime<Utc>| date.day() == 1 && date.time() == NaiveTime::MIN;
+        let ends_month = end_date
+            .trunc_subsecs(0)
+            .checked_add_signed(Duration::seconds(1))
+            .is_some_and(is_month_start);
+        let aligned = period == TimePeriod::Monthly
+            && self.month_anchor_day == 1
+            && tags.is_none_or(|tags| tags.is_empty())
+            && is_month_start(start_date)
+            && ends_month;
+        if !aligned {
+            return Ok(None);
+        }
+
+        let Some(summaries) = self
+            .ledger
+            .monthly_summaries(start_date.date_naive(), end_date.date_naive())?
+        else {
+            return Ok(None);
+        };
+
+        let mut income_buckets: HashMap<DateTime<Utc>, (Decimal, usize)> = HashMap::new();
+        let mut expense_buckets: HashMap<DateTime<Utc>, (Decimal, usize)> = HashMap::new();
+        for summary in summaries {
+            if entry_type.is_some_and(|entry_type| entry_type != summary.entry_type) {
+                continue;
+            }
+            let buckets = match summary.entry_type {
+                EntryType::Income => &mut income_buckets,
+                EntryType::Expense => &mut expense_buckets,
+            };
+            let bucket = summary.month.and_time(NaiveTime::MIN).and_utc();
+            let (value, count) = buckets.entry(bucket).or_insert((Decimal::ZERO, 0));
+            *value += summary.total;
+            *count += summary.count;
+        }
+
+        Ok(Some(self.report_from_buckets(
+            &income_buckets,
+            &expense_buckets,
+            start_date,
+            end_date,
+            period,
+        )))
+    }
+
+    /// Builds an unconverted report from per-bucket income and expense totals.
+    fn report_from_buckets(
+        &self,
+        income_buckets: &HashMap<DateTime<Utc>, (Decimal, usize)>,
+        expense_buckets: &HashMap<DateTime<Utc>, (Decimal, usize)>,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        period: TimePeriod,
+    ) -> IncomeExpenseReport {
+        let income_series =
+            self.build_series("Income", income_buckets, start_date, end_date, period);
+        let expense_series =
+            self.build_series("Expenses", expense_buckets, start_date, end_date, period);
+
+        let total_income: Decimal = income_series.points.iter().map(|p| p.value).sum();
+        let total_expenses: Decimal = expense_series.points.iter().map(|p| p.value).sum();
+
+        IncomeExpenseReport {
+            income_series,
+            expense_series,
+            summary: PeriodSummary {
+                income: total_income,
+                expenses: total_expenses,
+                net: total_income - total_expenses,
+            },
+            converted: false,
+            rates_used: Vec::new(),
         }
+    }
+
+    /// Builds a series with a point for every bucket in the range, taking
+    /// values and counts from `bucket_values` and zero for missing buckets.
+    fn build_series(
+        &self,
+        name: &str,
+        bucket_values: &HashMap<DateTime<Utc>, (Decimal, usize)>,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        period: TimePeriod,
+    ) -> TimeSeriesData {
+        let buckets = self.generate_time_buckets(start_date, end_date, period);
 
         // Create time series points
         let mut points: Vec<TimeSeriesPoint> = buckets
             .into_iter()
-            .map(|timestamp| TimeSeriesPoint {
-                timestamp,
-                value: bucket_values
+            .map(|timestamp| {
+                let (value, count) = bucket_values
                     .get(&timestamp)
                     .copied()
-                    .unwrap_or(Decimal::ZERO),
+                    .unwrap_or((Decimal::ZERO, 0));
+                TimeSeriesPoint {
+                    timestamp,
+                    value,
+                    count,
+                }
             })
             .collect();
 
         // Sort by timestamp
         points.sort_by_key(|p| p.timestamp);
 
-        Ok(TimeSeriesData {
+        TimeSeriesData {
             name: name.to_string(),
             points,
-        })
+        }
+    }
+
+    /// Returns the tags an entry is reported under, or "Untagged" if it has none.
+    fn report_tags(entry: &LedgerEntry) -> Vec<String> {
+        if entry.tags().is_empty() {
+            vec!["Untagged".to_string()]
+        } else {
+            entry.tags().iter().map(|t| t.name().to_string()).collect()
+        }
+    }
+
+    /// Fails if a report over the range would have more buckets than allowed.
+    ///
+    /// The buckets are counted arithmetically rather than walked, so a huge
+    /// range is rejected straight away.
+    fn check_bucket_count(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        period: TimePeriod,
+    ) -> BeansResult<()> {
+        let first = self.get_bucket_for_date(start_date, start_date, period);
+        let last = self.get_bucket_for_date(end_date, start_date, period);
+        // Bucket starts are aligned to the period, so whole steps between
+        // them count the buckets after the first
+        let steps = match period {
+            TimePeriod::Daily => (last - first).num_days(),
+            TimePeriod::Weekly => (last - first).num_weeks(),
+            TimePeriod::Monthly => {
+                i64::from(last.year() - first.year()) * 12 + i64::from(last.month())
+                    - i64::from(first.month())
+            }
+            TimePeriod::Yearly => i64::from(last.year() - first.year()),
+            TimePeriod::Total => 0,
+        };
+        let buckets = usize::try_from(steps + 1).unwrap_or(0);
+
+        if buckets > self.max_buckets {
+            return Err(BeansError::RangeTooLarge {
+                buckets,
+                max: self.max_buckets,
+            });
+        }
+
+        Ok(())
     }
 
     /// Generates time buckets for the given period.
@@ -325,19 +1159,25 @@ impl<'a> ReportGenerator<'a> {
         period: TimePeriod,
     ) -> Vec<DateTime<Utc>> {
         let mut buckets = Vec::new();
-        let mut current = self.get_bucket_for_date(start_date, period);
-        let end_bucket = self.get_bucket_for_date(end_date, period);
+        let mut current = Some(self.get_bucket_for_date(start_date, start_date, period));
+        let end_bucket = self.get_bucket_for_date(end_date, start_date, period);
 
-        while current <= end_bucket {
-            buckets.push(current);
-            current = self.next_bucket(current, period);
+        while let Some(bucket) = current.filter(|bucket| *bucket <= end_bucket) {
+            buckets.push(bucket);
+            current = self.next_bucket(bucket, period);
         }
 
         buckets
     }
 
-    /// Gets the bucket (normalized timestamp) for a given date.
-    fn get_bucket_for_date(&self, date: DateTime<Utc>, period: TimePeriod) -> DateTime<Utc> {
+    /// Gets the bucket (normalized timestamp) for a given date in a report
+    /// starting at `start_date`.
+    fn get_bucket_for_date(
+        &self,
+        date: DateTime<Utc>,
+        start_date: DateTime<Utc>,
+        period: TimePeriod,
+    ) -> DateTime<Utc> {
         match period {
             TimePeriod::Daily => {
                 // Start of day
@@ -353,9 +1193,16 @@ impl<'a> ReportGenerator<'a> {
                 start_of_week.and_hms_opt(0, 0, 0).unwrap().and_utc()
             }
             TimePeriod::Monthly => {
-                // Start of month
-                date.date_naive()
-                    .with_day(1)
+                // Start of month, shifted to the anchor day. Dates before the
+                // anchor day belong to the previous month's bucket.
+                let date = date.date_naive();
+                let month_start = if date.day() >= self.month_anchor_day {
+                    date
+                } else {
+                    date.checked_sub_months(Months::new(1)).unwrap()
+                };
+                month_start
+                    .with_day(self.month_anchor_day)
                     .unwrap()
                     .and_hms_opt(0, 0, 0)
                     .unwrap()
@@ -371,14 +1218,19 @@ impl<'a> ReportGenerator<'a> {
                     .unwrap()
                     .and_utc()
             }
+            TimePeriod::Total => {
+                // A single bucket for the whole range
+                start_date
+            }
         }
     }
 
-    /// Gets the next bucket after the current one.
-    fn next_bucket(&self, current: DateTime<Utc>, period: TimePeriod) -> DateTime<Utc> {
+    /// Gets the next bucket after the current one, or `None` if there is
+    /// none: for [`TimePeriod::Total`], or past the latest representable date.
+    fn next_bucket(&self, current: DateTime<Utc>, period: TimePeriod) -> Option<DateTime<Utc>> {
         match period {
-            TimePeriod::Daily => current + Duration::days(1),
-            TimePeriod::Weekly => current + Duration::weeks(1),
+            TimePeriod::Daily => current.checked_add_signed(Duration::days(1)),
+            TimePeriod::Weekly => current.checked_add_signed(Duration::weeks(1)),
             TimePeriod::Monthly => {
                 // Add one month
                 let month = current.month();
@@ -388,25 +1240,26 @@ impl<'a> ReportGenerator<'a> {
                 } else {
                     (month + 1, year)
                 };
-                current
-                    .date_naive()
-                    .with_year(next_year)
-                    .and_then(|d| d.with_month(next_month))
-                    .unwrap()
-                    .and_hms_opt(0, 0, 0)
-                    .unwrap()
-                    .and_utc()
+                Some(
+                    current
+                        .date_naive()
+                        .with_year(next_year)?
+                        .with_month(next_month)?
+                        .and_time(NaiveTime::MIN)
+                        .and_utc(),
+                )
             }
             TimePeriod::Yearly => {
                 // Add one year
-                current
-                    .date_naive()
-                    .with_year(current.year() + 1)
-                    .unwrap()
-                    .and_hms_opt(0, 0, 0)
-                    .unwrap()
-                    .and_utc()
+                Some(
+                    current
+                        .date_naive()
+                        .with_year(current.year() + 1)?
+                        .and_time(NaiveTime::MIN)
+                        .and_utc(),
+                )
             }
+            TimePeriod::Total => None,
         }
     }
 
@@ -416,15 +1269,36 @@ impl<'a> ReportGenerator<'a> {
         from_currency: &Currency<'_>,
         to_currency: &Currency<'_>,
     ) -> BeansResult<Decimal> {
+        Ok(self.convert_with_rate(from_currency, to_currency).await?.0)
+    }
+
+    /// Converts a currency amount to the target currency, returning the rate
+    /// applied, or `None` if no conversion was needed.
+    async fn convert_with_rate(
+        &self,
+        from_currency: &Currency<'_>,
+        to_currency: &Currency<'_>,
+    ) -> BeansResult<(Decimal, Option<Decimal>)> {
         // If currencies are the same, no conversion needed
         if from_currency.code() == to_currency.code() {
-            return Ok(*from_currency.amount());
+            return Ok((*from_currency.amount(), None));
         }
 
         // Use converter if available
         if let Some(ref converter) = self.converter {
-            let converted = converter.convert_amount(from_currency, to_currency).await?;
-            Ok(*converted.amount())
+            let (converted, rate) = converter
+                .convert_with_rate(from_currency, to_currency)
+                .await?;
+            let mut amount = *converted.amount();
+            if self.round_conversions {
+                if let Some(currency) = find_iso_currency(to_currency.code()) {
+                    amount = amount.round_dp_with_strategy(
+                        currency.exponent,
+                        RoundingStrategy::MidpointAwayFromZero,
+                    );
+                }
+            }
+            Ok((amount, Some(rate)))
         } else {
             // No converter available
             Err(BeansError::currency(format!(
@@ -435,6 +1309,66 @@ impl<'a> ReportGenerator<'a> {
         }
     }
 
+    /// Returns a generator with the same settings reading another ledger.
+    ///
+    /// The cache is not shared, since cached reports belong to this ledger.
+    fn for_ledger<'b>(&self, ledger: &'b LedgerManager) -> ReportGenerator<'b> {
+        ReportGenerator {
+            ledger,
+            converter: self.converter.clone(),
+            cache: None,
+            month_anchor_day: self.month_anchor_day,
+            date_format: self.date_format.clone(),
+            include_future: self.include_future,
+            signed_amounts: self.signed_amounts,
+            max_buckets: self.max_buckets,
+            display_scale: self.display_scale,
+            sql_aggregation: self.sql_aggregation,
+            round_conversions: self.round_conversions,
+            symbol_override: self.symbol_override.clone(),
+        }
+    }
+
+    /// Returns the end date to use for a report, excluding the future if configured.
+    fn effective_end_date(&self, end_date: DateTime<Utc>) -> DateTime<Utc> {
+        if self.include_future {
+            end_date
+        } else {
+            end_date.min(Utc::now())
+        }
+    }
+
+    /// Formats a date for export using the configured date format.
+    fn format_date(&self, date: DateTime<Utc>) -> String {
+        match self.date_format {
+            Some(ref format) => date.format(format).to_string(),
+            None => date.to_rfc3339(),
+        }
+    }
+
+    /// Returns an entry's amount as written to CSV exports.
+    fn export_amount(&self, entry: &LedgerEntry) -> Decimal {
+        if self.signed_amounts {
+            self.display(entry.signed_amount())
+        } else {
+            self.display(entry.amount())
+        }
+    }
+
+    /// Rounds an amount to the display scale, if one is set, padding it
+    /// with trailing zeros so every value shows the same number of places.
+    fn display(&self, value: Decimal) -> Decimal {
+        match self.display_scale {
+            Some(scale) => {
+                let mut value =
+                    value.round_dp_with_strategy(scale, RoundingStrategy::MidpointAwayFromZero);
+                value.rescale(scale);
+                value
+            }
+            None => value,
+        }
+    }
+
     /// Exports data to JSON format.
     fn export_to_json<T: serde::Serialize>(&self, data: &T) -> BeansResult<String> {
         serde_json::to_string_pretty(data)
@@ -475,17 +1409,23 @@ impl<'a> ReportGenerator<'a> {
 
             csv.push_str(&format!(
                 "{},{},{}\n",
-                timestamp.to_rfc3339(),
-                income,
-                expenses
+                self.format_date(timestamp),
+                self.display(income),
+                self.display(expenses)
             ));
         }
 
         // Add summary
         csv.push_str(&format!("\nSummary\n"));
-        csv.push_str(&format!("Total Income,{}\n", report.summary.income));
-        csv.push_str(&format!("Total Expenses,{}\n", report.summary.expenses));
-        csv.push_str(&format!("Net,{}\n", report.summary.net));
+        csv.push_str(&format!(
+            "Total Income,{}\n",
+            self.display(report.summary.income)
+        ));
+        csv.push_str(&format!(
+            "Total Expenses,{}\n",
+            self.display(report.summary.expenses)
+        ));
+        csv.push_str(&format!("Net,{}\n", self.display(report.summary.net)));
 
         Ok(csv)
     }
@@ -521,15 +1461,63 @@ impl<'a> ReportGenerator<'a> {
                 .copied()
                 .unwrap_or(Decimal::ZERO);
 
-            csv.push_str(&format!("{},{},{},{}\n", tag, income, expenses, net));
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                tag,
+                self.display(income),
+                self.display(expenses),
+                self.display(net)
+            ));
         }
 
         // Add summary
         csv.push_str(&format!("\nSummary\n"));
-        csv.push_str(&format!("Total Income,{}\n", report.summary.income));
-        csv.push_str(&format!("Total Expenses,{}\n", report.summary.expenses));
-        csv.push_str(&format!("Net,{}\n", report.summary.net));
+        csv.push_str(&format!(
+            "Total Income,{}\n",
+            self.display(report.summary.income)
+        ));
+        csv.push_str(&format!(
+            "Total Expenses,{}\n",
+            self.display(report.summary.expenses)
+        ));
+        csv.push_str(&format!("Net,{}\n", self.display(report.summary.net)));
 
         Ok(csv)
     }
 }
+
+/// Combined document written by [`ReportGenerator::export_full`].
+#[derive(Serialize)]
+struct FullExport<'a> {
+    entries: &'a [LedgerEntry],
+    summary: &'a PeriodSummary,
+    by_tag: &'a TaggedReport,
+}
+
+/// Document written by [`ReportGenerator::export_income_expense_report_with_tags`].
+#[derive(Serialize)]
+struct CombinedExport<'a> {
+    #[serde(flatten)]
+    report: &'a IncomeExpenseReport,
+    by_tag: &'a TaggedReport,
+}
+
+/// Exchange rates applied while building a report, keyed by `(from, to)`.
+type ExchangeRates = BTreeMap<(String, String), Decimal>;
+
+/// Document written by [`ReportGenerator::export_summary`].
+#[derive(Serialize)]
+struct SummaryExport<'a> {
+    currency: &'a str,
+    #[serde(flatten)]
+    summary: &'a PeriodSummary,
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
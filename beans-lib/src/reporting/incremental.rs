@@ -0,0 +1,82 @@
+//! Running summaries updated entry by entry.
+
+use crate::error::{BeansError, BeansResult};
+use crate::models::{EntryType, LedgerEntry};
+use crate::reporting::PeriodSummary;
+use rust_decimal::Decimal;
+
+/// A [`PeriodSummary`] kept up to date as entries are added or removed.
+///
+/// Each update is O(1), so a live view can follow a ledger without
+/// recomputing the whole summary. All entries must be in the summary's
+/// currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncrementalSummary {
+    summary: PeriodSummary,
+    currency_code: String,
+}
+
+impl IncrementalSummary {
+    /// Starts from an existing summary computed in the given currency.
+    pub fn new(summary: PeriodSummary, currency_code: impl Into<String>) -> Self {
+        Self {
+            summary,
+            currency_code: currency_code.into(),
+        }
+    }
+
+    /// Starts from an empty summary in the given currency.
+    pub fn empty(currency_code: impl Into<String>) -> Self {
+        Self::new(
+            PeriodSummary {
+                income: Decimal::ZERO,
+                expenses: Decimal::ZERO,
+                net: Decimal::ZERO,
+            },
+            currency_code,
+        )
+    }
+
+    /// Adds an entry to the summary.
+    ///
+    /// Fails, leaving the summary unchanged, if the entry is in another currency.
+    pub fn apply(&mut self, entry: &LedgerEntry) -> BeansResult<()> {
+        self.adjust(entry, entry.amount())
+    }
+
+    /// Removes a previously applied entry from the summary.
+    ///
+    /// Fails, leaving the summary unchanged, if the entry is in another currency.
+    pub fn revert(&mut self, entry: &LedgerEntry) -> BeansResult<()> {
+        self.adjust(entry, -entry.amount())
+    }
+
+    /// Returns the current summary.
+    pub fn summary(&self) -> &PeriodSummary {
+        &self.summary
+    }
+
+    /// Returns the currency the summary is kept in.
+    pub fn currency_code(&self) -> &str {
+        &self.currency_code
+    }
+
+    /// Adds `amount` to the side of the summary given by the entry's type.
+    fn adjust(&mut self, entry: &LedgerEntry, amount: Decimal) -> BeansResult<()> {
+        if entry.currency_code() != self.currency_code {
+            return Err(BeansError::Currency(format!(
+                "Entry in {} can't be added to a summary in {}",
+                entry.currency_code(),
+                self.currency_code
+            )));
+        }
+
+        match entry.entry_type() {
+            EntryType::Income => self.summary.income += amount,
+            EntryType::Expense => self.summary.expenses += amount,
+        }
+        self.summary.net = self.summary.income - self.summary.expenses;
+
+        Ok(())
+    }
+}
@@ -1,10 +1,13 @@
 //! Reporting and analytics module.
 
+mod cache;
 mod generator;
+mod incremental;
 mod types;
 
 pub use generator::ReportGenerator;
+pub use incremental::IncrementalSummary;
 pub use types::{
-    ExportFormat, IncomeExpenseReport, PeriodSummary, TaggedReport, TimePeriod, TimeSeriesData,
-    TimeSeriesPoint,
+    ChartData, ExportFormat, IncomeExpenseReport, LedgerComparison, PeriodSummary, Projection,
+    TagBreakdown, TaggedReport, TimePeriod, TimeSeriesData, TimeSeriesPoint,
 };
@@ -1,7 +1,9 @@
 //! Types for reporting and analytics.
 
+use crate::models::currency::find_iso_currency;
 use chrono::{DateTime, Utc};
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
+use rusty_money::Money;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -16,6 +18,8 @@ pub enum TimePeriod {
     Monthly,
     /// Yearly granularity.
     Yearly,
+    /// The whole range as a single period, starting at the range's start.
+    Total,
 }
 
 /// A single data point in a time series.
@@ -25,6 +29,9 @@ pub struct TimeSeriesPoint {
     pub timestamp: DateTime<Utc>,
     /// Value for the data point.
     pub value: Decimal,
+    /// Number of entries aggregated into the data point.
+    #[serde(default)]
+    pub count: usize,
 }
 
 /// A named series of data points.
@@ -47,6 +54,89 @@ pub struct PeriodSummary {
     pub net: Decimal,
 }
 
+impl PeriodSummary {
+    /// Formats total income as money in the given currency, e.g. `$10,000.00`.
+    ///
+    /// Unknown currency codes fall back to the plain decimal value.
+    pub fn format_income(&self, currency_code: &str) -> String {
+        format_money(self.income, currency_code, None)
+    }
+
+    /// Formats total expenses as money in the given currency.
+    ///
+    /// Unknown currency codes fall back to the plain decimal value.
+    pub fn format_expenses(&self, currency_code: &str) -> String {
+        format_money(self.expenses, currency_code, None)
+    }
+
+    /// Formats the net amount as money in the given currency.
+    ///
+    /// Unknown currency codes fall back to the plain decimal value.
+    pub fn format_net(&self, currency_code: &str) -> String {
+        format_money(self.net, currency_code, None)
+    }
+
+    /// Formats the whole summary on one line in the given currency,
+    /// e.g. `Income: ¥15,000 | Expenses: ¥5,000 | Net: ¥10,000`.
+    ///
+    /// Unknown currency codes fall back to plain decimal values.
+    pub fn display(&self, currency_code: &str) -> String {
+        self.display_with_symbol(currency_code, None)
+    }
+
+    /// Formats the summary like [`display`](Self::display), writing `symbol`
+    /// in place of the currency's own symbol if given.
+    pub(crate) fn display_with_symbol(&self, currency_code: &str, symbol: Option<&str>) -> String {
+        format!(
+            "Income: {} | Expenses: {} | Net: {}",
+            format_money(self.income, currency_code, symbol),
+            format_money(self.expenses, currency_code, symbol),
+            format_money(self.net, currency_code, symbol)
+        )
+    }
+}
+
+/// Side-by-side summaries of two ledgers over the same period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerComparison {
+    /// Summary of the first ledger.
+    pub first: PeriodSummary,
+    /// Summary of the second ledger.
+    pub second: PeriodSummary,
+    /// Totals of both ledgers added together.
+    pub combined: PeriodSummary,
+}
+
+/// Totals for a period so far, and where they are on pace to end up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Projection {
+    /// Totals from the start of the period up to the projection date.
+    pub actual: PeriodSummary,
+    /// Totals extrapolated to the end of the period.
+    pub projected: PeriodSummary,
+}
+
+/// Formats an amount using the currency's symbol, separators and minor units.
+///
+/// A `symbol` replaces the currency's own, keeping its position, e.g.
+/// `US$10.00` instead of `$10.00`.
+fn format_money(amount: Decimal, currency_code: &str, symbol: Option<&str>) -> String {
+    match find_iso_currency(currency_code) {
+        Some(currency) => {
+            // Always show every minor unit, e.g. $10.00 rather than $10
+            let mut amount = amount
+                .round_dp_with_strategy(currency.exponent, RoundingStrategy::MidpointAwayFromZero);
+            amount.rescale(currency.exponent);
+            let formatted = Money::from_decimal(amount, currency).to_string();
+            match symbol {
+                Some(symbol) => formatted.replacen(currency.symbol, symbol, 1),
+                None => formatted,
+            }
+        }
+        None => amount.to_string(),
+    }
+}
+
 /// Income and expense report with time series data.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IncomeExpenseReport {
@@ -56,6 +146,85 @@ pub struct IncomeExpenseReport {
     pub expense_series: TimeSeriesData,
     /// Overall summary for the entire period.
     pub summary: PeriodSummary,
+    /// Whether any amount was converted to the target currency.
+    #[serde(default)]
+    pub converted: bool,
+    /// Exchange rates applied, as `(from, to, rate)`, sorted by currency pair.
+    #[serde(default)]
+    pub rates_used: Vec<(String, String, Decimal)>,
+}
+
+impl IncomeExpenseReport {
+    /// Returns the report's series as aligned arrays, ready for charting.
+    ///
+    /// Both series are generated over the same buckets, so the arrays share
+    /// the `timestamps` index.
+    pub fn chart_data(&self) -> ChartData {
+        ChartData {
+            timestamps: self
+                .income_series
+                .points
+                .iter()
+                .map(|p| p.timestamp)
+                .collect(),
+            income: self.income_series.points.iter().map(|p| p.value).collect(),
+            expenses: self.expense_series.points.iter().map(|p| p.value).collect(),
+        }
+    }
+
+    /// Returns a single series with income minus expenses for each bucket.
+    ///
+    /// Both series share their buckets, so points are combined by position.
+    /// Each point's count is the number of income and expense entries in it.
+    pub fn net_series(&self) -> TimeSeriesData {
+        TimeSeriesData {
+            name: "Net".to_string(),
+            points: self
+                .income_series
+                .points
+                .iter()
+                .zip(&self.expense_series.points)
+                .map(|(income, expense)| TimeSeriesPoint {
+                    timestamp: income.timestamp,
+                    value: income.value - expense.value,
+                    count: income.count + expense.count,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the start of each bucket with its income, expenses and net.
+    ///
+    /// Amounts are in the report's currency, so the summaries can be
+    /// formatted with the same currency code the report was generated for.
+    pub fn period_summaries(&self) -> Vec<(DateTime<Utc>, PeriodSummary)> {
+        self.income_series
+            .points
+            .iter()
+            .zip(&self.expense_series.points)
+            .map(|(income, expense)| {
+                (
+                    income.timestamp,
+                    PeriodSummary {
+                        income: income.value,
+                        expenses: expense.value,
+                        net: income.value - expense.value,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+/// Chart-ready income and expense values, indexed by bucket.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartData {
+    /// Start of each bucket.
+    pub timestamps: Vec<DateTime<Utc>>,
+    /// Income for each bucket.
+    pub income: Vec<Decimal>,
+    /// Expenses for each bucket.
+    pub expenses: Vec<Decimal>,
 }
 
 /// Report grouped by tags.
@@ -67,10 +236,95 @@ pub struct TaggedReport {
     pub expenses_by_tag: HashMap<String, Decimal>,
     /// Net by tag.
     pub net_by_tag: HashMap<String, Decimal>,
+    /// Income, expenses, net and entry count per tag, sorted by net descending.
+    #[serde(default)]
+    pub per_tag: Vec<TagBreakdown>,
     /// Overall summary.
     pub summary: PeriodSummary,
 }
 
+/// Totals for a single tag in a [`TaggedReport`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagBreakdown {
+    /// Tag name.
+    pub tag: String,
+    /// Total income for the tag.
+    pub income: Decimal,
+    /// Total expenses for the tag.
+    pub expenses: Decimal,
+    /// Net amount (income - expenses) for the tag.
+    pub net: Decimal,
+    /// Number of entries with the tag.
+    pub count: usize,
+}
+
+impl TagBreakdown {
+    /// Sorts rows by net descending, breaking ties by tag name.
+    pub(crate) fn sort(rows: &mut [TagBreakdown]) {
+        rows.sort_by(|a, b| b.net.cmp(&a.net).then_with(|| a.tag.cmp(&b.tag)));
+    }
+}
+
+impl TaggedReport {
+    /// Name of the bucket that collects tags below the minimum amount.
+    pub const OTHER_TAG: &'static str = "Other";
+
+    /// Folds tags whose income and expenses are both below `min_amount`
+    /// into a single "Other" bucket.
+    ///
+    /// Amounts are moved, not dropped, so per-tag sums still add up to the
+    /// same totals as before.
+    pub fn with_min_amount(mut self, min_amount: Decimal) -> Self {
+        let small_tags: Vec<String> = self
+            .net_by_tag
+            .keys()
+            .filter(|tag| {
+                let income = self.income_by_tag.get(*tag).copied().unwrap_or_default();
+                let expenses = self.expenses_by_tag.get(*tag).copied().unwrap_or_default();
+                income < min_amount && expenses < min_amount
+            })
+            .cloned()
+            .collect();
+
+        for tag in small_tags {
+            for by_tag in [
+                &mut self.income_by_tag,
+                &mut self.expenses_by_tag,
+                &mut self.net_by_tag,
+            ] {
+                if let Some(amount) = by_tag.remove(&tag) {
+                    *by_tag
+                        .entry(Self::OTHER_TAG.to_string())
+                        .or_insert(Decimal::ZERO) += amount;
+                }
+            }
+
+            if let Some(index) = self.per_tag.iter().position(|row| row.tag == tag) {
+                let row = self.per_tag.remove(index);
+                match self
+                    .per_tag
+                    .iter_mut()
+                    .find(|other| other.tag == Self::OTHER_TAG)
+                {
+                    Some(other) => {
+                        other.income += row.income;
+                        other.expenses += row.expenses;
+                        other.net += row.net;
+                        other.count += row.count;
+                    }
+                    None => self.per_tag.push(TagBreakdown {
+                        tag: Self::OTHER_TAG.to_string(),
+                        ..row
+                    }),
+                }
+            }
+        }
+
+        TagBreakdown::sort(&mut self.per_tag);
+        self
+    }
+}
+
 /// Export format for reports.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExportFormat {
@@ -79,3 +333,24 @@ pub enum ExportFormat {
     /// JSON format.
     Json,
 }
+
+impl ExportFormat {
+    /// Returns the file extension for this format, without the leading dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    /// Returns the format matching a file extension, ignoring case.
+    ///
+    /// Returns `None` for unknown extensions.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.trim_start_matches('.').to_lowercase().as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Integration tests for the async repository adapter.
+#![cfg(feature = "async-repository")]
+
+mod support;
+use beans_lib::database::{
+    initialize_schema, AsyncRepository, BlockingRepository, EntryFilter, SQLiteRepository,
+};
+use beans_lib::error::BeansResult;
+use beans_lib::models::{EntryType, LedgerEntryBuilder, Tag};
+use rust_decimal_macros::dec;
+use support::*;
+
+#[tokio::test]
+async fn test_blocking_repository_adapter() -> BeansResult<()> {
+    let sqlite = SQLiteRepository::in_memory()?;
+    initialize_schema(&sqlite.get_connection()?.lock().unwrap())?;
+    let repo = BlockingRepository::new(sqlite);
+
+    let entry = LedgerEntryBuilder::new()
+        .name("Groceries")
+        .amount(dec!(42.00))
+        .currency_code(usd().to_owned())
+        .entry_type(EntryType::Expense)
+        .tag(Tag::new("food")?)
+        .build()?;
+    repo.create(&entry).await?;
+
+    let retrieved = repo.get(entry.id()).await?;
+    assert_eq!(retrieved.name(), "Groceries");
+
+    let filter = EntryFilter {
+        tags: vec!["food".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(repo.list(&filter).await?.len(), 1);
+    assert_eq!(repo.count(&filter).await?, 1);
+    assert_eq!(repo.stats().await?.entry_count, 1);
+
+    // Clones share the same underlying repository and can run concurrently
+    let other = repo.clone();
+    let (first, second) = tokio::join!(repo.count(&filter), other.count(&filter));
+    assert_eq!(first?, second?);
+
+    repo.delete(entry.id()).await?;
+    assert!(repo.get(entry.id()).await.is_err());
+    assert_eq!(repo.date_span().await?, None);
+
+    Ok(())
+}
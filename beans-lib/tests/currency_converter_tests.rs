@@ -1,8 +1,11 @@
 mod support;
 
-use beans_lib::currency::CurrencyConverter;
+use beans_lib::currency::{CurrencyConverter, RetryPolicy};
 use beans_lib::error::BeansResult;
-use beans_lib::models::Currency;
+use beans_lib::ledger::LedgerManager;
+use beans_lib::models::{Currency, EntryType, LedgerEntryBuilder};
+use beans_lib::reporting::{ReportGenerator, TimePeriod};
+use chrono::{TimeZone, Utc};
 use rust_decimal_macros::dec;
 use std::time::Duration;
 use support::*;
@@ -59,7 +62,8 @@ async fn test_exchange_rate_from_api() -> BeansResult<()> {
     let result = converter.convert_amount(&usd, &eur).await?;
 
     // 100 USD * 0.85 = 85 EUR
-    assert_eq!(*result.amount(), dec!(85.00));
+    let expected = Currency::new(dec!(85.00), eur.code())?;
+    assert!(result.approx_eq(&expected, dec!(0.01)));
     assert_eq!(result.code(), eur.code());
     Ok(())
 }
@@ -111,6 +115,42 @@ async fn test_cache_functionality() -> BeansResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_convert_batch() -> BeansResult<()> {
+    let mock_server = MockServer::start().await;
+    for (base, rate) in [("usd", 0.75), ("eur", 0.5)] {
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/currencies/{}.json", base)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"{{"date": "2025-10-31", "{}": {{"gbp": {}}}}}"#,
+                base, rate
+            )))
+            .expect(1) // One fetch per currency pair, however many items use it
+            .mount(&mock_server)
+            .await;
+    }
+
+    let mut converter = CurrencyConverter::new(Duration::from_secs(24 * 60 * 60));
+    converter.set_base_url(format!("{}/v1", mock_server.uri()));
+
+    let items = vec![
+        (dec!(100.00), usd().to_string()),
+        (dec!(50.00), eur().to_string()),
+        (dec!(10.00), usd().to_string()),
+        (dec!(5.00), "GBP".to_string()),
+    ];
+    let converted = converter
+        .convert_batch(&items, &Currency::new(dec!(0), "GBP")?)
+        .await?;
+
+    assert_eq!(
+        converted,
+        vec![dec!(75.00), dec!(25.00), dec!(7.50), dec!(5.00)]
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_api_error_handling() -> BeansResult<()> {
     // Start a mock server
@@ -266,3 +306,414 @@ async fn test_fallback_url() -> BeansResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_ledger_to_currency() -> BeansResult<()> {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/eur.json"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"{"date": "2025-10-31", "eur": {"usd": 1.25}}"#),
+        )
+        .mount(&mock_server)
+        .await;
+
+    // No rates are available for GBP
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/gbp.json"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+
+    let mut converter = CurrencyConverter::new(Duration::from_secs(24 * 60 * 60));
+    converter.set_base_url(format!("{}/v1", mock_server.uri()));
+
+    let ledger = LedgerManager::in_memory()?;
+    for (name, amount, currency) in [
+        ("Salary", dec!(1000.00), usd()),
+        ("Hotel", dec!(80.00), eur()),
+        ("Tea", dec!(5.00), "GBP"),
+    ] {
+        let entry = LedgerEntryBuilder::new()
+            .name(name)
+            .amount(amount)
+            .currency_code(currency.to_owned())
+            .entry_type(EntryType::Expense)
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    let (converted, summary) = ledger.to_currency(&converter, usd()).await?;
+
+    // Every converted entry is in the target currency
+    let entries = converted.get_all_entries()?;
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|e| e.currency_code() == usd()));
+
+    let hotel = entries.iter().find(|e| e.name() == "Hotel").unwrap();
+    assert_eq!(hotel.amount(), dec!(100.00));
+
+    // The entry that couldn't be converted is reported, not dropped silently
+    assert_eq!(summary.added.len(), 2);
+    assert_eq!(summary.failed.len(), 1);
+    let original = ledger.get_all_entries()?;
+    assert_eq!(original[summary.failed[0].index].name(), "Tea");
+
+    // The original ledger is untouched
+    assert_eq!(original.len(), 3);
+    assert!(original.iter().any(|e| e.currency_code() == eur()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ledger_net_in() -> BeansResult<()> {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/eur.json"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"{"date": "2025-10-31", "eur": {"usd": 1.25}}"#),
+        )
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/gbp.json"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+
+    let mut converter = CurrencyConverter::new(Duration::from_secs(24 * 60 * 60));
+    converter.set_base_url(format!("{}/v1", mock_server.uri()));
+
+    let ledger = LedgerManager::in_memory()?;
+    for (name, amount, currency, entry_type) in [
+        ("Salary", dec!(1000.00), usd(), EntryType::Income),
+        ("Rent", dec!(400.00), eur(), EntryType::Expense),
+        ("Bonus", dec!(100.00), eur(), EntryType::Income),
+    ] {
+        let entry = LedgerEntryBuilder::new()
+            .name(name)
+            .amount(amount)
+            .currency_code(currency.to_owned())
+            .entry_type(entry_type)
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    // 1000 - 400 * 1.25 + 100 * 1.25
+    let target = Currency::new(dec!(0), usd())?;
+    assert_eq!(ledger.net_in(&converter, &target).await?, dec!(625.00));
+
+    // A single failed conversion fails the whole total and names the entry
+    let tea = LedgerEntryBuilder::new()
+        .name("Tea")
+        .amount(dec!(5.00))
+        .currency_code("GBP".to_owned())
+        .entry_type(EntryType::Expense)
+        .build()?;
+    ledger.add_entry(&tea)?;
+
+    let err = ledger.net_in(&converter, &target).await.unwrap_err();
+    assert_eq!(err.kind(), "Currency");
+    assert!(err.to_string().contains("Tea"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retry_transient_failures() -> BeansResult<()> {
+    let mock_server = MockServer::start().await;
+
+    // The first two requests fail with a transient error
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/usd.json"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/usd.json"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"{"date": "2025-10-31", "usd": {"eur": 0.85}}"#),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut converter = CurrencyConverter::new(Duration::from_secs(24 * 60 * 60));
+    converter.set_base_url(format!("{}/v1", mock_server.uri()));
+    converter.set_retry_policy(RetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(1),
+    });
+
+    let usd = Currency::new(dec!(100.00), usd())?;
+    let eur = Currency::new(dec!(0.00), eur())?;
+
+    assert_eq!(converter.get_exchange_rate(&usd, &eur).await?, 0.85);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retry_transient_fallback_failures() -> BeansResult<()> {
+    let primary_server = MockServer::start().await;
+    let fallback_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/usd.json"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&primary_server)
+        .await;
+
+    // The fallback's server error is retried like the primary's
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/usd.json"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&fallback_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/usd.json"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"{"date": "2025-10-31", "usd": {"eur": 0.85}}"#),
+        )
+        .mount(&fallback_server)
+        .await;
+
+    let mut converter = CurrencyConverter::new(Duration::from_secs(24 * 60 * 60));
+    converter.set_base_url(format!("{}/v1", primary_server.uri()));
+    converter.set_fallback_url(format!("{}/v1", fallback_server.uri()));
+    converter.set_retry_policy(RetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(1),
+    });
+
+    let usd = Currency::new(dec!(100.00), usd())?;
+    let eur = Currency::new(dec!(0.00), eur())?;
+
+    assert_eq!(converter.get_exchange_rate(&usd, &eur).await?, 0.85);
+    assert_eq!(fallback_server.received_requests().await.unwrap().len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_no_retry_for_permanent_failures() -> BeansResult<()> {
+    let mock_server = MockServer::start().await;
+
+    // An unknown currency is not worth retrying
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/usd.json"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+
+    let mut converter = CurrencyConverter::new(Duration::from_secs(24 * 60 * 60));
+    converter.set_base_url(format!("{}/v1", mock_server.uri()));
+    converter.set_retry_policy(RetryPolicy {
+        max_attempts: 5,
+        base_delay: Duration::from_millis(1),
+    });
+
+    let usd = Currency::new(dec!(100.00), usd())?;
+    let eur = Currency::new(dec!(0.00), eur())?;
+
+    assert!(converter.get_exchange_rate(&usd, &eur).await.is_err());
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+
+    // Delays double with each retry
+    let policy = RetryPolicy::default();
+    assert_eq!(policy.delay_after(1), Duration::from_millis(100));
+    assert_eq!(policy.delay_after(2), Duration::from_millis(200));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_report_discloses_conversion() -> BeansResult<()> {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/eur.json"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"{"date": "2025-10-31", "eur": {"usd": 1.25}}"#),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut converter = CurrencyConverter::new(Duration::from_secs(24 * 60 * 60));
+    converter.set_base_url(format!("{}/v1", mock_server.uri()));
+
+    let date = Utc.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap();
+    let ledger = LedgerManager::in_memory()?;
+    for (name, amount, currency, entry_type) in [
+        ("Salary", dec!(1000.00), usd(), EntryType::Income),
+        ("Rent", dec!(400.00), eur(), EntryType::Expense),
+    ] {
+        let entry = LedgerEntryBuilder::new()
+            .name(name)
+            .date(date)
+            .amount(amount)
+            .currency_code(currency.to_owned())
+            .entry_type(entry_type)
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    let generator = ReportGenerator::new(&ledger).with_converter(converter);
+    let start = date - chrono::Duration::days(1);
+    let end = date + chrono::Duration::days(1);
+
+    // Income is all in USD already, so nothing is converted
+    let usd_target = || Currency::new(dec!(0), usd()).unwrap();
+    let report = generator
+        .income_expense_report(
+            start,
+            end,
+            TimePeriod::Monthly,
+            Some(usd_target()),
+            None,
+            Some(EntryType::Income),
+        )
+        .await?;
+    assert!(!report.converted);
+    assert!(report.rates_used.is_empty());
+
+    // Expenses in EUR are converted, and the rate is listed
+    let report = generator
+        .income_expense_report(
+            start,
+            end,
+            TimePeriod::Monthly,
+            Some(usd_target()),
+            None,
+            None,
+        )
+        .await?;
+    assert!(report.converted);
+    assert_eq!(
+        report.rates_used,
+        vec![("EUR".to_string(), "USD".to_string(), dec!(1.25))]
+    );
+    let expenses = Currency::new(report.summary.expenses, usd())?;
+    assert!(expenses.approx_eq(&Currency::new(dec!(500.00), usd())?, dec!(0.01)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_round_conversions() -> BeansResult<()> {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/eur.json"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"{"date": "2025-10-31", "eur": {"usd": 1.125}}"#),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let date = Utc.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap();
+    let ledger = LedgerManager::in_memory()?;
+    let entries: Vec<_> = (0..300)
+        .map(|_| {
+            LedgerEntryBuilder::new()
+                .name("Bus ticket")
+                .date(date)
+                .amount(dec!(0.33))
+                .currency_code(eur().to_owned())
+                .entry_type(EntryType::Expense)
+                .build()
+        })
+        .collect::<BeansResult<_>>()?;
+    ledger.add_entries(&entries)?;
+
+    let start = date - chrono::Duration::days(1);
+    let end = date + chrono::Duration::days(1);
+    let expenses = |round: bool| {
+        let mut converter = CurrencyConverter::new(Duration::from_secs(24 * 60 * 60));
+        converter.set_base_url(format!("{}/v1", mock_server.uri()));
+        let generator = ReportGenerator::new(&ledger)
+            .with_converter(converter)
+            .with_round_conversions(round);
+        async move {
+            let summary = generator
+                .period_summary(start, end, Some(Currency::new(dec!(0), usd())?), None)
+                .await?;
+            let report = generator
+                .income_expense_report(
+                    start,
+                    end,
+                    TimePeriod::Monthly,
+                    Some(Currency::new(dec!(0), usd())?),
+                    None,
+                    None,
+                )
+                .await?;
+            assert_eq!(report.summary.expenses, summary.expenses);
+            BeansResult::Ok(summary.expenses)
+        }
+    };
+
+    // Each 0.33 EUR is 0.37125 USD, or 0.37 once rounded
+    let per_entry: rust_decimal::Decimal = entries
+        .iter()
+        .map(|e| (e.amount() * dec!(1.125)).round_dp(2))
+        .sum();
+    assert_eq!(per_entry, dec!(111.00));
+    assert_eq!(expenses(true).await?, per_entry);
+
+    // Without rounding, the exact products are summed
+    assert_eq!(expenses(false).await?, dec!(111.375));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cache_stats() -> BeansResult<()> {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/currencies/usd.json"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"{"date": "2025-10-31", "usd": {"eur": 0.85}}"#),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut converter = CurrencyConverter::new(Duration::from_secs(24 * 60 * 60));
+    converter.set_base_url(format!("{}/v1", mock_server.uri()));
+
+    let usd = Currency::new(dec!(100.00), usd())?;
+    let eur = Currency::new(dec!(0.00), eur())?;
+
+    // The first conversion misses and fetches, the second is served from the cache
+    converter.convert_amount(&usd, &eur).await?;
+    converter.convert_amount(&usd, &eur).await?;
+
+    let stats = converter.cache_stats();
+    assert_eq!(stats.hits, 1);
+    assert_eq!(stats.misses, 1);
+    assert_eq!(stats.entries, 1);
+
+    converter.reset_cache_stats();
+    let stats = converter.cache_stats();
+    assert_eq!((stats.hits, stats.misses), (0, 0));
+    assert_eq!(stats.entries, 1);
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 mod support;
-use beans_lib::models::{EntryType, LedgerEntryBuilder, Tag};
-use chrono::{DateTime, Utc};
+use beans_lib::error::BeansError;
+use beans_lib::interop::EntryData;
+use beans_lib::models::currency::{is_supported_currency, supported_currencies};
+use beans_lib::models::{Currency, EntryType, LedgerEntry, LedgerEntryBuilder, Tag};
+use chrono::{DateTime, TimeZone, Utc};
 use rust_decimal::prelude::dec;
 use std::str::FromStr;
 use support::*;
@@ -148,6 +151,30 @@ fn test_entry_builder_validation() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_validate_fields() {
+    assert!(
+        LedgerEntry::validate_fields("Groceries", usd(), dec!(42.50), EntryType::Expense).is_ok()
+    );
+
+    // Empty name
+    assert!(LedgerEntry::validate_fields("", usd(), dec!(42.50), EntryType::Expense).is_err());
+    assert!(LedgerEntry::validate_fields("   ", usd(), dec!(42.50), EntryType::Expense).is_err());
+
+    // Bad currency
+    let err = LedgerEntry::validate_fields("Groceries", "XYZ", dec!(42.50), EntryType::Expense)
+        .unwrap_err();
+    assert_eq!(err.kind(), "Validation");
+
+    // Zero amount
+    assert!(LedgerEntry::validate_fields("Groceries", usd(), dec!(0), EntryType::Expense).is_err());
+
+    // Negative amount
+    assert!(
+        LedgerEntry::validate_fields("Groceries", usd(), dec!(-42.50), EntryType::Income).is_err()
+    );
+}
+
 #[test]
 fn test_entry_builder_from_entry() {
     let tag = Tag::new("groceries").unwrap();
@@ -207,6 +234,32 @@ fn test_entry_tags_methods() {
     assert!(!entry.has_any_tag(&["household", "electronics"]));
 }
 
+#[test]
+fn test_sorted_tags() {
+    let entry = LedgerEntryBuilder::new()
+        .name("Dinner")
+        .currency_code(usd().to_owned())
+        .amount(dec!(60.00))
+        .tags(
+            ["restaurant", "food", "travel", "business", "Dining"]
+                .map(|name| Tag::new(name).unwrap()),
+        )
+        .entry_type(EntryType::Expense)
+        .build()
+        .unwrap();
+
+    let names: Vec<&str> = entry.sorted_tags().iter().map(|t| t.name()).collect();
+    assert_eq!(
+        names,
+        vec!["business", "dining", "food", "restaurant", "travel"]
+    );
+
+    // The order doesn't depend on the call or on how the entry was built
+    let copy = LedgerEntryBuilder::from_entry(&entry).build().unwrap();
+    assert_eq!(entry.sorted_tags(), entry.sorted_tags());
+    assert_eq!(copy.sorted_tags(), entry.sorted_tags());
+}
+
 #[test]
 fn test_entry_summary_and_display() {
     let tag1 = Tag::new("groceries").unwrap();
@@ -281,6 +334,27 @@ fn test_entry_with_updated_at() {
     assert_eq!(updated.amount(), dec!(42.50));
 }
 
+#[test]
+fn test_entry_signed_amount() {
+    let income = LedgerEntryBuilder::new()
+        .name("Salary")
+        .currency_code(usd().to_owned())
+        .amount(dec!(1000.00))
+        .entry_type(EntryType::Income)
+        .build()
+        .unwrap();
+    assert_eq!(income.signed_amount(), dec!(1000.00));
+
+    let expense = LedgerEntryBuilder::new()
+        .name("Groceries")
+        .currency_code(usd().to_owned())
+        .amount(dec!(42.50))
+        .entry_type(EntryType::Expense)
+        .build()
+        .unwrap();
+    assert_eq!(expense.signed_amount(), dec!(-42.50));
+}
+
 #[test]
 fn test_entry_builder_tags_method() {
     let tag1 = Tag::new("groceries").unwrap();
@@ -303,3 +377,235 @@ fn test_entry_builder_tags_method() {
     assert!(entry.has_tag("food"));
     assert!(entry.has_tag("household"));
 }
+
+#[test]
+fn test_supported_currencies() {
+    let currencies = supported_currencies();
+
+    for code in ["USD", "EUR", "GBP", "JPY", "CAD", "AUD", "CHF", "CNY"] {
+        assert!(currencies.contains(&code), "missing {}", code);
+    }
+
+    // Every supported code can be turned into a Currency
+    for code in currencies {
+        assert!(Currency::new(dec!(1), code).is_ok());
+    }
+
+    assert!(is_supported_currency("USD"));
+    assert!(!is_supported_currency("usd"));
+    assert!(!is_supported_currency("ABC"));
+}
+
+#[test]
+fn test_currency_approx_eq() {
+    let converted = Currency::new(dec!(4500.0001), eur()).unwrap();
+    let expected = Currency::new(dec!(4500.00), eur()).unwrap();
+
+    assert_ne!(converted, expected);
+    assert!(converted.approx_eq(&expected, dec!(0.01)));
+    assert!(expected.approx_eq(&converted, dec!(0.0001)));
+    assert!(!converted.approx_eq(&expected, dec!(0.00001)));
+
+    // Amounts in different currencies never compare equal
+    let dollars = Currency::new(dec!(4500.00), usd()).unwrap();
+    assert!(!dollars.approx_eq(&expected, dec!(1)));
+}
+
+#[test]
+fn test_entry_data_conversion() {
+    let id = Uuid::new_v4();
+    let data = EntryData {
+        id: Some(id.to_string()),
+        date: "2024-03-15".to_string(),
+        name: " Groceries ".to_string(),
+        currency_code: "usd".to_string(),
+        amount: "42.50".to_string(),
+        description: Some("Weekly shop".to_string()),
+        entry_type: "Expense".to_string(),
+        tags: vec!["Food".to_string(), "household".to_string()],
+    };
+
+    let entry = LedgerEntry::try_from(data).unwrap();
+    assert_eq!(entry.id(), id);
+    assert_eq!(
+        entry.date(),
+        Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap()
+    );
+    assert_eq!(entry.name(), "Groceries");
+    assert_eq!(entry.currency_code(), "USD");
+    assert_eq!(entry.amount(), dec!(42.50));
+    assert_eq!(entry.description(), Some("Weekly shop"));
+    assert_eq!(entry.entry_type(), EntryType::Expense);
+    assert!(entry.has_tag("food"));
+    assert!(entry.has_tag("household"));
+
+    // Converting back and forth keeps every field
+    let round_trip = LedgerEntry::try_from(EntryData::from(&entry)).unwrap();
+    assert_eq!(round_trip.id(), entry.id());
+    assert_eq!(round_trip.date(), entry.date());
+    assert_eq!(round_trip.amount(), entry.amount());
+    assert_eq!(round_trip.tags(), entry.tags());
+
+    // RFC 3339 dates and blank descriptions are accepted
+    let data = EntryData {
+        date: "2024-03-15T10:30:00+02:00".to_string(),
+        name: "Salary".to_string(),
+        currency_code: "EUR".to_string(),
+        amount: "1000".to_string(),
+        description: Some("  ".to_string()),
+        entry_type: "income".to_string(),
+        ..Default::default()
+    };
+    let entry = LedgerEntry::try_from(data).unwrap();
+    assert_eq!(
+        entry.date(),
+        Utc.with_ymd_and_hms(2024, 3, 15, 8, 30, 0).unwrap()
+    );
+    assert_eq!(entry.description(), None);
+}
+
+#[test]
+fn test_entry_data_invalid() {
+    let valid = EntryData {
+        date: "2024-03-15".to_string(),
+        name: "Groceries".to_string(),
+        currency_code: "USD".to_string(),
+        amount: "42.50".to_string(),
+        entry_type: "expense".to_string(),
+        ..Default::default()
+    };
+    assert!(LedgerEntry::try_from(valid.clone()).is_ok());
+
+    for invalid in [
+        EntryData {
+            amount: "forty-two".to_string(),
+            ..valid.clone()
+        },
+        EntryData {
+            date: "15/03/2024".to_string(),
+            ..valid.clone()
+        },
+        EntryData {
+            entry_type: "transfer".to_string(),
+            ..valid.clone()
+        },
+        EntryData {
+            currency_code: "ABC".to_string(),
+            ..valid.clone()
+        },
+        EntryData {
+            id: Some("not-a-uuid".to_string()),
+            ..valid.clone()
+        },
+    ] {
+        let result = LedgerEntry::try_from(invalid);
+        assert!(matches!(result, Err(BeansError::Validation(_))));
+    }
+}
+
+#[test]
+fn test_builder_from_entry_data() {
+    let data = EntryData {
+        date: "2024-03-15".to_string(),
+        name: " Rent ".to_string(),
+        currency_code: "eur".to_string(),
+        amount: "950.00".to_string(),
+        entry_type: "expense".to_string(),
+        tags: vec!["Housing".to_string()],
+        ..Default::default()
+    };
+
+    // The builder can be adjusted before building
+    let entry = LedgerEntryBuilder::from_entry_data(&data)
+        .unwrap()
+        .description("March")
+        .build()
+        .unwrap();
+    assert_eq!(entry.name(), "Rent");
+    assert_eq!(entry.currency_code(), "EUR");
+    assert_eq!(entry.amount(), dec!(950.00));
+    assert_eq!(entry.entry_type(), EntryType::Expense);
+    assert_eq!(
+        entry.date(),
+        Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap()
+    );
+    assert!(entry.has_tag("housing"));
+    assert_eq!(entry.description(), Some("March"));
+
+    for invalid in [
+        EntryData {
+            amount: "9,50".to_string(),
+            ..data.clone()
+        },
+        EntryData {
+            date: "2024-13-01".to_string(),
+            ..data.clone()
+        },
+        EntryData {
+            entry_type: "gift".to_string(),
+            ..data.clone()
+        },
+        EntryData {
+            tags: vec!["".to_string()],
+            ..data.clone()
+        },
+    ] {
+        let result = LedgerEntryBuilder::from_entry_data(&invalid);
+        assert!(matches!(result, Err(BeansError::Validation(_))));
+    }
+
+    // Currencies are checked when the entry is built
+    let unknown = EntryData {
+        currency_code: "XYZ".to_string(),
+        ..data
+    };
+    let builder = LedgerEntryBuilder::from_entry_data(&unknown).unwrap();
+    assert!(matches!(builder.build(), Err(BeansError::Validation(_))));
+}
+
+#[test]
+fn test_currency_codes_are_handled_consistently() {
+    // Codes rusty_money or a frontend might produce but that the library doesn't support
+    for code in ["usd", "Eur", "XXX", "BTC", ""] {
+        assert!(!is_supported_currency(code));
+        assert!(matches!(
+            Currency::new(dec!(1), code),
+            Err(BeansError::Currency(_))
+        ));
+        let result = LedgerEntryBuilder::new()
+            .name("Test")
+            .currency_code(code.to_string())
+            .amount(dec!(1))
+            .entry_type(EntryType::Income)
+            .build();
+        assert!(result.is_err());
+    }
+
+    // Every supported code is accepted by every entry point
+    for code in supported_currencies() {
+        let entry = LedgerEntryBuilder::new()
+            .name("Test")
+            .currency_code(code.to_string())
+            .amount(dec!(1))
+            .entry_type(EntryType::Income)
+            .build()
+            .unwrap();
+        assert_eq!(entry.currency().unwrap().code(), *code);
+    }
+}
+
+#[test]
+fn test_short_id() {
+    let id = Uuid::parse_str("a1b2c3d4-e5f6-4789-8abc-def012345678").unwrap();
+    let entry = LedgerEntryBuilder::new()
+        .id(id)
+        .name("Test")
+        .currency_code(usd().to_owned())
+        .amount(dec!(1))
+        .entry_type(EntryType::Income)
+        .build()
+        .unwrap();
+
+    assert_eq!(entry.short_id(), "a1b2c3d4");
+    assert_eq!(entry.short_id(), entry.clone().short_id());
+}
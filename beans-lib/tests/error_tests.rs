@@ -0,0 +1,61 @@
+//! Integration tests for error serialization.
+use beans_lib::error::{BeansError, ErrorInfo};
+
+#[test]
+fn test_error_serialization() {
+    let network = reqwest::Client::new().get("not a url").build().unwrap_err();
+    let errors = [
+        (
+            BeansError::Database(rusqlite::Error::QueryReturnedNoRows),
+            "Database",
+        ),
+        (BeansError::database("locked"), "Database"),
+        (BeansError::Io(std::io::Error::other("disk full")), "Io"),
+        (BeansError::validation("bad"), "Validation"),
+        (BeansError::currency("XYZ"), "Currency"),
+        (BeansError::Network(network), "Network"),
+        (BeansError::Json("eof".to_string()), "Json"),
+        (
+            BeansError::LedgerNotFound("a.bean".to_string()),
+            "LedgerNotFound",
+        ),
+        (
+            BeansError::InvalidLedgerFormat("a.txt".to_string()),
+            "InvalidLedgerFormat",
+        ),
+        (BeansError::not_found("entry"), "NotFound"),
+        (
+            BeansError::ExchangeRateUnavailable {
+                from: "usd".to_string(),
+                to: "xyz".to_string(),
+            },
+            "ExchangeRateUnavailable",
+        ),
+        (BeansError::InvalidDateRange, "InvalidDateRange"),
+        (
+            BeansError::RangeTooLarge {
+                buckets: 20_000,
+                max: 10_000,
+            },
+            "RangeTooLarge",
+        ),
+        (BeansError::other("oops"), "Other"),
+        (
+            BeansError::NotImplemented("sync".to_string()),
+            "NotImplemented",
+        ),
+        (
+            BeansError::ConversionError("overflow".to_string()),
+            "Conversion",
+        ),
+    ];
+
+    for (error, kind) in errors {
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["kind"], kind);
+        assert_eq!(json["message"], error.to_string());
+
+        let info: ErrorInfo = serde_json::from_value(json).unwrap();
+        assert_eq!(info, ErrorInfo::from(&error));
+    }
+}
@@ -1,9 +1,17 @@
 //! Integration tests for the LedgerManager.
 mod support;
-use beans_lib::database::EntryFilter;
-use beans_lib::error::BeansResult;
-use beans_lib::ledger::LedgerManager;
-use beans_lib::models::{EntryType, LedgerEntry, LedgerEntryBuilder, Tag};
+use beans_lib::database::{EntryFilter, IssueKind, Repository};
+use beans_lib::error::{BeansError, BeansResult};
+use beans_lib::interop::EntryData;
+use beans_lib::ledger::{
+    DatePrecision, ImportOptions, ImportRecord, LedgerManager, TagPolicy, UndoToken, Warning,
+    MAX_MISSING_RECURRING,
+};
+use beans_lib::models::{
+    EntryTemplate, EntryType, LedgerEntry, LedgerEntryBuilder, Tag, TemplateOverrides,
+};
+use beans_lib::reporting::{ReportGenerator, TimePeriod};
+use chrono::{Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
 use rust_decimal_macros::dec;
 use support::*;
 use tempfile::tempdir;
@@ -56,6 +64,94 @@ fn test_in_memory_ledger() -> BeansResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_open_async() -> BeansResult<()> {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("async.bean");
+
+    let ledger = LedgerManager::open_async(&path).await?;
+    let entry = create_test_entry("Coffee", EntryType::Expense)?;
+    ledger.add_entry(&entry)?;
+    drop(ledger);
+
+    // The file is a regular ledger
+    let reopened = LedgerManager::open(&path)?;
+    assert_eq!(reopened.get_entry(entry.id())?.name(), "Coffee");
+
+    // The same checks as the blocking open apply
+    let result = LedgerManager::open_async(dir.path().join("async.db")).await;
+    assert!(matches!(result, Err(BeansError::InvalidLedgerFormat(_))));
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_reports_bad_rows() -> BeansResult<()> {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("ledger.bean");
+    let ledger = LedgerManager::open(&path)?;
+
+    let good = create_test_entry("Salary", EntryType::Income)?;
+    let bad = LedgerEntryBuilder::from_entry(&create_test_entry("Refund", EntryType::Expense)?)
+        .tag(Tag::new("shopping")?)
+        .build()?;
+    ledger.add_entry(&good)?;
+    ledger.add_entry(&bad)?;
+    assert!(ledger.validate()?.is_empty());
+
+    // Corrupt a row and leave a dangling tag link, as an external tool might
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    conn.execute(
+        "UPDATE entries SET amount = '-5.00', currency = 'ZZZ' WHERE id = ?",
+        [bad.id().to_string()],
+    )
+    .unwrap();
+    conn.execute_batch("PRAGMA foreign_keys = OFF; DELETE FROM tags WHERE name = 'shopping';")
+        .unwrap();
+    drop(conn);
+
+    let issues = ledger.validate()?;
+    let mut kinds: Vec<IssueKind> = issues.iter().map(|issue| issue.kind).collect();
+    kinds.sort_by_key(|kind| format!("{:?}", kind));
+    assert_eq!(
+        kinds,
+        vec![
+            IssueKind::InvalidCurrency,
+            IssueKind::MissingTag,
+            IssueKind::NonPositiveAmount
+        ]
+    );
+    let bad_id = bad.id().to_string();
+    assert!(issues.iter().all(|issue| issue.entry_id == bad_id));
+    assert!(issues
+        .iter()
+        .any(|issue| issue.to_string().contains("unknown currency 'ZZZ'")));
+
+    Ok(())
+}
+
+#[test]
+fn test_is_beans_file() -> BeansResult<()> {
+    let dir = tempdir().unwrap();
+
+    let ledger_path = dir.path().join("ledger.bean");
+    drop(LedgerManager::open(&ledger_path)?);
+    assert!(LedgerManager::is_beans_file(&ledger_path));
+
+    let other_path = dir.path().join("other.db");
+    let conn = rusqlite::Connection::open(&other_path).unwrap();
+    conn.execute_batch("CREATE TABLE things (id INTEGER PRIMARY KEY);")
+        .unwrap();
+    drop(conn);
+    assert!(!LedgerManager::is_beans_file(&other_path));
+
+    assert!(!LedgerManager::is_beans_file(
+        dir.path().join("missing.bean")
+    ));
+
+    Ok(())
+}
+
 #[test]
 fn test_file_ledger() -> BeansResult<()> {
     // Create a temporary directory for the test
@@ -184,6 +280,246 @@ fn test_list_and_filter() -> BeansResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_filter_by_name_contains() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+
+    for name in ["Rent January", "rent deposit", "Groceries", "100%_Rent"] {
+        ledger.add_entry(&create_test_entry(name, EntryType::Expense)?)?;
+    }
+
+    let filter = EntryFilter {
+        name_contains: Some("Rent".to_string()),
+        ..Default::default()
+    };
+    let mut names: Vec<String> = ledger
+        .list_entries(&filter)?
+        .iter()
+        .map(|e| e.name().to_string())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["100%_Rent", "Rent January", "rent deposit"]);
+    assert_eq!(ledger.count_entries(&filter)?, 3);
+
+    // Wildcard characters match literally
+    let filter = EntryFilter {
+        name_contains: Some("%_".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(ledger.count_entries(&filter)?, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_filter_by_exact_amount() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+
+    for (name, amount) in [
+        ("Rent", dec!(1200.00)),
+        ("Rent (late fee)", dec!(1200.01)),
+        ("Deposit", dec!(1200)),
+        ("Groceries", dec!(120.00)),
+        ("Tip", dec!(0.10)),
+        ("Rounding", dec!(0.1000000000000000001)),
+    ] {
+        let entry = LedgerEntryBuilder::new()
+            .name(name)
+            .amount(amount)
+            .currency_code(usd().to_owned())
+            .entry_type(EntryType::Expense)
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    // Trailing zeros don't matter, but every cent does
+    let filter = EntryFilter {
+        exact_amount: Some(dec!(1200.00)),
+        ..Default::default()
+    };
+    let mut names: Vec<String> = ledger
+        .list_entries(&filter)?
+        .iter()
+        .map(|e| e.name().to_string())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["Deposit", "Rent"]);
+    assert_eq!(ledger.count_entries(&filter)?, 2);
+
+    let filter = EntryFilter {
+        exact_amount: Some(dec!(1200.01)),
+        ..Default::default()
+    };
+    assert_eq!(ledger.count_entries(&filter)?, 1);
+
+    // Amounts that look alike as floating point are still told apart
+    let filter = EntryFilter {
+        exact_amount: Some(dec!(0.1)),
+        ..Default::default()
+    };
+    let matched = ledger.list_entries(&filter)?;
+    assert_eq!(matched.len(), 1);
+    assert_eq!(matched[0].name(), "Tip");
+
+    Ok(())
+}
+
+#[test]
+fn test_undo() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let entry = create_test_entry("Groceries", EntryType::Expense)?;
+
+    // Undoing an add removes the entry
+    let token = ledger.add_entry_undoable(&entry)?;
+    assert_eq!(token, UndoToken::Added(ledger.get_entry(entry.id())?));
+    ledger.undo(token)?;
+    assert!(matches!(
+        ledger.get_entry(entry.id()),
+        Err(BeansError::NotFound(_))
+    ));
+
+    // Undoing a delete restores the entry as it was
+    ledger.add_entry(&entry)?;
+    let stored = ledger.get_entry(entry.id())?;
+    let token = ledger.delete_entry_undoable(entry.id())?;
+    assert_eq!(token.entry_id(), entry.id());
+    assert_eq!(ledger.count_entries(&EntryFilter::default())?, 0);
+    ledger.undo(token)?;
+    assert_eq!(ledger.get_entry(entry.id())?, stored);
+
+    // Undoing an update restores the previous state
+    let renamed = LedgerEntryBuilder::from_entry(&stored)
+        .name("Groceries (Costco)")
+        .build()?;
+    let token = ledger.update_entry_undoable(&renamed)?;
+    assert_eq!(ledger.get_entry(entry.id())?.name(), "Groceries (Costco)");
+    ledger.undo(token)?;
+    assert_eq!(ledger.get_entry(entry.id())?, stored);
+
+    // Undo fails once the change no longer applies
+    let token = ledger.add_entry_undoable(&create_test_entry("Rent", EntryType::Expense)?)?;
+    ledger.delete_entry(token.entry_id())?;
+    assert!(ledger.undo(token).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_undo_conflicts() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let entry = create_test_entry("Groceries", EntryType::Expense)?;
+    ledger.add_entry(&entry)?;
+    let stored = ledger.get_entry(entry.id())?;
+
+    // Undoing an update after a later edit keeps the later edit
+    let renamed = LedgerEntryBuilder::from_entry(&stored)
+        .name("Groceries (Costco)")
+        .build()?;
+    let token = ledger.update_entry_undoable(&renamed)?;
+    let edited = LedgerEntryBuilder::from_entry(&ledger.get_entry(entry.id())?)
+        .amount(dec!(80.00))
+        .build()?;
+    ledger.update_entry(&edited)?;
+    let latest = ledger.get_entry(entry.id())?;
+    assert!(matches!(ledger.undo(token), Err(BeansError::Validation(_))));
+    assert_eq!(ledger.get_entry(entry.id())?, latest);
+
+    // Undoing a delete after the ID was re-created changes nothing
+    let token = ledger.delete_entry_undoable(entry.id())?;
+    ledger.add_entry(&stored)?;
+    let revision = ledger.revision();
+    assert!(matches!(ledger.undo(token), Err(BeansError::Validation(_))));
+    assert_eq!(ledger.revision(), revision);
+    assert_eq!(ledger.count_entries(&EntryFilter::default())?, 1);
+
+    // Undoing an add after the entry was edited keeps the edited entry
+    let token = ledger.add_entry_undoable(&create_test_entry("Rent", EntryType::Expense)?)?;
+    let rent = LedgerEntryBuilder::from_entry(&ledger.get_entry(token.entry_id())?)
+        .name("Rent (March)")
+        .build()?;
+    ledger.update_entry(&rent)?;
+    assert!(ledger.undo(token).is_err());
+    assert_eq!(ledger.get_entry(rent.id())?.name(), "Rent (March)");
+
+    // Restored entries are normalized like other writes
+    let token = ledger.delete_entry_undoable(entry.id())?;
+    let date_only = LedgerManager::with_repository(Box::new(support::create_test_repository()?))
+        .with_date_precision(DatePrecision::DateOnly);
+    date_only.undo(token)?;
+    let restored = date_only.get_entry(entry.id())?;
+    assert_eq!(
+        restored.date(),
+        DatePrecision::DateOnly.apply(latest.date())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_type_change_lock() -> BeansResult<()> {
+    for allow in [true, false] {
+        let ledger = LedgerManager::in_memory()?.with_allow_type_change(allow);
+        let salary = create_test_entry("Salary", EntryType::Income)?;
+        ledger.add_entry(&salary)?;
+
+        let flipped = LedgerEntryBuilder::from_entry(&salary)
+            .entry_type(EntryType::Expense)
+            .build()?;
+        let result = ledger.update_entry(&flipped);
+
+        let stored = ledger.get_entry(salary.id())?;
+        if allow {
+            assert!(result.is_ok());
+            assert_eq!(stored.entry_type(), EntryType::Expense);
+        } else {
+            assert!(matches!(result, Err(BeansError::Validation(_))));
+            assert_eq!(stored.entry_type(), EntryType::Income);
+        }
+
+        // Other changes are always allowed
+        let renamed = LedgerEntryBuilder::from_entry(&stored)
+            .name("Salary (March)")
+            .build()?;
+        ledger.update_entry(&renamed)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_entry_methods_take_references() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?.with_date_precision(DatePrecision::DateOnly);
+    let other = LedgerManager::in_memory()?;
+
+    // Entries passed by reference come back untouched, even though the
+    // ledger normalizes what it stores
+    let entry = create_test_entry("Borrowed", EntryType::Expense)?;
+    let original = entry.clone();
+    ledger.add_entry(&entry)?;
+    assert_eq!(entry, original);
+    assert_ne!(ledger.get_entry(entry.id())?.date(), entry.date());
+
+    let renamed = LedgerEntryBuilder::from_entry(&entry)
+        .name("Still borrowed")
+        .build()?;
+    let original_renamed = renamed.clone();
+    ledger.update_entry(&renamed)?;
+    assert_eq!(renamed, original_renamed);
+
+    let batch = vec![create_test_entry("Batch", EntryType::Income)?];
+    let original_batch = batch.clone();
+    ledger.add_entries(&batch)?;
+    assert_eq!(batch, original_batch);
+
+    // And the caller can keep using them, e.g. to copy them elsewhere
+    other.add_entry(&renamed)?;
+    other.add_entries(&batch)?;
+    assert_eq!(other.get_entry(renamed.id())?, renamed);
+    assert_eq!(other.get_all_entries()?.len(), 2);
+
+    Ok(())
+}
+
 #[test]
 fn test_transaction_atomicity() -> BeansResult<()> {
     // Create an in-memory ledger
@@ -215,3 +551,1118 @@ fn test_transaction_atomicity() -> BeansResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_reindex() -> BeansResult<()> {
+    // Reindexing a fresh ledger is a no-op
+    let ledger = LedgerManager::in_memory()?;
+    ledger.reindex()?;
+
+    // Bulk add entries
+    for i in 0..20 {
+        let entry_type = if i % 2 == 0 {
+            EntryType::Income
+        } else {
+            EntryType::Expense
+        };
+        let entry = create_test_entry(&format!("Entry {}", i), entry_type)?;
+        ledger.add_entry(&entry)?;
+    }
+
+    ledger.reindex()?;
+
+    // Queries still return correct results after reindexing
+    assert_eq!(ledger.get_all_entries()?.len(), 20);
+
+    let filter = EntryFilter {
+        entry_type: Some(EntryType::Expense),
+        tags: vec!["food".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(ledger.count_entries(&filter)?, 10);
+
+    Ok(())
+}
+
+#[test]
+fn test_error_kinds() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+
+    // Missing entries report a NotFound kind
+    let err = ledger.get_entry(uuid::Uuid::new_v4()).unwrap_err();
+    assert_eq!(err.kind(), "NotFound");
+
+    // Bad ledger paths report an InvalidLedgerFormat kind
+    let err = LedgerManager::open("test.txt").unwrap_err();
+    assert_eq!(err.kind(), "InvalidLedgerFormat");
+
+    // Builder validation failures report a Validation kind
+    let err = LedgerEntryBuilder::new().build().unwrap_err();
+    assert_eq!(err.kind(), "Validation");
+
+    Ok(())
+}
+
+#[test]
+fn test_unsupported_currency_rejected() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+
+    // The builder rejects unsupported currencies up front
+    let err = LedgerEntryBuilder::new()
+        .name("Mystery money")
+        .amount(dec!(10.00))
+        .currency_code("ABC".to_string())
+        .entry_type(EntryType::Income)
+        .build()
+        .unwrap_err();
+    assert_eq!(err.kind(), "Validation");
+
+    // Entries that bypass the builder, e.g. deserialized ones, are checked by the ledger
+    let valid = create_test_entry("Valid", EntryType::Income)?;
+    let mut json = serde_json::to_value(&valid).unwrap();
+    json["currency_code"] = "ABC".into();
+    let entry: LedgerEntry = serde_json::from_value(json).unwrap();
+
+    let err = ledger.add_entry(&entry).unwrap_err();
+    assert_eq!(err.kind(), "Validation");
+    assert!(err.to_string().contains("ABC"));
+
+    // Updates are validated the same way
+    ledger.add_entry(&valid)?;
+    assert!(ledger.update_entry(&entry).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_add_entries_batch() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+
+    let future_entry =
+        LedgerEntryBuilder::from_entry(&create_test_entry("Future", EntryType::Expense)?)
+            .date(chrono::Utc::now() + chrono::Duration::days(30))
+            .build()?;
+
+    let entries = vec![
+        create_test_entry("Income 1", EntryType::Income)?,
+        future_entry,
+        create_test_entry("Expense 1", EntryType::Expense)?,
+    ];
+
+    let summary = ledger.add_entries(&entries)?;
+
+    // Valid rows are added, the invalid one is reported by position
+    assert_eq!(summary.total(), 3);
+    assert!(!summary.is_complete());
+    assert_eq!(summary.added, vec![entries[0].id(), entries[2].id()]);
+    assert_eq!(summary.failed.len(), 1);
+    assert_eq!(summary.failed[0].index, 1);
+    assert!(summary.failed[0].message.contains("future"));
+
+    assert_eq!(ledger.get_all_entries()?.len(), 2);
+    assert!(ledger.get_entry(entries[1].id()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_filter_presets() -> BeansResult<()> {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("presets.bean");
+    let ledger = LedgerManager::open(&file_path)?;
+
+    let filter = EntryFilter {
+        start_date: Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+        end_date: Some(Utc.with_ymd_and_hms(2024, 1, 31, 23, 59, 59).unwrap()),
+        entry_type: Some(EntryType::Expense),
+        currency: Some(usd().to_owned()),
+        tags: vec!["food".to_string(), "household".to_string()],
+        limit: Some(20),
+        offset: None,
+        ..Default::default()
+    };
+
+    ledger.save_filter_preset("monthly expenses in USD", &filter)?;
+    assert_eq!(
+        ledger.load_filter_preset("monthly expenses in USD")?,
+        filter
+    );
+
+    // Saving under the same name overwrites the preset
+    let other = EntryFilter {
+        entry_type: Some(EntryType::Income),
+        ..Default::default()
+    };
+    ledger.save_filter_preset("monthly expenses in USD", &other)?;
+    ledger.save_filter_preset("all income", &other)?;
+    assert_eq!(ledger.load_filter_preset("monthly expenses in USD")?, other);
+    assert_eq!(
+        ledger.list_filter_presets()?,
+        vec![
+            "all income".to_string(),
+            "monthly expenses in USD".to_string()
+        ]
+    );
+
+    // Presets are persisted with the ledger
+    drop(ledger);
+    let reopened = LedgerManager::open(&file_path)?;
+    assert_eq!(reopened.load_filter_preset("all income")?, other);
+
+    // Unknown presets are reported as not found
+    let err = reopened.load_filter_preset("missing").unwrap_err();
+    assert_eq!(err.kind(), "NotFound");
+
+    Ok(())
+}
+
+#[test]
+fn test_entry_templates() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+
+    let coffee = EntryTemplate {
+        name: "Coffee".to_string(),
+        currency_code: usd().to_owned(),
+        amount: Some(dec!(5.00)),
+        description: Some("Morning coffee".to_string()),
+        tags: vec![Tag::new("coffee")?],
+        entry_type: EntryType::Expense,
+    };
+    ledger.save_template("coffee", &coffee)?;
+    assert_eq!(ledger.load_template("coffee")?, coffee);
+    assert_eq!(ledger.list_templates()?, vec!["coffee".to_string()]);
+
+    // Instantiating with an amount override keeps the other template fields
+    let overrides = TemplateOverrides {
+        amount: Some(dec!(6.50)),
+        ..Default::default()
+    };
+    let entry = ledger.create_from_template("coffee", &overrides)?;
+    assert_eq!(entry.name(), "Coffee");
+    assert_eq!(entry.amount(), dec!(6.50));
+    assert_eq!(entry.currency_code(), usd());
+    assert_eq!(entry.entry_type(), EntryType::Expense);
+    assert_eq!(entry.description(), Some("Morning coffee"));
+    assert!(entry.has_tag("coffee"));
+
+    // The entry was added to the ledger, dated now
+    let stored = ledger.get_entry(entry.id())?;
+    assert_eq!(stored.amount(), dec!(6.50));
+    assert!(Utc::now() - stored.date() < chrono::Duration::minutes(1));
+
+    // Without overrides the template's amount is used
+    let entry = ledger.create_from_template("coffee", &TemplateOverrides::default())?;
+    assert_eq!(entry.amount(), dec!(5.00));
+
+    // Unknown templates are reported as not found
+    let err = ledger
+        .create_from_template("tea", &TemplateOverrides::default())
+        .unwrap_err();
+    assert_eq!(err.kind(), "NotFound");
+
+    Ok(())
+}
+
+#[test]
+fn test_ledger_stats() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+
+    // An empty ledger has no date span
+    let stats = ledger.ledger_stats()?;
+    assert_eq!(stats.entry_count, 0);
+    assert_eq!(stats.date_span, None);
+    assert!(stats.currencies.is_empty());
+    assert!(stats.tags.is_empty());
+    assert!(stats.net_by_currency.is_empty());
+
+    let earliest = Utc.with_ymd_and_hms(2024, 1, 5, 0, 0, 0).unwrap();
+    let latest = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+
+    let income = create_test_entry("Salary", EntryType::Income)?;
+    ledger.add_entry(
+        &LedgerEntryBuilder::from_entry(&income)
+            .date(earliest)
+            .build()?,
+    )?;
+
+    let expense = create_test_entry("Lunch", EntryType::Expense)?;
+    ledger.add_entry(
+        &LedgerEntryBuilder::from_entry(&expense)
+            .date(latest)
+            .build()?,
+    )?;
+
+    let euro_expense = LedgerEntryBuilder::new()
+        .name("Train")
+        .amount(dec!(30.00))
+        .currency_code(eur().to_owned())
+        .entry_type(EntryType::Expense)
+        .date(Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap())
+        .build()?;
+    ledger.add_entry(&euro_expense)?;
+
+    let stats = ledger.ledger_stats()?;
+    assert_eq!(stats.entry_count, 3);
+    assert_eq!(stats.date_span, Some((earliest, latest)));
+    assert_eq!(stats.currencies, vec!["EUR", "USD"]);
+    assert_eq!(stats.tags, vec!["expense", "food", "income", "salary"]);
+    assert_eq!(stats.net_by_currency.len(), 2);
+    assert_eq!(stats.net_by_currency["USD"], dec!(50.00));
+    assert_eq!(stats.net_by_currency["EUR"], dec!(-30.00));
+
+    Ok(())
+}
+
+#[test]
+fn test_date_span() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    assert_eq!(ledger.date_span()?, None);
+
+    let dates = [
+        Utc.with_ymd_and_hms(2023, 6, 1, 8, 30, 0).unwrap(),
+        Utc.with_ymd_and_hms(2021, 2, 14, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2024, 11, 30, 23, 59, 59).unwrap(),
+    ];
+    for date in dates {
+        let entry = create_test_entry("Entry", EntryType::Expense)?;
+        ledger.add_entry(&LedgerEntryBuilder::from_entry(&entry).date(date).build()?)?;
+    }
+
+    assert_eq!(ledger.date_span()?, Some((dates[1], dates[2])));
+
+    Ok(())
+}
+
+#[test]
+fn test_largest_by_abs() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+
+    for (name, amount, entry_type) in [
+        ("Coffee", dec!(4.50), EntryType::Expense),
+        ("Salary", dec!(200.00), EntryType::Income),
+        ("Rent", dec!(500.00), EntryType::Expense),
+        ("Refund", dec!(25.00), EntryType::Income),
+    ] {
+        let entry = LedgerEntryBuilder::new()
+            .name(name)
+            .amount(amount)
+            .currency_code(usd().to_owned())
+            .entry_type(entry_type)
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    let largest = ledger.largest_by_abs(&EntryFilter::default(), 3)?;
+    let names: Vec<&str> = largest.iter().map(|e| e.name()).collect();
+    assert_eq!(names, vec!["Rent", "Salary", "Refund"]);
+
+    // The filter still applies, but its limit doesn't
+    let filter = EntryFilter {
+        entry_type: Some(EntryType::Income),
+        limit: Some(1),
+        ..Default::default()
+    };
+    let largest = ledger.largest_by_abs(&filter, 10)?;
+    let names: Vec<&str> = largest.iter().map(|e| e.name()).collect();
+    assert_eq!(names, vec!["Salary", "Refund"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_recently_added_follows_insertion_order() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+
+    // Added in a different order than their transaction dates
+    for (name, month, day) in [("June", 6, 15), ("December", 12, 31), ("January", 1, 1)] {
+        let entry = LedgerEntryBuilder::new()
+            .name(name)
+            .date(Utc.with_ymd_and_hms(2024, month, day, 0, 0, 0).unwrap())
+            .amount(dec!(10.00))
+            .currency_code(usd().to_owned())
+            .entry_type(EntryType::Expense)
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    let recent = ledger.recently_added(2)?;
+    let names: Vec<&str> = recent.iter().map(|e| e.name()).collect();
+    assert_eq!(names, vec!["January", "December"]);
+
+    // The default ordering is still by transaction date
+    let all = ledger.get_all_entries()?;
+    let names: Vec<&str> = all.iter().map(|e| e.name()).collect();
+    assert_eq!(names, vec!["December", "June", "January"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_import_skips_zero_amounts() -> BeansResult<()> {
+    let json = r#"[
+        {"date": "2024-03-01T00:00:00Z", "name": "Groceries", "currency_code": "USD",
+         "amount": "42.10", "entry_type": "expense", "tags": ["food"]},
+        {"date": "2024-03-02T00:00:00Z", "name": "Balance memo", "currency_code": "USD",
+         "amount": "0", "entry_type": "expense"},
+        {"date": "2024-03-03T00:00:00Z", "name": "Paycheck", "currency_code": "USD",
+         "amount": "1500", "entry_type": "income"}
+    ]"#;
+
+    // Zero-amount rows are skipped by default
+    let ledger = LedgerManager::in_memory()?;
+    let summary = ledger.import_json(json, &ImportOptions::default())?;
+    assert_eq!(summary.added.len(), 2);
+    assert_eq!(summary.skipped, vec![1]);
+    assert!(summary.is_complete());
+    assert_eq!(summary.total(), 3);
+    assert_eq!(ledger.get_all_entries()?.len(), 2);
+
+    // Without skipping they are rejected, but don't abort the import
+    let ledger = LedgerManager::in_memory()?;
+    let options = ImportOptions {
+        skip_zero_amounts: false,
+        ..Default::default()
+    };
+    let summary = ledger.import_json(json, &options)?;
+    assert_eq!(summary.added.len(), 2);
+    assert!(summary.skipped.is_empty());
+    assert_eq!(summary.failed.len(), 1);
+    assert_eq!(summary.failed[0].index, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_import_tag_policy() -> BeansResult<()> {
+    let json = r#"[
+        {"date": "2024-03-01T00:00:00Z", "name": "Bar tab", "currency_code": "USD",
+         "amount": "38.00", "entry_type": "expense", "tags": ["Food & Drink", "nightlife"]},
+        {"date": "2024-03-02T00:00:00Z", "name": "Paycheck", "currency_code": "USD",
+         "amount": "1500", "entry_type": "income", "tags": ["salary"]}
+    ]"#;
+
+    // Invalid tags reject the row by default
+    let ledger = LedgerManager::in_memory()?;
+    let summary = ledger.import_json(json, &ImportOptions::default())?;
+    assert_eq!(summary.added.len(), 1);
+    assert_eq!(summary.failed[0].index, 0);
+    assert!(summary.sanitized_tags.is_empty());
+
+    // With sanitizing, the row is kept and the rewrite is reported
+    let ledger = LedgerManager::in_memory()?;
+    let options = ImportOptions {
+        tag_policy: TagPolicy::Sanitize,
+        ..Default::default()
+    };
+    let summary = ledger.import_json(json, &options)?;
+    assert_eq!(summary.added.len(), 2);
+    assert!(summary.is_complete());
+    assert_eq!(summary.sanitized_tags.len(), 1);
+    assert_eq!(summary.sanitized_tags[0].index, 0);
+    assert_eq!(summary.sanitized_tags[0].original, "Food & Drink");
+    assert_eq!(summary.sanitized_tags[0].tag.name(), "food-drink");
+
+    let entry = ledger.get_entry(summary.added[0])?;
+    assert!(entry.has_tag("food-drink"));
+    assert!(entry.has_tag("nightlife"));
+
+    Ok(())
+}
+
+#[test]
+fn test_import_extra_tags() -> BeansResult<()> {
+    let json = r#"[
+        {"date": "2024-06-01T00:00:00Z", "name": "Rent", "currency_code": "USD",
+         "amount": "900", "entry_type": "expense", "tags": ["housing"]},
+        {"date": "2024-06-02T00:00:00Z", "name": "Paycheck", "currency_code": "USD",
+         "amount": "1500", "entry_type": "income", "tags": ["salary"]}
+    ]"#;
+
+    let ledger = LedgerManager::in_memory()?;
+    let options = ImportOptions {
+        extra_tags: vec![Tag::new("batch1")?],
+        ..Default::default()
+    };
+    let summary = ledger.import_json(json, &options)?;
+    assert_eq!(summary.added.len(), 2);
+
+    let rent = ledger.get_entry(summary.added[0])?;
+    assert!(rent.has_all_tags(["housing", "batch1"]));
+    let paycheck = ledger.get_entry(summary.added[1])?;
+    assert!(paycheck.has_all_tags(["salary", "batch1"]));
+    assert_eq!(paycheck.tags().len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_import_with_deferred_foreign_keys() -> BeansResult<()> {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("import.bean");
+    let ledger = LedgerManager::open(&path)?;
+
+    let records: Vec<ImportRecord> = (0..1000)
+        .map(|i| ImportRecord {
+            date: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::hours(i),
+            name: format!("Row {}", i),
+            currency_code: usd().to_owned(),
+            amount: dec!(10.00),
+            description: None,
+            tags: vec!["bulk".to_string(), format!("batch-{}", i % 10)],
+            entry_type: EntryType::Expense,
+        })
+        .collect();
+    let options = ImportOptions {
+        defer_foreign_keys: true,
+        ..Default::default()
+    };
+
+    let summary = ledger.import_records(&records, &options)?;
+    assert_eq!(summary.added.len(), 1000);
+    assert!(summary.is_complete());
+    assert_eq!(ledger.entries_with_tag("bulk")?.len(), 1000);
+
+    // Referential integrity holds
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    let count = |sql: &str| -> i64 { conn.query_row(sql, [], |row| row.get(0)).unwrap() };
+    assert_eq!(count("SELECT COUNT(*) FROM pragma_foreign_key_check"), 0);
+    assert_eq!(count("SELECT COUNT(*) FROM entry_tags"), 2000);
+
+    // Enforcement is back on afterwards, so deletes still cascade
+    ledger.delete_entry(summary.added[0])?;
+    assert_eq!(count("SELECT COUNT(*) FROM entry_tags"), 1998);
+
+    Ok(())
+}
+
+#[test]
+fn test_date_only_precision() -> BeansResult<()> {
+    let afternoon = Utc.with_ymd_and_hms(2024, 5, 17, 15, 42, 9).unwrap();
+    let midnight = Utc.with_ymd_and_hms(2024, 5, 17, 0, 0, 0).unwrap();
+    let entry = LedgerEntryBuilder::new()
+        .name("Lunch")
+        .currency_code(usd().to_owned())
+        .amount(dec!(12.00))
+        .entry_type(EntryType::Expense)
+        .date(afternoon)
+        .build()?;
+
+    // Full timestamps are kept by default
+    let ledger = LedgerManager::in_memory()?;
+    assert_eq!(ledger.date_precision(), DatePrecision::DateTime);
+    let id = ledger.add_entry(&entry)?;
+    assert_eq!(ledger.get_entry(id)?.date(), afternoon);
+
+    let ledger = LedgerManager::in_memory()?.with_date_precision(DatePrecision::DateOnly);
+    let id = ledger.add_entry(&entry)?;
+    assert_eq!(ledger.get_entry(id)?.date(), midnight);
+
+    // Updates are normalized too
+    ledger.update_entry(&entry.with_date(afternoon + Duration::days(1)))?;
+    assert_eq!(ledger.get_entry(id)?.date(), midnight + Duration::days(1));
+
+    Ok(())
+}
+
+#[test]
+fn test_needs_review() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let entry = |name: &str, amount, currency: &str, tag: Option<&str>, description| {
+        let mut builder = LedgerEntryBuilder::new()
+            .name(name)
+            .amount(amount)
+            .currency_code(currency.to_owned())
+            .entry_type(EntryType::Expense);
+        if let Some(tag) = tag {
+            builder = builder.tag(Tag::new(tag).unwrap());
+        }
+        if let Some(description) = description {
+            builder = builder.description(description);
+        }
+        builder.build()
+    };
+
+    // Clean entries, which also make USD the dominant currency for "travel"
+    for name in ["Train", "Taxi", "Bus"] {
+        ledger.add_entry(&entry(
+            name,
+            dec!(20.00),
+            usd(),
+            Some("travel"),
+            Some("Commute"),
+        )?)?;
+    }
+    assert!(ledger.needs_review()?.is_empty());
+
+    let untagged = entry("Mystery", dec!(5.00), usd(), None, Some("No idea"))?;
+    let undescribed = entry("Lunch", dec!(12.00), usd(), Some("food"), None)?;
+    let precise = entry(
+        "Fuel",
+        dec!(10.005),
+        usd(),
+        Some("car"),
+        Some("Half a cent"),
+    )?;
+    let foreign = entry("Hotel", dec!(80.00), eur(), Some("travel"), Some("Paris"))?;
+    for e in [&untagged, &undescribed, &precise, &foreign] {
+        ledger.add_entry(e)?;
+    }
+
+    let report = ledger.needs_review()?;
+    let ids = |entries: &[LedgerEntry]| entries.iter().map(|e| e.id()).collect::<Vec<_>>();
+    assert_eq!(ids(&report.untagged), vec![untagged.id()]);
+    assert_eq!(ids(&report.missing_description), vec![undescribed.id()]);
+    assert_eq!(ids(&report.suspicious_amounts), vec![precise.id()]);
+    assert_eq!(ids(&report.currency_anomalies), vec![foreign.id()]);
+    assert!(!report.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_entries_with_tag() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+
+    let lunch = create_test_entry("Lunch", EntryType::Expense)?;
+    let dinner = create_test_entry("Dinner", EntryType::Expense)?;
+    let salary = create_test_entry("Salary", EntryType::Income)?;
+    for entry in [&lunch, &dinner, &salary] {
+        ledger.add_entry(entry)?;
+    }
+
+    let mut ids: Vec<_> = ledger
+        .entries_with_tag("food")?
+        .iter()
+        .map(|e| e.id())
+        .collect();
+    ids.sort();
+    let mut expected = vec![lunch.id(), dinner.id()];
+    expected.sort();
+    assert_eq!(ids, expected);
+
+    // Tag names are normalized like Tag::new does
+    assert_eq!(ledger.entries_with_tag(" Food ")?.len(), 2);
+    assert_eq!(ledger.entries_with_tag("salary")?.len(), 1);
+    assert!(ledger.entries_with_tag("travel")?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_detect_missing_recurring() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    for (name, month) in [
+        ("Salary", 1),
+        ("Salary", 2),
+        ("Salary bonus", 3),
+        ("salary", 4),
+    ] {
+        ledger.add_entry(
+            &LedgerEntryBuilder::new()
+                .name(name)
+                .currency_code(usd().to_string())
+                .amount(dec!(3000))
+                .entry_type(EntryType::Income)
+                .date(Utc.with_ymd_and_hms(2024, month, 25, 9, 0, 0).unwrap())
+                .build()?,
+        )?;
+    }
+
+    let missing =
+        ledger.detect_missing_recurring("Salary", Duration::days(30), Duration::days(7))?;
+
+    // March is flagged; the bonus has a different name and doesn't count
+    let april = Utc.with_ymd_and_hms(2024, 4, 25, 9, 0, 0).unwrap();
+    assert_eq!(
+        missing[0].date_naive(),
+        NaiveDate::from_ymd_opt(2024, 3, 26).unwrap()
+    );
+    // Every month since the last salary is missing too
+    assert!(missing[1..].iter().all(|date| *date > april));
+    assert!(missing.len() > 2);
+
+    assert!(ledger
+        .detect_missing_recurring("Rent", Duration::days(30), Duration::days(7))?
+        .is_empty());
+    assert!(matches!(
+        ledger.detect_missing_recurring("Salary", Duration::zero(), Duration::days(7)),
+        Err(BeansError::Validation(_))
+    ));
+    assert!(matches!(
+        ledger.detect_missing_recurring("Salary", Duration::seconds(1), Duration::zero()),
+        Err(BeansError::Validation(_))
+    ));
+
+    // Years of daily gaps are capped, keeping the earliest
+    let start = Utc.with_ymd_and_hms(2020, 1, 1, 8, 0, 0).unwrap();
+    ledger.add_entry(
+        &LedgerEntryBuilder::new()
+            .name("Coffee")
+            .currency_code(usd().to_string())
+            .amount(dec!(3))
+            .entry_type(EntryType::Expense)
+            .date(start)
+            .build()?,
+    )?;
+    let missing = ledger.detect_missing_recurring("Coffee", Duration::days(1), Duration::zero())?;
+    assert_eq!(missing.len(), MAX_MISSING_RECURRING);
+    assert_eq!(missing[0], start + Duration::days(1));
+
+    Ok(())
+}
+
+#[test]
+fn test_add_entry_with_warnings() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let groceries = |amount, day| {
+        LedgerEntryBuilder::new()
+            .name("Groceries")
+            .currency_code(usd().to_string())
+            .amount(amount)
+            .entry_type(EntryType::Expense)
+            .date(Utc.with_ymd_and_hms(2024, 5, day, 12, 0, 0).unwrap())
+            .tag(Tag::new("groceries").unwrap())
+            .build()
+    };
+    for (day, amount) in [(1, dec!(45.00)), (8, dec!(60.00)), (15, dec!(55.00))] {
+        let (_, warnings) = ledger.add_entry_with_warnings(&groceries(amount, day)?)?;
+        assert!(warnings.is_empty());
+    }
+
+    // An unusually large amount is flagged but still added
+    let (id, warnings) = ledger.add_entry_with_warnings(&groceries(dec!(480.00), 22)?)?;
+    assert_eq!(
+        warnings,
+        vec![Warning::AmountAnomaly {
+            tag: "groceries".to_string(),
+            amount: dec!(480.00),
+            average: dec!(160.00) / dec!(3),
+        }]
+    );
+    assert!(warnings[0].to_string().contains("groceries"));
+    assert_eq!(ledger.get_entry(id)?.amount(), dec!(480.00));
+
+    // So is payroll on a Saturday
+    let date = Utc.with_ymd_and_hms(2024, 6, 1, 9, 0, 0).unwrap();
+    let payroll = LedgerEntryBuilder::new()
+        .name("Salary")
+        .currency_code(usd().to_string())
+        .amount(dec!(3000.00))
+        .entry_type(EntryType::Income)
+        .date(date)
+        .tag(Tag::new("salary")?)
+        .build()?;
+    let (_, warnings) = ledger.add_entry_with_warnings(&payroll)?;
+    assert_eq!(warnings, vec![Warning::WeekendPayroll(date)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_currency_anomalies() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    assert!(ledger.currency_anomalies()?.is_empty());
+
+    let paycheck = |currency: &str| {
+        LedgerEntryBuilder::new()
+            .name("Paycheck")
+            .amount(dec!(1000.00))
+            .currency_code(currency.to_owned())
+            .entry_type(EntryType::Income)
+            .tag(Tag::new("salary").unwrap())
+            .build()
+    };
+
+    for _ in 0..4 {
+        ledger.add_entry(&paycheck(usd())?)?;
+    }
+    let outlier = paycheck(eur())?;
+    ledger.add_entry(&outlier)?;
+
+    // A tag without a majority currency is not flagged
+    for currency in [usd(), eur()] {
+        let entry = LedgerEntryBuilder::new()
+            .name("Trip")
+            .amount(dec!(200.00))
+            .currency_code(currency.to_owned())
+            .entry_type(EntryType::Expense)
+            .tag(Tag::new("travel")?)
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    let anomalies = ledger.currency_anomalies()?;
+    assert_eq!(anomalies.len(), 1);
+    assert_eq!(anomalies[0].id(), outlier.id());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_refresh_after_external_change() -> BeansResult<()> {
+    let repository = create_test_repository()?;
+    let conn = repository.conn.clone();
+    let ledger = LedgerManager::with_repository(Box::new(repository));
+
+    let entry = create_test_entry("Salary", EntryType::Income)?;
+    ledger.add_entry(&entry)?;
+
+    let generator = ReportGenerator::new(&ledger).with_cache(4);
+    let start = entry.date() - Duration::days(1);
+    let end = entry.date() + Duration::days(1);
+    let report = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    assert_eq!(report.summary.income, dec!(100.00));
+
+    // Bulk-edit the amount behind the manager's back
+    conn.lock()
+        .unwrap()
+        .execute(
+            "UPDATE entries SET amount = '250.00', amount_value = 250.0",
+            [],
+        )
+        .unwrap();
+
+    // The cached report is stale until the ledger is refreshed
+    let report = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    assert_eq!(report.summary.income, dec!(100.00));
+
+    ledger.refresh();
+
+    let report = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    assert_eq!(report.summary.income, dec!(250.00));
+    assert_eq!(ledger.get_entry(entry.id())?.amount(), dec!(250.00));
+
+    Ok(())
+}
+
+#[test]
+fn test_get_by_prefix() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+
+    let mut entries = Vec::new();
+    for (id, name) in [
+        ("a1b2c3d4-0000-4000-8000-000000000001", "First"),
+        ("a1b2c3d4-0000-4000-8000-000000000002", "Second"),
+        ("ffee0011-0000-4000-8000-000000000003", "Third"),
+    ] {
+        let id = uuid::Uuid::parse_str(id).unwrap();
+        let entry = LedgerEntryBuilder::new()
+            .id(id)
+            .name(name)
+            .amount(dec!(10.00))
+            .currency_code(usd().to_owned())
+            .entry_type(EntryType::Expense)
+            .build()?;
+        ledger.add_entry(&entry)?;
+        entries.push(entry);
+    }
+
+    // A short code resolves back to its entry, in any case
+    let third = &entries[2];
+    assert_eq!(ledger.get_by_prefix(&third.short_id())?.id(), third.id());
+    assert_eq!(ledger.get_by_prefix("FFEE")?.id(), third.id());
+
+    // Colliding short codes need a longer prefix
+    let err = ledger.get_by_prefix(&entries[0].short_id()).unwrap_err();
+    assert!(matches!(err, BeansError::Validation(_)));
+    assert!(err.to_string().contains("ambiguous"));
+    let full = entries[1].id().to_string();
+    assert_eq!(ledger.get_by_prefix(&full)?.name(), "Second");
+
+    // Prefixes past the first hyphen match with or without hyphens
+    assert_eq!(ledger.get_by_prefix("ffee00110")?.name(), "Third");
+    assert_eq!(ledger.get_by_prefix("FFEE0011-0")?.name(), "Third");
+    assert_eq!(
+        ledger
+            .get_by_prefix(&entries[1].id().simple().to_string())?
+            .name(),
+        "Second"
+    );
+    assert!(matches!(
+        ledger.get_by_prefix(&format!("{}0", entries[1].id().simple())),
+        Err(BeansError::Validation(_))
+    ));
+
+    assert!(matches!(
+        ledger.get_by_prefix("0000"),
+        Err(BeansError::NotFound(_))
+    ));
+    assert!(matches!(
+        ledger.get_by_prefix("xyz"),
+        Err(BeansError::Validation(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_split_entry() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let shopping = LedgerEntryBuilder::new()
+        .name("Shopping")
+        .amount(dec!(200.00))
+        .currency_code(usd().to_owned())
+        .entry_type(EntryType::Expense)
+        .description("Weekly run")
+        .build()?;
+    ledger.add_entry(&shopping)?;
+
+    // Parts that don't add up are rejected and nothing changes
+    let result = ledger.split_entry(
+        shopping.id(),
+        vec![
+            (dec!(120.00), vec![Tag::new("food")?]),
+            (dec!(70.00), vec![Tag::new("household")?]),
+        ],
+    );
+    assert!(matches!(result, Err(BeansError::Validation(_))));
+    assert!(ledger.get_entry(shopping.id()).is_ok());
+
+    // Zero, negative and overly precise parts are rejected, even when the
+    // parts add up
+    for amounts in [
+        [dec!(200.00), dec!(0)],
+        [dec!(250.00), dec!(-50.00)],
+        [dec!(120.005), dec!(79.995)],
+    ] {
+        let result = ledger.split_entry(
+            shopping.id(),
+            amounts
+                .into_iter()
+                .map(|amount| (amount, Vec::new()))
+                .collect(),
+        );
+        assert!(matches!(result, Err(BeansError::Validation(_))));
+        assert_eq!(ledger.get_all_entries()?.len(), 1);
+    }
+
+    let parts = ledger.split_entry(
+        shopping.id(),
+        vec![
+            (dec!(120.00), vec![Tag::new("food")?]),
+            (dec!(80.00), vec![Tag::new("household")?]),
+        ],
+    )?;
+
+    assert!(matches!(
+        ledger.get_entry(shopping.id()),
+        Err(BeansError::NotFound(_))
+    ));
+
+    let entries = ledger.get_all_entries()?;
+    assert_eq!(entries.len(), 2);
+    let total: rust_decimal::Decimal = entries.iter().map(|e| e.amount()).sum();
+    assert_eq!(total, dec!(200.00));
+
+    assert_eq!(parts[0].amount(), dec!(120.00));
+    assert!(parts[0].has_tag("food"));
+    assert!(parts[1].has_tag("household"));
+    for part in &parts {
+        assert_eq!(part.date(), shopping.date());
+        assert_eq!(part.currency_code(), usd());
+        assert_eq!(part.entry_type(), EntryType::Expense);
+        assert_eq!(part.description(), Some("Weekly run"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_entries() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let base = Utc.with_ymd_and_hms(2024, 4, 10, 0, 0, 0).unwrap();
+
+    let mut ids = Vec::new();
+    for (name, amount, days, tag) in [
+        ("Bakery", dec!(4.50), 2, "food"),
+        ("Market", dec!(30.25), 0, "food"),
+        ("Hardware", dec!(12.00), 1, "household"),
+    ] {
+        let entry = LedgerEntryBuilder::new()
+            .name(name)
+            .date(base + Duration::days(days))
+            .amount(amount)
+            .currency_code(usd().to_owned())
+            .entry_type(EntryType::Expense)
+            .tag(Tag::new(tag)?)
+            .build()?;
+        ids.push(ledger.add_entry(&entry)?);
+    }
+
+    let merged = ledger.merge_entries(&ids)?;
+    assert_eq!(merged.amount(), dec!(46.75));
+    assert_eq!(merged.date(), base);
+    assert_eq!(merged.name(), "Bakery + Market + Hardware");
+    assert_eq!(merged.tags().len(), 2);
+    assert!(merged.has_all_tags(["food", "household"]));
+
+    let entries = ledger.get_all_entries()?;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].id(), merged.id());
+
+    // Mixed currencies are rejected and nothing changes
+    let euro = LedgerEntryBuilder::new()
+        .name("Croissant")
+        .date(base)
+        .amount(dec!(2.00))
+        .currency_code(eur().to_owned())
+        .entry_type(EntryType::Expense)
+        .build()?;
+    ledger.add_entry(&euro)?;
+    let err = ledger.merge_entries(&[merged.id(), euro.id()]).unwrap_err();
+    assert!(matches!(err, BeansError::Validation(_)));
+    assert!(err.to_string().contains("different currencies"));
+    assert_eq!(ledger.get_all_entries()?.len(), 2);
+
+    // On a date-only ledger the merged entry is normalized like any write,
+    // even if the stored entries still carry a time of day
+    let repository = support::create_test_repository()?;
+    let noon = base + Duration::hours(12);
+    let mut ids = Vec::new();
+    for days in [0, 1] {
+        let entry = LedgerEntryBuilder::new()
+            .name("Coffee")
+            .date(noon + Duration::days(days))
+            .amount(dec!(3.00))
+            .currency_code(usd().to_owned())
+            .entry_type(EntryType::Expense)
+            .build()?;
+        repository.create(&entry)?;
+        ids.push(entry.id());
+    }
+    let date_only = LedgerManager::with_repository(Box::new(repository))
+        .with_date_precision(DatePrecision::DateOnly);
+    let merged = date_only.merge_entries(&ids)?;
+    assert_eq!(merged.date(), base);
+    assert_eq!(date_only.get_entry(merged.id())?.date(), base);
+
+    Ok(())
+}
+
+#[test]
+fn test_default_time_of_day() -> BeansResult<()> {
+    let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+    let ledger = LedgerManager::in_memory()?.with_default_time_of_day(noon);
+    let data = EntryData {
+        date: "2024-03-15".to_string(),
+        name: "Groceries".to_string(),
+        currency_code: "usd".to_string(),
+        amount: "42.50".to_string(),
+        entry_type: "expense".to_string(),
+        ..Default::default()
+    };
+
+    let entry = ledger.entry_from_data(data.clone())?;
+    ledger.add_entry(&entry)?;
+    let stored = ledger.get_entry(entry.id())?;
+    assert_eq!(
+        stored.date(),
+        Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap()
+    );
+
+    // Viewed from UTC-5 the entry stays on the day it was entered for,
+    // whereas midnight UTC would fall on the previous day
+    let eastern = FixedOffset::west_opt(5 * 3600).unwrap();
+    let day = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    assert_eq!(stored.date().with_timezone(&eastern).date_naive(), day);
+    let midnight = LedgerEntry::try_from(data)?;
+    assert_eq!(
+        midnight.date().with_timezone(&eastern).date_naive(),
+        day.pred_opt().unwrap()
+    );
+
+    // Full timestamps keep their own time
+    let timestamped = ledger.entry_from_data(EntryData {
+        date: "2024-03-15T08:30:00Z".to_string(),
+        name: "Coffee".to_string(),
+        currency_code: "USD".to_string(),
+        amount: "3.00".to_string(),
+        entry_type: "expense".to_string(),
+        ..Default::default()
+    })?;
+    assert_eq!(
+        timestamped.date(),
+        Utc.with_ymd_and_hms(2024, 3, 15, 8, 30, 0).unwrap()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_default_time_of_day_today() -> BeansResult<()> {
+    // The latest default time is still ahead of now on today's date
+    let late = NaiveTime::from_hms_opt(23, 59, 59).unwrap();
+    let ledger = LedgerManager::in_memory()?.with_default_time_of_day(late);
+    let today = Utc::now().date_naive();
+    let data = EntryData {
+        date: today.format("%Y-%m-%d").to_string(),
+        name: "Lunch".to_string(),
+        currency_code: "USD".to_string(),
+        amount: "12.00".to_string(),
+        entry_type: "expense".to_string(),
+        ..Default::default()
+    };
+
+    let entry = ledger.entry_from_data(data.clone())?;
+    ledger.add_entry(&entry)?;
+    let stored = ledger.get_entry(entry.id())?;
+    assert_eq!(stored.date().date_naive(), today);
+    assert!(stored.date() <= Utc::now());
+
+    // Re-saving an edit with the same day keeps the stored time
+    let edited = ledger.entry_from_data_for_edit(
+        &stored,
+        EntryData {
+            amount: "15.00".to_string(),
+            ..data
+        },
+    )?;
+    assert_eq!(edited.id(), stored.id());
+    assert_eq!(edited.date(), stored.date());
+    assert_eq!(edited.created_at(), stored.created_at());
+    ledger.update_entry(&edited)?;
+    assert_eq!(ledger.get_entry(entry.id())?.amount(), dec!(15.00));
+
+    Ok(())
+}
+
+#[test]
+fn test_edit_from_data_keeps_metadata() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let entry = LedgerEntryBuilder::new()
+        .name("Rent")
+        .amount(dec!(900.00))
+        .currency_code(usd().to_owned())
+        .entry_type(EntryType::Expense)
+        .date(Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap())
+        .metadata("invoice", "INV-42")
+        .build()?;
+    ledger.add_entry(&entry)?;
+
+    // The form's data has no metadata, so an edit must not drop it
+    let data = EntryData {
+        date: "2024-03-01".to_string(),
+        name: "Rent".to_string(),
+        currency_code: "USD".to_string(),
+        amount: "950.00".to_string(),
+        entry_type: "expense".to_string(),
+        ..Default::default()
+    };
+    let edited = ledger.entry_from_data_for_edit(&ledger.get_entry(entry.id())?, data)?;
+    ledger.update_entry(&edited)?;
+
+    let stored = ledger.get_entry(entry.id())?;
+    assert_eq!(stored.amount(), dec!(950.00));
+    assert_eq!(
+        stored.metadata().get("invoice").map(String::as_str),
+        Some("INV-42")
+    );
+
+    Ok(())
+}
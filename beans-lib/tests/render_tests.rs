@@ -0,0 +1,48 @@
+//! Integration tests for plain-text rendering.
+mod support;
+use beans_lib::models::{EntryType, LedgerEntryBuilder, Tag};
+use beans_lib::render::entries_table;
+use chrono::{TimeZone, Utc};
+use rust_decimal_macros::dec;
+use support::*;
+
+#[test]
+fn test_entries_table() {
+    let entries = vec![
+        LedgerEntryBuilder::new()
+            .name("Salary")
+            .date(Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap())
+            .amount(dec!(5000.00))
+            .currency_code(usd().to_owned())
+            .entry_type(EntryType::Income)
+            .tag(Tag::new("work").unwrap())
+            .tag(Tag::new("monthly").unwrap())
+            .build()
+            .unwrap(),
+        LedgerEntryBuilder::new()
+            .name("寿司 with the whole team after the launch")
+            .date(Utc.with_ymd_and_hms(2024, 2, 2, 0, 0, 0).unwrap())
+            .amount(dec!(84.50))
+            .currency_code(eur().to_owned())
+            .entry_type(EntryType::Expense)
+            .build()
+            .unwrap(),
+    ];
+
+    let table = entries_table(&entries);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "Date        Name                       Amount  Currency  Type     Tags",
+            "----------  ------------------------  -------  --------  -------  -------------",
+            "2024-01-31  Salary                    5000.00  USD       income   monthly, work",
+            // The wide characters count double, so the long name is cut to
+            // the same 24 columns and the amount stays right-aligned
+            "2024-02-02  寿司 with the whole tea…    84.50  EUR       expense",
+        ]
+    );
+
+    // With no entries only the header is rendered
+    assert_eq!(entries_table(&[]).lines().count(), 2);
+}
@@ -2,13 +2,20 @@
 
 mod support;
 
-use beans_lib::error::BeansResult;
+use beans_lib::database::{DailyTotal, EntryFilter, MonthlySummary, Repository, SQLiteRepository};
+use beans_lib::error::{BeansError, BeansResult};
 use beans_lib::ledger::LedgerManager;
-use beans_lib::models::{EntryType, LedgerEntryBuilder, Tag};
+use beans_lib::models::{Currency, EntryTemplate, EntryType, LedgerEntry, LedgerEntryBuilder, Tag};
 use beans_lib::prelude::IncomeExpenseReport;
-use beans_lib::reporting::{ExportFormat, ReportGenerator, TimePeriod};
-use chrono::{Duration, TimeZone, Utc};
+use beans_lib::reporting::{
+    ExportFormat, IncrementalSummary, PeriodSummary, Projection, ReportGenerator, TaggedReport,
+    TimePeriod, TimeSeriesPoint,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
 use rust_decimal_macros::dec;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use uuid::Uuid;
 
 /// Creates a ledger with sample entries for testing.
 async fn create_test_ledger_with_entries() -> BeansResult<LedgerManager> {
@@ -112,7 +119,7 @@ async fn test_income_expense_report_monthly() -> BeansResult<()> {
     let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
 
     let report = generator
-        .income_expense_report(start, end, TimePeriod::Monthly, None, None)
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
         .await?;
 
     // Verify summary
@@ -135,6 +142,74 @@ async fn test_income_expense_report_monthly() -> BeansResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_time_series_counts() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let coffee = LedgerEntryBuilder::new()
+        .name("Coffee January")
+        .currency_code(support::usd().to_string())
+        .amount(dec!(4.50))
+        .entry_type(EntryType::Expense)
+        .date(Utc.with_ymd_and_hms(2024, 1, 20, 0, 0, 0).unwrap())
+        .build()?;
+    ledger.add_entry(&coffee)?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+
+    let report = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+
+    // January has rent, groceries and coffee
+    assert_eq!(report.expense_series.points[0].value, dec!(1804.50));
+    assert_eq!(report.expense_series.points[0].count, 3);
+    assert_eq!(report.expense_series.points[1].count, 2);
+    assert_eq!(report.expense_series.points[2].count, 1);
+    assert_eq!(report.income_series.points[0].count, 1);
+
+    // Points serialized without a count deserialize with a count of zero
+    let point: TimeSeriesPoint =
+        serde_json::from_str(r#"{"timestamp": "2024-01-01T00:00:00Z", "value": "12.5"}"#).unwrap();
+    assert_eq!(point.count, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_income_expense_report_entry_type() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+
+    let report = generator
+        .income_expense_report(
+            start,
+            end,
+            TimePeriod::Monthly,
+            None,
+            None,
+            Some(EntryType::Expense),
+        )
+        .await?;
+
+    // Only the expense series is populated
+    assert_eq!(report.income_series.points.len(), 3);
+    assert!(report
+        .income_series
+        .points
+        .iter()
+        .all(|p| p.value == dec!(0)));
+    assert_eq!(report.expense_series.points[0].value, dec!(1800.00));
+    assert_eq!(report.summary.income, dec!(0));
+    assert_eq!(report.summary.expenses, dec!(5000.00));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_income_expense_report_daily() -> BeansResult<()> {
     let ledger = LedgerManager::in_memory()?;
@@ -164,7 +239,7 @@ async fn test_income_expense_report_daily() -> BeansResult<()> {
     let end = Utc.with_ymd_and_hms(2024, 1, 3, 23, 59, 59).unwrap();
 
     let report = generator
-        .income_expense_report(start, end, TimePeriod::Daily, None, None)
+        .income_expense_report(start, end, TimePeriod::Daily, None, None, None)
         .await?;
 
     // Verify we have daily data points
@@ -210,7 +285,7 @@ async fn test_income_expense_report_weekly() -> BeansResult<()> {
     let end = Utc.with_ymd_and_hms(2024, 1, 14, 23, 59, 59).unwrap();
 
     let report = generator
-        .income_expense_report(start, end, TimePeriod::Weekly, None, None)
+        .income_expense_report(start, end, TimePeriod::Weekly, None, None, None)
         .await?;
 
     // Verify we have weekly data
@@ -220,6 +295,58 @@ async fn test_income_expense_report_weekly() -> BeansResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_income_expense_report_month_anchor_day() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+
+    for (day, month, amount) in [(10, 1, dec!(100)), (20, 1, dec!(200)), (10, 2, dec!(50))] {
+        let entry = LedgerEntryBuilder::new()
+            .name("Invoice")
+            .currency_code(support::usd().to_string())
+            .amount(amount)
+            .entry_type(EntryType::Income)
+            .date(Utc.with_ymd_and_hms(2024, month, day, 12, 0, 0).unwrap())
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    let generator = ReportGenerator::new(&ledger).with_month_anchor_day(15)?;
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 2, 28, 23, 59, 59).unwrap();
+
+    let report = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+
+    let points = &report.income_series.points;
+    assert_eq!(points.len(), 3);
+
+    // The entry on January 10th belongs to the month anchored on December 15th
+    assert_eq!(
+        points[0].timestamp,
+        Utc.with_ymd_and_hms(2023, 12, 15, 0, 0, 0).unwrap()
+    );
+    assert_eq!(points[0].value, dec!(100));
+
+    // January 20th and February 10th share the month anchored on January 15th
+    assert_eq!(
+        points[1].timestamp,
+        Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap()
+    );
+    assert_eq!(points[1].value, dec!(250));
+    assert_eq!(points[2].value, dec!(0));
+
+    // Anchor days that don't exist in every month are rejected
+    assert!(ReportGenerator::new(&ledger)
+        .with_month_anchor_day(29)
+        .is_err());
+    assert!(ReportGenerator::new(&ledger)
+        .with_month_anchor_day(0)
+        .is_err());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_income_expense_report_yearly() -> BeansResult<()> {
     let ledger = LedgerManager::in_memory()?;
@@ -250,7 +377,7 @@ async fn test_income_expense_report_yearly() -> BeansResult<()> {
     let end = Utc.with_ymd_and_hms(2024, 12, 31, 23, 59, 59).unwrap();
 
     let report = generator
-        .income_expense_report(start, end, TimePeriod::Yearly, None, None)
+        .income_expense_report(start, end, TimePeriod::Yearly, None, None, None)
         .await?;
 
     // Verify yearly aggregation
@@ -260,6 +387,90 @@ async fn test_income_expense_report_yearly() -> BeansResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_full_range_report() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let report = generator.full_range_report(TimePeriod::Monthly).await?;
+    let (earliest, latest) = ledger.date_span()?.unwrap();
+
+    // Buckets cover the months of the earliest and latest entries
+    let points = &report.income_series.points;
+    assert_eq!(
+        points.first().unwrap().timestamp,
+        Utc.with_ymd_and_hms(earliest.year(), earliest.month(), 1, 0, 0, 0)
+            .unwrap()
+    );
+    assert_eq!(
+        points.last().unwrap().timestamp,
+        Utc.with_ymd_and_hms(latest.year(), latest.month(), 1, 0, 0, 0)
+            .unwrap()
+    );
+
+    // Every entry is included
+    assert_eq!(report.summary.income, dec!(15000.00));
+    assert_eq!(report.summary.expenses, dec!(5000.00));
+
+    // An empty ledger yields an empty report
+    let empty = LedgerManager::in_memory()?;
+    let report = ReportGenerator::new(&empty)
+        .full_range_report(TimePeriod::Monthly)
+        .await?;
+    assert_eq!(report.summary.net, dec!(0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_exclude_future_entries() -> BeansResult<()> {
+    // The ledger refuses future-dated entries, so store it directly
+    let repository = support::create_test_repository()?;
+    let now = Utc::now();
+    for (name, date) in [
+        ("Paid", now - Duration::days(3)),
+        ("Scheduled", now + Duration::days(31)),
+    ] {
+        let entry = LedgerEntryBuilder::new()
+            .name(name)
+            .currency_code(support::usd().to_string())
+            .amount(dec!(100.00))
+            .entry_type(EntryType::Expense)
+            .date(date)
+            .build()?;
+        repository.create(&entry)?;
+    }
+    let ledger = LedgerManager::with_repository(Box::new(repository));
+
+    let start = now - Duration::days(30);
+    let end = now + Duration::days(60);
+
+    // Future entries are included by default
+    let summary = ReportGenerator::new(&ledger)
+        .period_summary(start, end, None, None)
+        .await?;
+    assert_eq!(summary.expenses, dec!(200.00));
+
+    let generator = ReportGenerator::new(&ledger).with_include_future(false);
+    let summary = generator.period_summary(start, end, None, None).await?;
+    assert_eq!(summary.expenses, dec!(100.00));
+
+    let report = generator
+        .income_expense_report(start, end, TimePeriod::Daily, None, None, None)
+        .await?;
+    assert_eq!(report.summary.expenses, dec!(100.00));
+    assert!(report
+        .expense_series
+        .points
+        .iter()
+        .all(|p| p.timestamp <= now));
+
+    let by_tag = generator.tagged_report(start, end, None).await?;
+    assert_eq!(by_tag.summary.expenses, dec!(100.00));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_period_summary() -> BeansResult<()> {
     let ledger = create_test_ledger_with_entries().await?;
@@ -277,6 +488,98 @@ async fn test_period_summary() -> BeansResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_compare_ledgers() -> BeansResult<()> {
+    let business = create_test_ledger_with_entries().await?;
+    let personal = LedgerManager::in_memory()?;
+    let date = Utc.with_ymd_and_hms(2024, 2, 10, 0, 0, 0).unwrap();
+    for (name, amount, entry_type) in [
+        ("Gift", dec!(300.00), EntryType::Income),
+        ("Groceries", dec!(1200.00), EntryType::Expense),
+    ] {
+        personal.add_entry(
+            &LedgerEntryBuilder::new()
+                .name(name)
+                .currency_code(support::usd().to_string())
+                .amount(amount)
+                .entry_type(entry_type)
+                .date(date)
+                .build()?,
+        )?;
+    }
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+    let comparison = ReportGenerator::new(&business)
+        .compare_ledgers(&business, &personal, start, end, None)
+        .await?;
+
+    assert_eq!(comparison.first.net, dec!(10000.00));
+    assert_eq!(comparison.second.net, dec!(-900.00));
+    assert_eq!(comparison.combined.income, dec!(15300.00));
+    assert_eq!(comparison.combined.expenses, dec!(6200.00));
+    assert_eq!(
+        comparison.combined.net,
+        comparison.first.net + comparison.second.net
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_project_period() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    for day in 0..15 {
+        ledger.add_entry(
+            &LedgerEntryBuilder::new()
+                .name("Lunch")
+                .currency_code(support::usd().to_string())
+                .amount(dec!(100.00))
+                .entry_type(EntryType::Expense)
+                .date(start + Duration::days(day))
+                .build()?,
+        )?;
+    }
+
+    let generator = ReportGenerator::new(&ledger);
+    let as_of = Utc.with_ymd_and_hms(2024, 1, 16, 0, 0, 0).unwrap();
+    let month_end = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+    let Projection { actual, projected } = generator
+        .project_period(start, as_of, month_end, None)
+        .await?;
+
+    // Half a month of spending projects to roughly double: 1500 * 31 / 15.
+    assert_eq!(actual.expenses, dec!(1500.00));
+    assert!((projected.expenses - dec!(3100)).abs() < dec!(0.01));
+    assert_eq!(projected.income, dec!(0));
+    assert_eq!(projected.net, -projected.expenses);
+
+    assert!(matches!(
+        generator
+            .project_period(as_of, start, month_end, None)
+            .await,
+        Err(BeansError::InvalidDateRange)
+    ));
+
+    // Less than a second in, the pace is still measurable
+    let Projection { actual, projected } = generator
+        .project_period(start, start + Duration::milliseconds(500), month_end, None)
+        .await?;
+    assert_eq!(actual.expenses, dec!(100.00));
+    assert!(projected.expenses > actual.expenses);
+
+    // Less than a millisecond in, there is no pace to extrapolate
+    assert!(matches!(
+        generator
+            .project_period(start, start + Duration::microseconds(500), month_end, None)
+            .await,
+        Err(BeansError::InvalidDateRange)
+    ));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_period_summary_with_tag_filter() -> BeansResult<()> {
     let ledger = create_test_ledger_with_entries().await?;
@@ -333,6 +636,212 @@ async fn test_tagged_report() -> BeansResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_period_tag_matrix() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    for (name, day, tag, amount, entry_type) in [
+        ("Rent", (1, 5), "home", dec!(1000.00), EntryType::Expense),
+        ("Lunch", (1, 12), "food", dec!(15.00), EntryType::Expense),
+        ("Dinner", (1, 20), "food", dec!(40.00), EntryType::Expense),
+        ("Rent", (2, 5), "home", dec!(1000.00), EntryType::Expense),
+        ("Sublet", (2, 10), "home", dec!(300.00), EntryType::Income),
+    ] {
+        let entry = LedgerEntryBuilder::new()
+            .name(name)
+            .currency_code(support::usd().to_string())
+            .amount(amount)
+            .entry_type(entry_type)
+            .date(Utc.with_ymd_and_hms(2024, day.0, day.1, 0, 0, 0).unwrap())
+            .tag(Tag::new(tag)?)
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    let generator = ReportGenerator::new(&ledger);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+    let matrix = generator
+        .period_tag_matrix(start, end, TimePeriod::Monthly, None)
+        .await?;
+
+    assert_eq!(matrix.len(), 3);
+
+    let (january, by_tag) = &matrix[0];
+    assert_eq!(*january, start);
+    assert_eq!(by_tag.len(), 2);
+    assert_eq!(by_tag["home"], dec!(-1000.00));
+    assert_eq!(by_tag["food"], dec!(-55.00));
+
+    let (february, by_tag) = &matrix[1];
+    assert_eq!(
+        *february,
+        Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap()
+    );
+    assert_eq!(by_tag.len(), 1);
+    assert_eq!(by_tag["home"], dec!(-700.00));
+
+    // March has no entries
+    assert!(matrix[2].1.is_empty());
+
+    // Naming the shared currency as the target changes nothing
+    let in_usd = generator
+        .period_tag_matrix(
+            start,
+            end,
+            TimePeriod::Monthly,
+            Some(Currency::new(dec!(0), support::usd())?),
+        )
+        .await?;
+    assert_eq!(in_usd, matrix);
+
+    // Mixed currencies can't be added up without a target
+    ledger.add_entry(
+        &LedgerEntryBuilder::new()
+            .name("Croissant")
+            .currency_code(support::eur().to_string())
+            .amount(dec!(3.00))
+            .entry_type(EntryType::Expense)
+            .date(Utc.with_ymd_and_hms(2024, 3, 2, 0, 0, 0).unwrap())
+            .tag(Tag::new("food")?)
+            .build()?,
+    )?;
+    assert!(matches!(
+        generator
+            .period_tag_matrix(start, end, TimePeriod::Monthly, None)
+            .await,
+        Err(BeansError::Validation(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tag_net_series() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    for (name, month, amount, entry_type) in [
+        ("Flight", 1, dec!(400.00), EntryType::Expense),
+        ("Flight refund", 1, dec!(300.00), EntryType::Income),
+        ("Hotel", 3, dec!(250.00), EntryType::Expense),
+        ("Hotel refund", 3, dec!(250.00), EntryType::Income),
+        ("Taxi", 3, dec!(30.00), EntryType::Expense),
+    ] {
+        let entry = LedgerEntryBuilder::new()
+            .name(name)
+            .currency_code(support::usd().to_string())
+            .amount(amount)
+            .entry_type(entry_type)
+            .date(Utc.with_ymd_and_hms(2024, month, 10, 0, 0, 0).unwrap())
+            .tag(Tag::new("reimbursable")?)
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    // Entries with other tags are ignored
+    let salary = LedgerEntryBuilder::new()
+        .name("Salary")
+        .currency_code(support::usd().to_string())
+        .amount(dec!(5000.00))
+        .entry_type(EntryType::Income)
+        .date(Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap())
+        .tag(Tag::new("salary")?)
+        .build()?;
+    ledger.add_entry(&salary)?;
+
+    let generator = ReportGenerator::new(&ledger);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+    let series = generator
+        .tag_net_series("Reimbursable", start, end, TimePeriod::Monthly, None)
+        .await?;
+
+    assert_eq!(series.name, "reimbursable");
+    let values: Vec<_> = series.points.iter().map(|p| p.value).collect();
+    assert_eq!(values, vec![dec!(-100.00), dec!(0), dec!(-30.00)]);
+    assert_eq!(series.points[1].count, 0);
+    assert_eq!(series.points[2].count, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tagged_report_min_amount() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let date = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+
+    for (tag, amount) in [
+        ("rent", dec!(1200.00)),
+        ("coffee", dec!(3.50)),
+        ("gum", dec!(1.25)),
+        ("parking", dec!(4.00)),
+    ] {
+        let entry = LedgerEntryBuilder::new()
+            .name(tag)
+            .currency_code(support::usd().to_string())
+            .amount(amount)
+            .entry_type(EntryType::Expense)
+            .date(date)
+            .tag(Tag::new(tag)?)
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    let generator = ReportGenerator::new(&ledger);
+    let report = generator
+        .tagged_report(date, date + Duration::days(1), None)
+        .await?
+        .with_min_amount(dec!(5.00));
+
+    assert_eq!(report.expenses_by_tag.len(), 2);
+    assert_eq!(report.expenses_by_tag["rent"], dec!(1200.00));
+    assert_eq!(report.expenses_by_tag[TaggedReport::OTHER_TAG], dec!(8.75));
+    assert_eq!(report.net_by_tag[TaggedReport::OTHER_TAG], dec!(-8.75));
+    assert!(!report.income_by_tag.contains_key(TaggedReport::OTHER_TAG));
+
+    // Totals are unchanged
+    let total: rust_decimal::Decimal = report.expenses_by_tag.values().sum();
+    assert_eq!(total, report.summary.expenses);
+
+    // Per-tag rows are folded the same way
+    assert_eq!(report.per_tag.len(), 2);
+    assert_eq!(report.per_tag[0].tag, TaggedReport::OTHER_TAG);
+    assert_eq!(report.per_tag[0].expenses, dec!(8.75));
+    assert_eq!(report.per_tag[0].count, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tagged_report_per_tag() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let refund = LedgerEntryBuilder::new()
+        .name("Rent refund")
+        .currency_code(support::usd().to_string())
+        .amount(dec!(250.00))
+        .entry_type(EntryType::Income)
+        .date(Utc.with_ymd_and_hms(2024, 2, 20, 0, 0, 0).unwrap())
+        .tag(Tag::new("rent")?)
+        .build()?;
+    ledger.add_entry(&refund)?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+    let report = generator.tagged_report(start, end, None).await?;
+
+    // Sorted by net descending
+    let tags: Vec<&str> = report.per_tag.iter().map(|row| row.tag.as_str()).collect();
+    assert_eq!(tags, vec!["salary", "utilities", "groceries", "rent"]);
+
+    // Income and expenses for the same tag share one row
+    let rent = report.per_tag.iter().find(|row| row.tag == "rent").unwrap();
+    assert_eq!(rent.income, dec!(250.00));
+    assert_eq!(rent.expenses, dec!(4500.00));
+    assert_eq!(rent.net, dec!(-4250.00));
+    assert_eq!(rent.count, 4);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_tagged_report_with_untagged_entries() -> BeansResult<()> {
     let ledger = LedgerManager::in_memory()?;
@@ -372,7 +881,7 @@ async fn test_export_income_expense_report_json() -> BeansResult<()> {
     let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
 
     let report = generator
-        .income_expense_report(start, end, TimePeriod::Monthly, None, None)
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
         .await?;
 
     let json = generator.export_income_expense_report(&report, ExportFormat::Json)?;
@@ -399,7 +908,7 @@ async fn test_export_income_expense_report_csv() -> BeansResult<()> {
     let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
 
     let report = generator
-        .income_expense_report(start, end, TimePeriod::Monthly, None, None)
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
         .await?;
 
     let csv = generator.export_income_expense_report(&report, ExportFormat::Csv)?;
@@ -418,6 +927,342 @@ async fn test_export_income_expense_report_csv() -> BeansResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_export_csv_with_date_format() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger).with_date_format("%d/%m/%Y")?;
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+
+    let report = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    let csv = generator.export_income_expense_report(&report, ExportFormat::Csv)?;
+
+    let rows: Vec<&str> = csv.lines().skip(1).take(3).collect();
+    assert!(rows[0].starts_with("01/01/2024,"));
+    assert!(rows[1].starts_with("01/02/2024,"));
+    assert!(rows[2].starts_with("01/03/2024,"));
+
+    // Invalid specifiers are rejected up front
+    assert!(ReportGenerator::new(&ledger)
+        .with_date_format("%Y-%Q")
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_export_full_json() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+
+    let mut output = Vec::new();
+    generator
+        .export_full(start, end, ExportFormat::Json, &mut output)
+        .await?;
+
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), ledger.get_all_entries()?.len());
+
+    // The summary and tag breakdown agree with each other
+    assert_eq!(json["summary"]["income"], "15000.00");
+    assert_eq!(json["summary"]["expenses"], "5000.00");
+    assert_eq!(json["by_tag"]["summary"], json["summary"]);
+    assert_eq!(json["by_tag"]["expenses_by_tag"]["rent"], "4500.00");
+
+    // CSV output contains the entries followed by the tag breakdown
+    let mut output = Vec::new();
+    generator
+        .export_full(start, end, ExportFormat::Csv, &mut output)
+        .await?;
+    let csv = String::from_utf8(output).unwrap();
+    assert!(csv.starts_with("Date,Name,Type,Amount,Currency,Tags,Description\n"));
+    assert!(csv.contains("Salary January"));
+    assert!(csv.contains("Tag,Income,Expenses,Net"));
+    assert!(csv.contains("Total Income,15000.00"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_export_summary() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+    let summary = generator.period_summary(start, end, None, None).await?;
+
+    let mut output = Vec::new();
+    generator.export_summary(&summary, "USD", ExportFormat::Csv, &mut output)?;
+    let csv = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "Currency,Income,Expenses,Net",
+            "USD,15000.00,5000.00,10000.00"
+        ]
+    );
+
+    let mut output = Vec::new();
+    generator.export_summary(&summary, "USD", ExportFormat::Json, &mut output)?;
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["currency"], "USD");
+    assert_eq!(json["income"], "15000.00");
+    assert_eq!(json["net"], "10000.00");
+    assert!(json.get("income_series").is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_display_scale_only_rounds_exports() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let date = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+    for amount in [dec!(10.0049), dec!(20.0036)] {
+        let entry = LedgerEntryBuilder::new()
+            .name("Interest")
+            .currency_code("USD".to_string())
+            .amount(amount)
+            .entry_type(EntryType::Income)
+            .date(date)
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    let generator = ReportGenerator::new(&ledger).with_display_scale(2);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 1, 31, 23, 59, 59).unwrap();
+    let summary = generator.period_summary(start, end, None, None).await?;
+
+    // The summary keeps full precision...
+    assert_eq!(summary.income, dec!(30.0085));
+
+    // ...while the export is rounded
+    let mut output = Vec::new();
+    generator.export_summary(&summary, "USD", ExportFormat::Csv, &mut output)?;
+    let csv = String::from_utf8(output).unwrap();
+    assert_eq!(csv.lines().nth(1), Some("USD,30.01,0.00,30.01"));
+
+    let report = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    assert_eq!(report.summary.net, dec!(30.0085));
+    let csv = generator.export_income_expense_report(&report, ExportFormat::Csv)?;
+    assert!(csv.contains("Net,30.01\n"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_export_negative_net() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let date = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+    for (name, amount, entry_type) in [
+        ("Refund", dec!(40.00), EntryType::Income),
+        ("Rent", dec!(1200.00), EntryType::Expense),
+    ] {
+        let entry = LedgerEntryBuilder::new()
+            .name(name)
+            .currency_code("USD".to_string())
+            .amount(amount)
+            .entry_type(entry_type)
+            .date(date)
+            .tag(Tag::new("home")?)
+            .build()?;
+        ledger.add_entry(&entry)?;
+    }
+
+    let generator = ReportGenerator::new(&ledger);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 1, 31, 23, 59, 59).unwrap();
+
+    let summary = generator.period_summary(start, end, None, None).await?;
+    let mut output = Vec::new();
+    generator.export_summary(&summary, "USD", ExportFormat::Csv, &mut output)?;
+    let csv = String::from_utf8(output).unwrap();
+    assert_eq!(csv.lines().nth(1), Some("USD,40.00,1200.00,-1160.00"));
+
+    let report = generator.tagged_report(start, end, None).await?;
+    let csv = generator.export_tagged_report(&report, ExportFormat::Csv)?;
+    assert!(csv.contains("home,40.00,1200.00,-1160.00\n"));
+    assert!(csv.contains("Net,-1160.00\n"));
+    assert!(!csv.contains('('));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_export_report_with_tags() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+    let report = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    let by_tag = generator.tagged_report(start, end, None).await?;
+
+    let csv =
+        generator.export_income_expense_report_with_tags(&report, &by_tag, ExportFormat::Csv)?;
+    let (periods, tags) = csv.split_once("\n\nTag,Income,Expenses,Net\n").unwrap();
+
+    assert!(periods.starts_with("Timestamp,Income,Expenses\n"));
+    assert_eq!(
+        periods
+            .lines()
+            .skip(1)
+            .take_while(|l| !l.is_empty())
+            .count(),
+        report.income_series.points.len()
+    );
+    assert!(periods.ends_with(&format!("Net,{}", report.summary.net)));
+
+    for (tag, net) in &by_tag.net_by_tag {
+        let row = tags
+            .lines()
+            .find(|l| l.starts_with(&format!("{},", tag)))
+            .unwrap();
+        assert!(row.ends_with(&format!(",{}", net)));
+    }
+    assert!(tags.ends_with(&format!("Net,{}\n", by_tag.summary.net)));
+
+    let json =
+        generator.export_income_expense_report_with_tags(&report, &by_tag, ExportFormat::Json)?;
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(json.get("income_series").is_some());
+    assert!(json["by_tag"].get("net_by_tag").is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_export_entries_json_streams_every_entry() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let entries: Vec<LedgerEntry> = (0..1000)
+        .map(|i| {
+            LedgerEntryBuilder::new()
+                .name(format!("Row {}", i))
+                .currency_code("USD".to_string())
+                .amount(dec!(1.00))
+                .entry_type(EntryType::Expense)
+                // Several entries share each date, across page boundaries
+                .date(date + Duration::days(i / 7))
+                .tag(Tag::new("bulk")?)
+                .build()
+        })
+        .collect::<BeansResult<_>>()?;
+    ledger.add_entries(&entries)?;
+
+    let generator = ReportGenerator::new(&ledger);
+    let mut output = Vec::new();
+    let written = generator.export_entries_json(&EntryFilter::default(), &mut output)?;
+    assert_eq!(written, 1000);
+
+    let parsed: Vec<LedgerEntry> = serde_json::from_slice(&output).unwrap();
+    assert_eq!(parsed.len(), 1000);
+    let ids: std::collections::HashSet<Uuid> = parsed.iter().map(|e| e.id()).collect();
+    assert_eq!(ids.len(), 1000);
+    assert_eq!(parsed, ledger.list_entries(&EntryFilter::default())?);
+
+    // Limits and offsets are honoured, and an empty result is still an array
+    let mut output = Vec::new();
+    let filter = EntryFilter {
+        limit: Some(600),
+        offset: Some(550),
+        ..Default::default()
+    };
+    assert_eq!(generator.export_entries_json(&filter, &mut output)?, 450);
+    let parsed: Vec<LedgerEntry> = serde_json::from_slice(&output).unwrap();
+    assert_eq!(parsed, ledger.list_entries(&filter)?);
+
+    let mut output = Vec::new();
+    let filter = EntryFilter {
+        name_contains: Some("missing".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(generator.export_entries_json(&filter, &mut output)?, 0);
+    assert_eq!(String::from_utf8(output).unwrap(), "[]\n");
+
+    Ok(())
+}
+
+#[test]
+fn test_export_entries_json_pages_through_one_date() -> BeansResult<()> {
+    let ledger = LedgerManager::in_memory()?;
+    let date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+    // More than two pages of entries at the very same instant
+    let entries: Vec<LedgerEntry> = (0..1200)
+        .map(|i| {
+            LedgerEntryBuilder::new()
+                .name(format!("Row {}", i))
+                .currency_code("USD".to_string())
+                .amount(dec!(1.00))
+                .entry_type(EntryType::Expense)
+                .date(date)
+                .build()
+        })
+        .collect::<BeansResult<_>>()?;
+    ledger.add_entries(&entries)?;
+
+    let mut output = Vec::new();
+    let written =
+        ReportGenerator::new(&ledger).export_entries_json(&EntryFilter::default(), &mut output)?;
+    assert_eq!(written, 1200);
+
+    // Each entry appears exactly once, newest insertion first
+    let parsed: Vec<LedgerEntry> = serde_json::from_slice(&output).unwrap();
+    let ids: std::collections::HashSet<Uuid> = parsed.iter().map(|e| e.id()).collect();
+    assert_eq!(ids.len(), 1200);
+    assert_eq!(parsed.first().map(|e| e.name()), Some("Row 1199"));
+    assert_eq!(parsed.last().map(|e| e.name()), Some("Row 0"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_export_csv_signed_amounts() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 1, 31, 23, 59, 59).unwrap();
+
+    let mut output = Vec::new();
+    ReportGenerator::new(&ledger)
+        .with_signed_amounts(true)
+        .export_full(start, end, ExportFormat::Csv, &mut output)
+        .await?;
+    let csv = String::from_utf8(output).unwrap();
+    let row = |name: &str| {
+        csv.lines()
+            .find(|line| line.contains(name))
+            .unwrap()
+            .to_string()
+    };
+
+    assert!(row("Rent January").contains(",expense,-1500.00,"));
+    assert!(row("Salary January").contains(",income,5000.00,"));
+
+    // Amounts are unsigned by default
+    let mut output = Vec::new();
+    ReportGenerator::new(&ledger)
+        .export_full(start, end, ExportFormat::Csv, &mut output)
+        .await?;
+    let csv = String::from_utf8(output).unwrap();
+    assert!(csv.contains(",expense,1500.00,"));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_export_tagged_report_json() -> BeansResult<()> {
     let ledger = create_test_ledger_with_entries().await?;
@@ -470,7 +1315,7 @@ async fn test_invalid_date_range() -> BeansResult<()> {
 
     // Should fail because start > end
     let result = generator
-        .income_expense_report(start, end, TimePeriod::Monthly, None, None)
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
         .await;
 
     assert!(result.is_err());
@@ -491,7 +1336,7 @@ async fn test_report_with_empty_ledger() -> BeansResult<()> {
     let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
 
     let report = generator
-        .income_expense_report(start, end, TimePeriod::Monthly, None, None)
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
         .await?;
 
     // Empty ledger should return zero values
@@ -527,7 +1372,7 @@ async fn test_timezone_handling() -> BeansResult<()> {
     let end = Utc.with_ymd_and_hms(2024, 2, 29, 23, 59, 59).unwrap();
 
     let report = generator
-        .income_expense_report(start, end, TimePeriod::Monthly, None, None)
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
         .await?;
 
     // Income should be in January bucket (UTC)
@@ -578,3 +1423,859 @@ async fn test_multiple_tags_per_entry() -> BeansResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_max_buckets_guard() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let start = Utc.with_ymd_and_hms(1974, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2023, 12, 31, 0, 0, 0).unwrap();
+
+    // Fifty years of daily buckets is over the default limit
+    let err = generator
+        .income_expense_report(start, end, TimePeriod::Daily, None, None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), "RangeTooLarge");
+    assert!(matches!(
+        err,
+        BeansError::RangeTooLarge {
+            buckets: 18262,
+            max: 10_000
+        }
+    ));
+
+    // The same range by month is fine, as is a raised limit
+    generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    let generator = generator.with_max_buckets(20_000);
+    generator
+        .income_expense_report(start, end, TimePeriod::Daily, None, None, None)
+        .await?;
+
+    // Ranges reaching the latest representable date are counted without
+    // walking them, and the walk stops cleanly at that date
+    let err = generator
+        .income_expense_report(
+            start,
+            DateTime::<Utc>::MAX_UTC,
+            TimePeriod::Daily,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BeansError::RangeTooLarge { buckets, .. } if buckets > 95_000_000));
+    let total = generator
+        .income_expense_report(
+            start,
+            DateTime::<Utc>::MAX_UTC,
+            TimePeriod::Total,
+            None,
+            None,
+            None,
+        )
+        .await?;
+    assert_eq!(total.income_series.points.len(), 1);
+    let last_year = Utc
+        .with_ymd_and_hms(DateTime::<Utc>::MAX_UTC.year() - 1, 6, 1, 0, 0, 0)
+        .unwrap();
+    let yearly = generator
+        .income_expense_report(
+            last_year,
+            DateTime::<Utc>::MAX_UTC,
+            TimePeriod::Yearly,
+            None,
+            None,
+            None,
+        )
+        .await?;
+    assert_eq!(yearly.income_series.points.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_incremental_summary() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end_of_february = Utc.with_ymd_and_hms(2024, 2, 29, 23, 59, 59).unwrap();
+    let end_of_march = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+
+    // Start from January and February, then apply March one entry at a time
+    let summary = generator
+        .period_summary(start, end_of_february, None, None)
+        .await?;
+    let mut running = IncrementalSummary::new(summary, "USD");
+    let march = EntryFilter {
+        start_date: Some(end_of_february),
+        end_date: Some(end_of_march),
+        ..Default::default()
+    };
+    let march_entries = ledger.list_entries(&march)?;
+    assert!(!march_entries.is_empty());
+    for entry in &march_entries {
+        running.apply(entry)?;
+    }
+
+    let full = generator
+        .period_summary(start, end_of_march, None, None)
+        .await?;
+    assert_eq!(running.summary(), &full);
+
+    // Reverting an entry matches recomputing without it
+    let removed = &march_entries[0];
+    running.revert(removed)?;
+    ledger.delete_entry(removed.id())?;
+    let full = generator
+        .period_summary(start, end_of_march, None, None)
+        .await?;
+    assert_eq!(running.summary(), &full);
+
+    // Entries in another currency are rejected and change nothing
+    let euros = LedgerEntryBuilder::new()
+        .name("Hotel")
+        .currency_code("EUR".to_string())
+        .amount(dec!(80.00))
+        .entry_type(EntryType::Expense)
+        .build()?;
+    let err = running.apply(&euros).unwrap_err();
+    assert_eq!(err.kind(), "Currency");
+    assert_eq!(running.summary(), &full);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_chart_data() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+
+    let report = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    let chart = report.chart_data();
+
+    assert_eq!(
+        chart.timestamps,
+        vec![
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap(),
+        ]
+    );
+    assert_eq!(
+        chart.income,
+        vec![dec!(5000.00), dec!(5000.00), dec!(5000.00)]
+    );
+    assert_eq!(
+        chart.expenses,
+        vec![dec!(1800.00), dec!(1700.00), dec!(1500.00)]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_net_series() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+
+    let report = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    let net = report.net_series();
+
+    assert_eq!(net.name, "Net");
+    assert_eq!(net.points.len(), report.income_series.points.len());
+    for ((point, income), expense) in net
+        .points
+        .iter()
+        .zip(&report.income_series.points)
+        .zip(&report.expense_series.points)
+    {
+        assert_eq!(point.timestamp, income.timestamp);
+        assert_eq!(point.value, income.value - expense.value);
+        assert_eq!(point.count, income.count + expense.count);
+    }
+
+    let total: rust_decimal::Decimal = net.points.iter().map(|p| p.value).sum();
+    assert_eq!(total, report.summary.net);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_total_period() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+
+    let total = generator
+        .income_expense_report(start, end, TimePeriod::Total, None, None, None)
+        .await?;
+    let monthly = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+
+    assert_eq!(total.income_series.points.len(), 1);
+    assert_eq!(total.expense_series.points.len(), 1);
+    let income = &total.income_series.points[0];
+    assert_eq!(income.timestamp, start);
+    assert_eq!(income.value, monthly.summary.income);
+    assert_eq!(
+        total.expense_series.points[0].value,
+        monthly.summary.expenses
+    );
+    assert_eq!(
+        income.count,
+        monthly
+            .income_series
+            .points
+            .iter()
+            .map(|p| p.count)
+            .sum::<usize>()
+    );
+    assert_eq!(total.summary, monthly.summary);
+
+    // The same holds when the database does the summing
+    let aggregated = generator
+        .clone()
+        .with_sql_aggregation(true)
+        .income_expense_report(start, end, TimePeriod::Total, None, None, None)
+        .await?;
+    assert_eq!(aggregated, total);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_period_summaries() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger);
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+
+    let report = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    let periods = report.period_summaries();
+
+    assert_eq!(periods.len(), 3);
+    for (((timestamp, summary), income), expense) in periods
+        .iter()
+        .zip(&report.income_series.points)
+        .zip(&report.expense_series.points)
+    {
+        assert_eq!(*timestamp, income.timestamp);
+        assert_eq!(summary.income, income.value);
+        assert_eq!(summary.expenses, expense.value);
+        assert_eq!(summary.net, income.value - expense.value);
+    }
+    assert_eq!(periods[0].1.net, dec!(3200.00));
+    assert_eq!(periods[0].1.format_net("USD"), "$3,200.00");
+
+    Ok(())
+}
+
+#[test]
+fn test_export_format_extensions() {
+    for format in [ExportFormat::Csv, ExportFormat::Json] {
+        assert_eq!(
+            ExportFormat::from_extension(format.extension()),
+            Some(format)
+        );
+    }
+
+    assert_eq!(ExportFormat::Csv.extension(), "csv");
+    assert_eq!(ExportFormat::Json.extension(), "json");
+    assert_eq!(
+        ExportFormat::from_extension("JSON"),
+        Some(ExportFormat::Json)
+    );
+    assert_eq!(
+        ExportFormat::from_extension(".csv"),
+        Some(ExportFormat::Csv)
+    );
+    assert_eq!(ExportFormat::from_extension("xlsx"), None);
+    assert_eq!(ExportFormat::from_extension(""), None);
+}
+
+/// Repository wrapper that counts how many times entries are listed.
+#[derive(Debug)]
+struct CountingRepository {
+    inner: SQLiteRepository,
+    lists: Arc<AtomicUsize>,
+}
+
+impl Repository for CountingRepository {
+    fn create(&self, entry: &LedgerEntry) -> BeansResult<()> {
+        self.inner.create(entry)
+    }
+
+    fn create_many(&self, entries: &[LedgerEntry]) -> BeansResult<()> {
+        self.inner.create_many(entries)
+    }
+
+    fn replace(&self, ids: &[Uuid], entries: &[LedgerEntry]) -> BeansResult<()> {
+        self.inner.replace(ids, entries)
+    }
+
+    fn get(&self, id: Uuid) -> BeansResult<LedgerEntry> {
+        self.inner.get(id)
+    }
+
+    fn update(&self, entry: &LedgerEntry) -> BeansResult<()> {
+        self.inner.update(entry)
+    }
+
+    fn delete(&self, id: Uuid) -> BeansResult<()> {
+        self.inner.delete(id)
+    }
+
+    fn list(&self, filter: &EntryFilter) -> BeansResult<Vec<LedgerEntry>> {
+        self.lists.fetch_add(1, Ordering::SeqCst);
+        self.inner.list(filter)
+    }
+
+    fn count(&self, filter: &EntryFilter) -> BeansResult<usize> {
+        self.inner.count(filter)
+    }
+
+    fn daily_totals(&self, filter: &EntryFilter) -> BeansResult<Vec<DailyTotal>> {
+        self.inner.daily_totals(filter)
+    }
+
+    fn rebuild_monthly_summaries(&self) -> BeansResult<()> {
+        self.inner.rebuild_monthly_summaries()
+    }
+
+    fn monthly_summaries(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> BeansResult<Option<Vec<MonthlySummary>>> {
+        self.inner.monthly_summaries(start, end)
+    }
+
+    fn date_span(&self) -> BeansResult<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+        self.inner.date_span()
+    }
+
+    fn reindex(&self) -> BeansResult<()> {
+        self.inner.reindex()
+    }
+
+    fn save_filter_preset(&self, name: &str, filter: &EntryFilter) -> BeansResult<()> {
+        self.inner.save_filter_preset(name, filter)
+    }
+
+    fn load_filter_preset(&self, name: &str) -> BeansResult<EntryFilter> {
+        self.inner.load_filter_preset(name)
+    }
+
+    fn list_filter_presets(&self) -> BeansResult<Vec<String>> {
+        self.inner.list_filter_presets()
+    }
+
+    fn save_template(&self, name: &str, template: &EntryTemplate) -> BeansResult<()> {
+        self.inner.save_template(name, template)
+    }
+
+    fn load_template(&self, name: &str) -> BeansResult<EntryTemplate> {
+        self.inner.load_template(name)
+    }
+
+    fn list_templates(&self) -> BeansResult<Vec<String>> {
+        self.inner.list_templates()
+    }
+}
+
+#[test]
+fn test_repository_defaults_match_sqlite() -> BeansResult<()> {
+    let repository = CountingRepository {
+        inner: support::create_test_repository()?,
+        lists: Arc::new(AtomicUsize::new(0)),
+    };
+
+    let start = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+    for (i, (currency, entry_type, tag)) in [
+        (support::usd(), EntryType::Income, "salary"),
+        (support::eur(), EntryType::Expense, "food"),
+        (support::usd(), EntryType::Expense, "rent"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        repository.create(
+            &LedgerEntryBuilder::new()
+                .name("Entry")
+                .currency_code(currency.to_string())
+                .amount(dec!(10.5) * rust_decimal::Decimal::from(i + 1))
+                .entry_type(entry_type)
+                .date(start + Duration::days(i as i64 * 40))
+                .tag(Tag::new(tag)?)
+                .build()?,
+        )?;
+    }
+
+    let inner = &repository.inner;
+    let expected = inner.stats()?;
+    let stats = repository.stats()?;
+    assert_eq!(stats.entry_count, expected.entry_count);
+    assert_eq!(stats.date_span, expected.date_span);
+    assert_eq!(stats.currencies, expected.currencies);
+    assert_eq!(stats.tags, expected.tags);
+    assert_eq!(stats.net_by_currency, expected.net_by_currency);
+
+    let filter = EntryFilter {
+        entry_type: Some(EntryType::Expense),
+        limit: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(
+        repository.amount_total(&filter)?,
+        inner.amount_total(&filter)?
+    );
+    assert_eq!(repository.list_lenient(&filter)?.0, inner.list(&filter)?);
+
+    let id = inner.list(&EntryFilter::default())?[0].id();
+    let prefix = &id.to_string()[..8];
+    assert_eq!(
+        repository.ids_with_prefix(prefix)?,
+        inner.ids_with_prefix(prefix)?
+    );
+    assert_eq!(repository.ids_with_prefix("")?.len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sql_aggregation_matches_entries() -> BeansResult<()> {
+    let lists = Arc::new(AtomicUsize::new(0));
+    let ledger = LedgerManager::with_repository(Box::new(CountingRepository {
+        inner: support::create_test_repository()?,
+        lists: lists.clone(),
+    }));
+
+    // Hundreds of entries throughout one day, and a few on the next
+    let day = Utc.with_ymd_and_hms(2024, 5, 10, 0, 0, 0).unwrap();
+    let mut entries = Vec::new();
+    for i in 0..500 {
+        let (entry_type, amount) = if i % 5 == 0 {
+            (EntryType::Income, dec!(12.345))
+        } else {
+            (
+                EntryType::Expense,
+                dec!(0.01) * rust_decimal::Decimal::from(i),
+            )
+        };
+        entries.push(
+            LedgerEntryBuilder::new()
+                .name("Tick")
+                .currency_code("USD".to_string())
+                .amount(amount)
+                .entry_type(entry_type)
+                .date(day + Duration::seconds(i * 97))
+                .build()?,
+        );
+    }
+    for hour in [1, 23] {
+        entries.push(
+            LedgerEntryBuilder::new()
+                .name("Late")
+                .currency_code("USD".to_string())
+                .amount(dec!(5.00))
+                .entry_type(EntryType::Expense)
+                .date(Utc.with_ymd_and_hms(2024, 5, 11, hour, 0, 0).unwrap())
+                .build()?,
+        );
+    }
+    ledger.add_entries(&entries)?;
+
+    let start = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 5, 31, 23, 59, 59).unwrap();
+
+    for period in [TimePeriod::Daily, TimePeriod::Weekly, TimePeriod::Monthly] {
+        lists.store(0, Ordering::SeqCst);
+        let aggregated = ReportGenerator::new(&ledger)
+            .with_sql_aggregation(true)
+            .income_expense_report(start, end, period, None, None, None)
+            .await?;
+        assert_eq!(lists.load(Ordering::SeqCst), 0);
+
+        let listed = ReportGenerator::new(&ledger)
+            .income_expense_report(start, end, period, None, None, None)
+            .await?;
+        assert_eq!(aggregated, listed);
+    }
+
+    let report = ReportGenerator::new(&ledger)
+        .with_sql_aggregation(true)
+        .income_expense_report(start, end, TimePeriod::Daily, None, None, None)
+        .await?;
+    let busy_day = &report.income_series.points[9];
+    assert_eq!(busy_day.timestamp, day);
+    assert_eq!(busy_day.count, 100);
+    assert_eq!(busy_day.value, dec!(1234.500));
+    let expenses = &report.expense_series.points;
+    assert_eq!(expenses[9].count, 400);
+    assert_eq!(expenses[9].value, dec!(1000.00));
+    assert_eq!(expenses[10].count, 2);
+    assert_eq!(expenses[10].value, dec!(10.00));
+
+    // Entry type filters apply to the aggregated path too
+    let income_only = ReportGenerator::new(&ledger)
+        .with_sql_aggregation(true)
+        .income_expense_report(
+            start,
+            end,
+            TimePeriod::Monthly,
+            None,
+            None,
+            Some(EntryType::Income),
+        )
+        .await?;
+    assert_eq!(income_only.summary.expenses, dec!(0));
+    assert_eq!(income_only.summary.income, dec!(1234.500));
+
+    // Amounts that can't be totalled exactly as integers are refused
+    // rather than rounded
+    ledger.add_entry(
+        &LedgerEntryBuilder::new()
+            .name("Dust")
+            .currency_code("USD".to_string())
+            .amount(dec!(0.0000000000000000001))
+            .entry_type(EntryType::Expense)
+            .date(day)
+            .build()?,
+    )?;
+    assert!(matches!(
+        ReportGenerator::new(&ledger)
+            .with_sql_aggregation(true)
+            .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+            .await,
+        Err(BeansError::DatabaseCustom(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_summary_cache_matches_entries() -> BeansResult<()> {
+    let lists = Arc::new(AtomicUsize::new(0));
+    let ledger = LedgerManager::with_repository(Box::new(CountingRepository {
+        inner: support::create_test_repository()?,
+        lists: lists.clone(),
+    }));
+
+    let mut entries = Vec::new();
+    for (month, day, currency, amount, entry_type) in [
+        (1, 3, "USD", dec!(3000.00), EntryType::Income),
+        (1, 17, "USD", dec!(120.50), EntryType::Expense),
+        (1, 31, "EUR", dec!(80.25), EntryType::Expense),
+        (2, 1, "USD", dec!(3000.00), EntryType::Income),
+        (2, 14, "EUR", dec!(45.00), EntryType::Expense),
+        (3, 31, "USD", dec!(999.99), EntryType::Expense),
+    ] {
+        entries.push(
+            LedgerEntryBuilder::new()
+                .name("Entry")
+                .currency_code(currency.to_string())
+                .amount(amount)
+                .entry_type(entry_type)
+                .date(Utc.with_ymd_and_hms(2024, month, day, 23, 30, 0).unwrap())
+                .build()?,
+        );
+    }
+    ledger.add_entries(&entries)?;
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+    let generator = ReportGenerator::new(&ledger);
+    let report = |entry_type| {
+        generator.income_expense_report(start, end, TimePeriod::Monthly, None, None, entry_type)
+    };
+    let january = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let march = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+    let fresh = report(None).await?;
+    let fresh_expenses = report(Some(EntryType::Expense)).await?;
+    assert!(ledger.monthly_summaries(january, march)?.is_none());
+
+    ledger.rebuild_summary_cache()?;
+    let summaries = ledger.monthly_summaries(january, january)?.unwrap();
+    assert_eq!(summaries.len(), 3);
+    assert_eq!(summaries[0].currency, "EUR");
+    assert_eq!(summaries[0].total, dec!(80.25));
+
+    // Whole-month reports are served from the cache without listing entries
+    lists.store(0, Ordering::SeqCst);
+    assert_eq!(report(None).await?, fresh);
+    assert_eq!(report(Some(EntryType::Expense)).await?, fresh_expenses);
+    assert_eq!(lists.load(Ordering::SeqCst), 0);
+
+    // Writes after the build are folded into the cache, which keeps
+    // serving reports without listing entries
+    let late = LedgerEntryBuilder::new()
+        .name("Late")
+        .currency_code("USD".to_string())
+        .amount(dec!(10.00))
+        .entry_type(EntryType::Expense)
+        .date(Utc.with_ymd_and_hms(2024, 2, 20, 0, 0, 0).unwrap())
+        .build()?;
+    ledger.add_entry(&late)?;
+    let updated = report(None).await?;
+    assert_eq!(
+        updated.summary.expenses,
+        fresh.summary.expenses + dec!(10.00)
+    );
+
+    // Moving an entry to another month updates both months
+    ledger.update_entry(&late.with_date(Utc.with_ymd_and_hms(2024, 3, 5, 0, 0, 0).unwrap()))?;
+    let moved = report(None).await?;
+    let expenses =
+        |report: &IncomeExpenseReport, month: usize| report.expense_series.points[month].value;
+    assert_eq!(moved.summary, updated.summary);
+    assert_eq!(expenses(&moved, 1), expenses(&fresh, 1));
+    assert_eq!(expenses(&moved, 2), expenses(&fresh, 2) + dec!(10.00));
+
+    ledger.delete_entry(late.id())?;
+    assert_eq!(report(None).await?, fresh);
+    assert_eq!(lists.load(Ordering::SeqCst), 0);
+
+    // A built cache over an empty ledger is empty, not missing
+    let empty = LedgerManager::in_memory()?;
+    assert!(empty.monthly_summaries(january, march)?.is_none());
+    empty.rebuild_summary_cache()?;
+    assert_eq!(empty.monthly_summaries(january, march)?, Some(Vec::new()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_report_cache() -> BeansResult<()> {
+    let lists = Arc::new(AtomicUsize::new(0));
+    let ledger = LedgerManager::with_repository(Box::new(CountingRepository {
+        inner: support::create_test_repository()?,
+        lists: lists.clone(),
+    }));
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 1, 31, 23, 59, 59).unwrap();
+
+    ledger.add_entry(
+        &LedgerEntryBuilder::new()
+            .name("Salary")
+            .currency_code(support::usd().to_string())
+            .amount(dec!(1000.00))
+            .entry_type(EntryType::Income)
+            .date(start)
+            .build()?,
+    )?;
+
+    let generator = ReportGenerator::new(&ledger).with_cache(4);
+
+    let first = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    let lists_after_first = lists.load(Ordering::SeqCst);
+    assert!(lists_after_first > 0);
+
+    // Same inputs are served from the cache
+    let second = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    assert_eq!(lists.load(Ordering::SeqCst), lists_after_first);
+    assert_eq!(second.summary.income, first.summary.income);
+
+    // Different inputs are a miss
+    generator
+        .income_expense_report(start, end, TimePeriod::Daily, None, None, None)
+        .await?;
+    assert!(lists.load(Ordering::SeqCst) > lists_after_first);
+
+    // Mutating the ledger invalidates cached reports
+    ledger.add_entry(
+        &LedgerEntryBuilder::new()
+            .name("Bonus")
+            .currency_code(support::usd().to_string())
+            .amount(dec!(500.00))
+            .entry_type(EntryType::Income)
+            .date(start + Duration::days(1))
+            .build()?,
+    )?;
+
+    let lists_before_refresh = lists.load(Ordering::SeqCst);
+    let refreshed = generator
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    assert!(lists.load(Ordering::SeqCst) > lists_before_refresh);
+    assert_eq!(refreshed.summary.income, dec!(1500.00));
+
+    // A clone shares the cache, but its settings are part of the key
+    let rounded = generator.clone().with_round_conversions(true);
+    let lists_before_clone = lists.load(Ordering::SeqCst);
+    rounded
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    assert!(lists.load(Ordering::SeqCst) > lists_before_clone);
+
+    // Excluding future entries still caches ranges that end in the past
+    let past_only = generator.clone().with_include_future(false);
+    past_only
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    let lists_after_past = lists.load(Ordering::SeqCst);
+    past_only
+        .income_expense_report(start, end, TimePeriod::Monthly, None, None, None)
+        .await?;
+    assert_eq!(lists.load(Ordering::SeqCst), lists_after_past);
+
+    // A request the generator rejects isn't answered from the cache
+    generator
+        .income_expense_report(start, end, TimePeriod::Daily, None, None, None)
+        .await?;
+    let limited = generator.clone().with_max_buckets(1);
+    assert!(matches!(
+        limited
+            .income_expense_report(start, end, TimePeriod::Daily, None, None, None)
+            .await,
+        Err(BeansError::RangeTooLarge { .. })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_report_cache_follows_now() -> BeansResult<()> {
+    // An entry dated just ahead of now, e.g. written by another client
+    let repository = support::create_test_repository()?;
+    let soon = Utc::now() + Duration::milliseconds(300);
+    repository.create(
+        &LedgerEntryBuilder::new()
+            .name("Salary")
+            .currency_code(support::usd().to_string())
+            .amount(dec!(1000.00))
+            .entry_type(EntryType::Income)
+            .date(soon)
+            .build()?,
+    )?;
+    let ledger = LedgerManager::with_repository(Box::new(repository));
+    let generator = ReportGenerator::new(&ledger)
+        .with_cache(4)
+        .with_include_future(false);
+
+    let start = soon - Duration::days(1);
+    let end = soon + Duration::days(1);
+    let before = generator
+        .income_expense_report(start, end, TimePeriod::Yearly, None, None, None)
+        .await?;
+    assert_eq!(before.summary.income, dec!(0));
+
+    // Once now passes the entry's date, the same request includes it
+    tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+    let after = generator
+        .income_expense_report(start, end, TimePeriod::Yearly, None, None, None)
+        .await?;
+    assert_eq!(after.summary.income, dec!(1000.00));
+
+    Ok(())
+}
+
+#[test]
+fn test_period_summary_formatting() {
+    let summary = PeriodSummary {
+        income: dec!(15000.00),
+        expenses: dec!(5000.5),
+        net: dec!(9999.5),
+    };
+
+    // USD uses a symbol, thousands separators and two minor units
+    assert_eq!(summary.format_income("USD"), "$15,000.00");
+    assert_eq!(summary.format_expenses("USD"), "$5,000.50");
+    assert_eq!(summary.format_net("USD"), "$9,999.50");
+
+    // JPY has no minor units
+    assert_eq!(summary.format_income("JPY"), "¥15,000");
+    assert_eq!(summary.format_expenses("JPY"), "¥5,001");
+
+    // Negative amounts keep their sign
+    let loss = PeriodSummary {
+        income: dec!(1),
+        expenses: dec!(11),
+        net: dec!(-10),
+    };
+    assert_eq!(loss.format_net("USD"), "-$10.00");
+
+    // Unknown codes fall back to the plain decimal
+    assert_eq!(summary.format_net("ZZZ"), "9999.5");
+}
+
+#[test]
+fn test_period_summary_display() {
+    let summary = PeriodSummary {
+        income: dec!(15000),
+        expenses: dec!(5000),
+        net: dec!(10000),
+    };
+
+    assert_eq!(
+        summary.display("USD"),
+        "Income: $15,000.00 | Expenses: $5,000.00 | Net: $10,000.00"
+    );
+    assert_eq!(
+        summary.display("JPY"),
+        "Income: ¥15,000 | Expenses: ¥5,000 | Net: ¥10,000"
+    );
+}
+
+#[tokio::test]
+async fn test_symbol_override() -> BeansResult<()> {
+    let ledger = create_test_ledger_with_entries().await?;
+    let generator = ReportGenerator::new(&ledger).with_symbol_override("US$");
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+    let summary = generator.period_summary(start, end, None, None).await?;
+
+    assert_eq!(
+        generator.format_summary(&summary, "USD"),
+        "Income: US$15,000.00 | Expenses: US$5,000.00 | Net: US$10,000.00"
+    );
+    let loss = PeriodSummary {
+        income: dec!(0),
+        expenses: dec!(10),
+        net: dec!(-10),
+    };
+    assert!(generator
+        .format_summary(&loss, "USD")
+        .ends_with("Net: -US$10.00"));
+
+    // Data fields keep the ISO code and plain amounts
+    let mut json = Vec::new();
+    generator.export_summary(&summary, "USD", ExportFormat::Json, &mut json)?;
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["currency"], "USD");
+    let mut csv = Vec::new();
+    generator.export_summary(&summary, "USD", ExportFormat::Csv, &mut csv)?;
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.contains("USD,15000.00,"));
+    assert!(!csv.contains('$'));
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 //! Integration tests for the SQLiteRepository.
 mod support;
-use beans_lib::database::{initialize_schema, EntryFilter, Repository, SQLiteRepository};
+use beans_lib::database::{
+    initialize_schema, EntryFilter, Repository, SQLiteRepository, SortField,
+};
 use beans_lib::error::BeansResult;
 use beans_lib::models::{EntryType, LedgerEntry, LedgerEntryBuilder, Tag};
 use chrono::{Duration, Utc};
@@ -367,6 +369,38 @@ fn test_count() -> BeansResult<()> {
     let expense_count = repo.count(&expense_filter)?;
     assert_eq!(expense_count, 3);
 
+    // Every condition at once, so the clauses and parameters must line up
+    let tagged = LedgerEntryBuilder::new()
+        .name("Lunch with client")
+        .amount(dec!(42.50))
+        .currency_code(usd().to_owned())
+        .entry_type(EntryType::Expense)
+        .tag(Tag::new("food")?)
+        .metadata("vendor", "Acme")
+        .build()?;
+    repo.create(&tagged)?;
+
+    let mut all_filter = EntryFilter {
+        start_date: Some(tagged.date() - Duration::days(1)),
+        end_date: Some(tagged.date() + Duration::days(1)),
+        entry_type: Some(EntryType::Expense),
+        currency: Some(usd().to_owned()),
+        name_contains: Some("client".to_string()),
+        exact_amount: Some(dec!(42.5)),
+        tags: vec!["food".to_string()],
+        ..Default::default()
+    };
+    all_filter
+        .metadata
+        .insert("vendor".to_string(), "Acme".to_string());
+    assert_eq!(repo.count(&all_filter)?, 1);
+    assert_eq!(repo.list(&all_filter)?.len(), 1);
+
+    all_filter
+        .metadata
+        .insert("vendor".to_string(), "Globex".to_string());
+    assert_eq!(repo.count(&all_filter)?, 0);
+
     Ok(())
 }
 
@@ -398,3 +432,332 @@ fn test_non_existent_entry() -> BeansResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_timestamps_round_trip() -> BeansResult<()> {
+    let repo = create_test_repository()?;
+
+    let created_at = Utc::now() - Duration::days(10);
+    let updated_at = Utc::now() - Duration::days(2);
+    let entry =
+        LedgerEntryBuilder::from_entry(&create_test_entry("Timestamps", EntryType::Income)?)
+            .created_at(created_at)
+            .updated_at(updated_at)
+            .build()?;
+    repo.create(&entry)?;
+
+    // The stored timestamps are preserved when reading the entry back
+    let retrieved = repo.get(entry.id())?;
+    assert_eq!(retrieved.created_at(), created_at);
+    assert_eq!(retrieved.updated_at(), updated_at);
+
+    // Both timestamps are part of the serialized entry
+    let json = serde_json::to_value(&retrieved).unwrap();
+    assert_eq!(json["created_at"], serde_json::json!(created_at));
+    assert_eq!(json["updated_at"], serde_json::json!(updated_at));
+
+    Ok(())
+}
+
+#[test]
+fn test_amount_value_migration() -> BeansResult<()> {
+    let repo = SQLiteRepository::in_memory()?;
+    let conn = repo.get_connection()?.lock().unwrap();
+
+    // A version 3 ledger, which only stores amounts as text
+    conn.execute_batch(
+        "
+        CREATE TABLE schema_version (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            version INTEGER NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        INSERT INTO schema_version VALUES (1, 3, '2024-01-01T00:00:00+00:00');
+        CREATE TABLE entries (
+            id TEXT PRIMARY KEY,
+            date TEXT NOT NULL,
+            name TEXT NOT NULL,
+            currency TEXT NOT NULL,
+            amount TEXT NOT NULL,
+            description TEXT,
+            entry_type TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        CREATE TABLE tags (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE);
+        CREATE TABLE entry_tags (entry_id TEXT NOT NULL, tag_id INTEGER NOT NULL);
+        INSERT INTO entries VALUES
+            ('5f0c6f4e-1a8e-4d5b-9a57-2f1c0f4f6a01', '2024-01-01T00:00:00+00:00', 'Rent',
+             'USD', '1234.56', NULL, 'Expense',
+             '2024-01-01T00:00:00+00:00', '2024-01-01T00:00:00+00:00'),
+            ('5f0c6f4e-1a8e-4d5b-9a57-2f1c0f4f6a02', '2024-01-02T00:00:00+00:00', 'Coffee',
+             'USD', '0.10', NULL, 'Expense',
+             '2024-01-02T00:00:00+00:00', '2024-01-02T00:00:00+00:00');
+        ",
+    )
+    .unwrap();
+
+    initialize_schema(&conn)?;
+
+    // Existing text amounts are backfilled as numbers
+    let rent: f64 = conn
+        .query_row(
+            "SELECT amount_value FROM entries WHERE name = 'Rent'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(rent, 1234.56);
+
+    let total: f64 = conn
+        .query_row("SELECT SUM(amount_value) FROM entries", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert!((total - 1234.66).abs() < 1e-9);
+    drop(conn);
+
+    // New and updated entries keep the numeric copy in sync
+    let entry = create_test_entry("Numeric", EntryType::Income)?;
+    repo.create(&entry)?;
+    let updated = LedgerEntryBuilder::from_entry(&entry)
+        .amount(dec!(75.25))
+        .build()?;
+    repo.update(&updated)?;
+
+    let conn = repo.get_connection()?.lock().unwrap();
+    let value: f64 = conn
+        .query_row(
+            "SELECT amount_value FROM entries WHERE name = 'Numeric'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(value, 75.25);
+
+    Ok(())
+}
+
+#[test]
+fn test_entry_metadata() -> BeansResult<()> {
+    let repo = create_test_repository()?;
+
+    let invoice = LedgerEntryBuilder::new()
+        .name("Office supplies")
+        .amount(dec!(75.00))
+        .currency_code(usd().to_owned())
+        .entry_type(EntryType::Expense)
+        .metadata("invoice_number", "1234")
+        .metadata("vendor", "Acme")
+        .build()?;
+    let other = LedgerEntryBuilder::new()
+        .name("Printer paper")
+        .amount(dec!(20.00))
+        .currency_code(usd().to_owned())
+        .entry_type(EntryType::Expense)
+        .metadata("vendor", "Globex")
+        .build()?;
+    repo.create(&invoice)?;
+    repo.create(&other)?;
+
+    let retrieved = repo.get(invoice.id())?;
+    assert_eq!(retrieved.metadata().len(), 2);
+    assert_eq!(
+        retrieved
+            .metadata()
+            .get("invoice_number")
+            .map(String::as_str),
+        Some("1234")
+    );
+    assert_eq!(
+        retrieved.metadata().get("vendor").map(String::as_str),
+        Some("Acme")
+    );
+
+    let mut filter = EntryFilter::default();
+    filter
+        .metadata
+        .insert("vendor".to_string(), "Acme".to_string());
+    let matching = repo.list(&filter)?;
+    assert_eq!(matching.len(), 1);
+    assert_eq!(matching[0].id(), invoice.id());
+    assert_eq!(repo.count(&filter)?, 1);
+
+    // Updating an entry replaces its metadata
+    let updated = LedgerEntryBuilder::from_entry(&retrieved)
+        .metadata("vendor", "Initech")
+        .build()?;
+    repo.update(&updated)?;
+    assert_eq!(repo.count(&filter)?, 0);
+    assert_eq!(
+        repo.get(invoice.id())?
+            .metadata()
+            .get("vendor")
+            .map(String::as_str),
+        Some("Initech")
+    );
+
+    // Empty keys are rejected
+    let result = LedgerEntryBuilder::new()
+        .name("Bad")
+        .amount(dec!(1.00))
+        .currency_code(usd().to_owned())
+        .entry_type(EntryType::Expense)
+        .metadata("  ", "value")
+        .build();
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_currency_error() -> BeansResult<()> {
+    let repo = create_test_repository()?;
+    let entry = create_test_entry("Salary", EntryType::Income)?;
+    repo.create(&entry)?;
+
+    // Corrupt the stored currency behind the repository's back
+    repo.get_connection()?
+        .lock()
+        .unwrap()
+        .execute("UPDATE entries SET currency = 'XXX'", [])
+        .unwrap();
+
+    for err in [
+        repo.get(entry.id()).unwrap_err(),
+        repo.list(&EntryFilter::default()).unwrap_err(),
+    ] {
+        let message = err.to_string();
+        assert!(message.contains("unknown currency 'XXX'"), "{}", message);
+        assert!(message.contains("index: 3"), "{}", message);
+        assert!(!message.contains("amount"), "{}", message);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_list_lenient_skips_corrupt_rows() -> BeansResult<()> {
+    let repo = create_test_repository()?;
+    let valid = create_test_entry("Salary", EntryType::Income)?;
+    let corrupt = create_test_entry("Groceries", EntryType::Expense)?;
+    repo.create(&valid)?;
+    repo.create(&corrupt)?;
+
+    repo.get_connection()?
+        .lock()
+        .unwrap()
+        .execute(
+            "UPDATE entries SET date = 'yesterday' WHERE id = ?",
+            [corrupt.id().to_string()],
+        )
+        .unwrap();
+
+    assert!(repo.list(&EntryFilter::default()).is_err());
+
+    let (entries, errors) = repo.list_lenient(&EntryFilter::default())?;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].id(), valid.id());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].id, Some(corrupt.id().to_string()));
+    assert!(errors[0].message.contains("Invalid date"));
+
+    Ok(())
+}
+
+#[test]
+fn test_entry_filter_json_round_trip() {
+    let start = Utc::now() - Duration::days(7);
+    let filter = EntryFilter {
+        start_date: Some(start),
+        end_date: Some(Utc::now()),
+        entry_type: Some(EntryType::Expense),
+        currency: Some("USD".to_string()),
+        name_contains: Some("coffee".to_string()),
+        exact_amount: Some(dec!(4.50)),
+        tags: vec!["food".to_string()],
+        metadata: [("store".to_string(), "corner".to_string())]
+            .into_iter()
+            .collect(),
+        limit: Some(10),
+        offset: Some(20),
+        sort: SortField::CreatedAt,
+    };
+
+    let json = serde_json::to_value(&filter).unwrap();
+    assert_eq!(json["entry_type"], "expense");
+    assert_eq!(json["sort"], "created_at");
+    assert_eq!(json["exact_amount"], "4.50");
+    assert_eq!(json["start_date"], serde_json::json!(start));
+
+    let parsed: EntryFilter = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, filter);
+
+    // Missing fields take their default.
+    let empty: EntryFilter = serde_json::from_str("{}").unwrap();
+    assert_eq!(empty, EntryFilter::default());
+
+    // Filters saved before sort names were snake case still load.
+    let legacy: EntryFilter = serde_json::from_str(r#"{"sort":"CreatedAt"}"#).unwrap();
+    assert_eq!(legacy.sort, SortField::CreatedAt);
+}
+
+#[test]
+fn test_amount_total() -> BeansResult<()> {
+    let repo = create_test_repository()?;
+    assert_eq!(repo.amount_total(&EntryFilter::default())?, (dec!(0), 0));
+
+    for (amount, tag) in [
+        (dec!(12.5), "food"),
+        (dec!(0.125), "food"),
+        (dec!(40), "food"),
+        (dec!(99.99), "rent"),
+    ] {
+        let entry = LedgerEntryBuilder::new()
+            .name("Entry")
+            .currency_code(usd().to_owned())
+            .amount(amount)
+            .entry_type(EntryType::Expense)
+            .tag(Tag::new(tag)?)
+            .build()?;
+        repo.create(&entry)?;
+    }
+
+    // The database's exact sum agrees with summing the listed entries
+    let filter = EntryFilter {
+        tags: vec!["food".to_string()],
+        limit: Some(1),
+        ..Default::default()
+    };
+    let listed: Vec<LedgerEntry> = repo.list(&EntryFilter {
+        limit: None,
+        ..filter.clone()
+    })?;
+    let (total, count) = repo.amount_total(&filter)?;
+    assert_eq!(count, listed.len());
+    assert_eq!(total, listed.iter().map(|e| e.amount()).sum());
+    assert_eq!(total, dec!(52.625));
+
+    Ok(())
+}
+
+#[test]
+fn test_create_many_deferred_restores_foreign_keys() -> BeansResult<()> {
+    let repo = create_test_repository()?;
+    let entry = create_test_entry("Groceries", EntryType::Expense)?;
+
+    // A failed batch reports its own error and leaves enforcement on
+    let err = repo
+        .create_many_deferred(&[entry.clone(), entry.clone()])
+        .unwrap_err();
+    assert!(err.to_string().contains("insert"), "{}", err);
+    assert_eq!(repo.count(&EntryFilter::default())?, 0);
+
+    let conn = repo.get_connection()?.lock().unwrap();
+    let enabled: i64 = conn
+        .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(enabled, 1);
+
+    Ok(())
+}
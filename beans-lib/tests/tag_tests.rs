@@ -38,6 +38,24 @@ fn test_tag_validation() {
     assert!(Tag::new("food_and_drinks").is_ok());
 }
 
+#[test]
+fn test_tag_sanitize() {
+    let sanitized = |name: &str| Tag::sanitize(name).unwrap().name().to_string();
+
+    assert_eq!(sanitized("Food & Drink"), "food-drink");
+    assert_eq!(sanitized("  trips / 2024!  "), "trips-2024");
+    assert_eq!(sanitized("--side--hustle--"), "side-hustle");
+    assert_eq!(sanitized("already_valid"), "already_valid");
+    assert_eq!(sanitized("Café Crème"), "café-crème");
+
+    // Long names are cut to the maximum length
+    assert_eq!(sanitized(&"word ".repeat(20)).len(), 49);
+
+    // Nothing usable left
+    assert!(Tag::sanitize("").is_err());
+    assert!(Tag::sanitize(" & ").is_err());
+}
+
 #[test]
 fn test_tag_from_str() {
     let tag = Tag::from_str("groceries").unwrap();
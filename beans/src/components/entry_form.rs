@@ -1,12 +1,11 @@
 //! Entry form component for adding and editing ledger entries
 
+use beans_lib::models::currency::supported_currencies;
 use beans_lib::prelude::*;
-use chrono::TimeZone;
 use dioxus::prelude::*;
-use rust_decimal::Decimal;
-use std::str::FromStr;
 
 use crate::components::date_picker::DatePicker;
+use crate::state::AppState;
 
 /// EntryForm component for adding and editing ledger entries
 ///
@@ -15,7 +14,7 @@ use crate::components::date_picker::DatePicker;
 /// - Name input (required)
 /// - Type selector (Income/Expense radio buttons)
 /// - Amount input with validation
-/// - Currency code input (3-letter, auto-uppercase)
+/// - Currency selector populated from the library's supported currencies
 /// - Description textarea
 /// - Tag management (add/remove tags)
 #[component]
@@ -24,6 +23,8 @@ pub fn EntryForm(
     on_save: EventHandler<LedgerEntry>,
     on_cancel: EventHandler<()>,
 ) -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+
     // Form state
     let mut date = use_signal(|| {
         entry
@@ -32,7 +33,12 @@ pub fn EntryForm(
             .unwrap_or_else(|| Utc::now().format("%Y-%m-%d").to_string())
     });
     let have_entry = entry.is_some();
-    let mut name = use_signal(|| entry.as_ref().map(|e| e.name().to_string()).unwrap_or_default());
+    let mut name = use_signal(|| {
+        entry
+            .as_ref()
+            .map(|e| e.name().to_string())
+            .unwrap_or_default()
+    });
 
     let mut entry_type = use_signal(|| {
         entry
@@ -68,7 +74,7 @@ pub fn EntryForm(
         entry
             .as_ref()
             .map(|e| {
-                e.tags()
+                e.sorted_tags()
                     .iter()
                     .map(|t| t.name().to_string())
                     .collect::<Vec<_>>()
@@ -84,8 +90,13 @@ pub fn EntryForm(
         let tag_name = tag_input().trim().to_string();
         if !tag_name.is_empty() {
             // Validate tag format (only letters, numbers, hyphens, underscores)
-            if !tag_name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
-                error_message.set("Tags can only contain letters, numbers, hyphens, and underscores".to_string());
+            if !tag_name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+            {
+                error_message.set(
+                    "Tags can only contain letters, numbers, hyphens, and underscores".to_string(),
+                );
                 return;
             }
 
@@ -137,70 +148,32 @@ pub fn EntryForm(
             return;
         }
 
-        // Parse amount
-        let amount_decimal = match Decimal::from_str(&amount()) {
-            Ok(d) if d <= Decimal::ZERO => {
-                error_message.set("Amount must be positive".to_string());
-                return;
-            }
-            Ok(d) => d,
-            Err(_) => {
-                error_message.set("Invalid amount format".to_string());
-                return;
-            }
+        // Parse and validate the fields the same way every frontend does
+        let data = EntryData {
+            id: entry.as_ref().map(|existing| existing.id().to_string()),
+            date: date(),
+            name: name(),
+            currency_code: currency_code(),
+            amount: amount(),
+            description: Some(description()),
+            entry_type: entry_type().to_string(),
+            tags: tags(),
         };
 
-        // Parse date
-        let date_time = match chrono::NaiveDate::parse_from_str(&date(), "%Y-%m-%d") {
-            Ok(d) => {
-                let naive_datetime = d.and_hms_opt(0, 0, 0).unwrap();
-                Utc.from_utc_datetime(&naive_datetime)
-            }
-            Err(_) => {
-                error_message.set("Invalid date format".to_string());
-                return;
-            }
+        // Date-only values get the open ledger's default time of day, and
+        // edits keep the existing entry's ID, creation time and time of day
+        let parsed = match (&app_state.read().ledger_manager, &entry) {
+            (Some(manager), Some(existing)) => manager.entry_from_data_for_edit(existing, data),
+            (Some(manager), None) => manager.entry_from_data(data),
+            (None, _) => LedgerEntry::try_from(data),
         };
 
-        // Create tags
-        let tag_objects = match tags()
-            .iter()
-            .map(|t| Tag::new(t))
-            .collect::<Result<Vec<_>, _>>()
-        {
-            Ok(tags) => tags,
-            Err(e) => {
-                error_message.set(format!("Invalid tag: {}", e));
-                return;
-            }
-        };
-
-        // Build the entry
-        let mut builder = LedgerEntryBuilder::new()
-            .name(name())
-            .amount(amount_decimal)
-            .currency_code(currency_code().to_uppercase())
-            .entry_type(entry_type())
-            .date(date_time)
-            .tags(tag_objects);
-
-        // Add description if present
-        if !description().trim().is_empty() {
-            builder = builder.description(description());
-        }
-
-        // If editing an existing entry, preserve its ID and creation timestamp
-        if let Some(existing) = &entry {
-            builder = builder.id(existing.id()).created_at(existing.created_at());
-        }
-
-        // Build the entry
-        match builder.build() {
+        match parsed {
             Ok(entry) => {
                 on_save.call(entry);
             }
             Err(e) => {
-                error_message.set(format!("Error: {}", e));
+                error_message.set(e.to_string());
             }
         }
     };
@@ -312,15 +285,16 @@ pub fn EntryForm(
                         class: "form-label",
                         "Currency Code *"
                     }
-                    input {
+                    select {
                         class: "form-input",
-                        r#type: "text",
                         value: "{currency_code}",
-                        placeholder: "USD",
-                        maxlength: "3",
-                        oninput: move |evt| {
-                            let value = evt.value().clone().to_uppercase();
-                            currency_code.set(value);
+                        onchange: move |evt| currency_code.set(evt.value().clone()),
+                        for code in supported_currencies() {
+                            option {
+                                value: "{code}",
+                                selected: currency_code() == *code,
+                                "{code}"
+                            }
                         }
                     }
                 }
@@ -1,4 +1,4 @@
-//! Filter panel component for date and tag filtering
+//! Filter panel component for date, name and tag filtering
 
 use crate::state::AppState;
 use chrono::{DateTime, NaiveDate, TimeZone, Utc};
@@ -11,6 +11,7 @@ pub fn FilterPanel(on_apply: EventHandler<()>) -> Element {
     let mut start_date = use_signal(|| String::new());
     let mut end_date = use_signal(|| String::new());
     let mut tag_input = use_signal(|| String::new());
+    let mut name_input = use_signal(|| String::new());
 
     // Initialize with current filter values
     use_effect(move || {
@@ -23,6 +24,10 @@ pub fn FilterPanel(on_apply: EventHandler<()>) -> Element {
         if let Some(end) = filter.date_range.end {
             end_date.set(end.format("%Y-%m-%d").to_string());
         }
+
+        if let Some(name) = &filter.name_contains {
+            name_input.set(name.clone());
+        }
     });
 
     let apply_filter = move |_| {
@@ -48,9 +53,13 @@ pub fn FilterPanel(on_apply: EventHandler<()>) -> Element {
             state.filter.date_range.end = None;
         }
 
+        // Name keyword
+        let name = name_input().trim().to_string();
+        state.filter.name_contains = if name.is_empty() { None } else { Some(name) };
+
         // Reload entries with new filter
         if let Err(e) = state.load_entries() {
-            state.set_error(format!("Failed to load entries: {}", e));
+            state.set_beans_error("Failed to load entries", &e);
         }
 
         drop(state);
@@ -78,14 +87,16 @@ pub fn FilterPanel(on_apply: EventHandler<()>) -> Element {
         start_date.set(String::new());
         end_date.set(String::new());
         tag_input.set(String::new());
+        name_input.set(String::new());
 
         let mut state = app_state.write();
         state.filter.date_range.start = None;
         state.filter.date_range.end = None;
         state.filter.tags.clear();
+        state.filter.name_contains = None;
 
         if let Err(e) = state.load_entries() {
-            state.set_error(format!("Failed to load entries: {}", e));
+            state.set_beans_error("Failed to load entries", &e);
         }
 
         drop(state);
@@ -129,6 +140,26 @@ pub fn FilterPanel(on_apply: EventHandler<()>) -> Element {
                 }
             }
 
+            // Name filter
+            div {
+                class: "filter-section",
+
+                div {
+                    class: "filter-row",
+
+                    div {
+                        class: "filter-field",
+                        label { "Name contains:" }
+                        input {
+                            r#type: "text",
+                            value: "{name_input}",
+                            placeholder: "e.g. Rent",
+                            oninput: move |evt| name_input.set(evt.value().clone())
+                        }
+                    }
+                }
+            }
+
             // Tag filters
             div {
                 class: "filter-section",
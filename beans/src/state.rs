@@ -1,8 +1,23 @@
 //! Application state management
 
 use beans_lib::prelude::*;
-use chrono::{DateTime, Utc};
-use std::path::PathBuf;
+use chrono::{DateTime, NaiveTime, Utc};
+use std::path::{Path, PathBuf};
+
+/// Error kind used when an operation requires an open ledger.
+pub const ERROR_KIND_NO_LEDGER: &str = "NoLedger";
+
+/// Error kind used for errors raised by the UI itself.
+pub const ERROR_KIND_OTHER: &str = "Other";
+
+/// Opens a ledger with the settings the UI relies on.
+///
+/// The date picker only yields dates, so entries are placed at noon UTC to
+/// keep them on the picked day in every nearby timezone.
+fn open_manager(path: &Path) -> BeansResult<LedgerManager> {
+    let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+    Ok(LedgerManager::open(path)?.with_default_time_of_day(noon))
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum View {
@@ -32,6 +47,7 @@ impl Default for DateRange {
 pub struct Filter {
     pub date_range: DateRange,
     pub tags: Vec<String>,
+    pub name_contains: Option<String>,
 }
 
 impl Default for Filter {
@@ -39,6 +55,7 @@ impl Default for Filter {
         Self {
             date_range: DateRange::default(),
             tags: Vec::new(),
+            name_contains: None,
         }
     }
 }
@@ -51,6 +68,8 @@ pub struct AppState {
     pub filter: Filter,
     pub selected_entry: Option<Uuid>,
     pub error_message: Option<String>,
+    /// Stable kind of the current error, so views can branch on it.
+    pub error_kind: Option<&'static str>,
     pub success_message: Option<String>,
 }
 
@@ -64,6 +83,7 @@ impl AppState {
             filter: Filter::default(),
             selected_entry: None,
             error_message: None,
+            error_kind: None,
             success_message: None,
         }
     }
@@ -75,6 +95,21 @@ impl AppState {
 
     pub fn set_error(&mut self, message: String) {
         self.error_message = Some(message);
+        self.error_kind = Some(ERROR_KIND_OTHER);
+        self.success_message = None;
+    }
+
+    /// Records a library error, keeping its kind alongside the message.
+    pub fn set_beans_error(&mut self, context: &str, error: &BeansError) {
+        self.error_message = Some(format!("{}: {}", context, error));
+        self.error_kind = Some(error.kind());
+        self.success_message = None;
+    }
+
+    /// Records that an operation needed an open ledger but none was open.
+    pub fn set_no_ledger_error(&mut self) {
+        self.error_message = Some("No ledger is open".to_string());
+        self.error_kind = Some(ERROR_KIND_NO_LEDGER);
         self.success_message = None;
     }
 
@@ -85,6 +120,7 @@ impl AppState {
 
     pub fn clear_messages(&mut self) {
         self.error_message = None;
+        self.error_kind = None;
         self.success_message = None;
     }
 
@@ -100,6 +136,8 @@ impl AppState {
                 filter_obj.end_date = Some(end)
             }
 
+            filter_obj.name_contains = self.filter.name_contains.clone();
+
             for tag in &self.filter.tags {
                 filter_obj.tags.push(tag.clone());
             }
@@ -110,7 +148,7 @@ impl AppState {
     }
 
     pub fn open_ledger(&mut self, path: PathBuf) -> BeansResult<()> {
-        let manager = LedgerManager::open(path.clone())?;
+        let manager = open_manager(&path)?;
         self.ledger_manager = Some(manager);
         self.ledger_path = Some(path);
         self.load_entries()?;
@@ -119,7 +157,7 @@ impl AppState {
     }
 
     pub fn create_ledger(&mut self, path: PathBuf) -> BeansResult<()> {
-        let manager = LedgerManager::open(path.clone())?;
+        let manager = open_manager(&path)?;
         self.ledger_manager = Some(manager);
         self.ledger_path = Some(path);
         self.entries = Vec::new();
@@ -26,18 +26,18 @@ pub fn AddEntryView() -> Element {
 
                     // Reload entries
                     if let Err(e) = state.load_entries() {
-                        state.set_error(format!("Failed to reload entries: {}", e));
+                        state.set_beans_error("Failed to reload entries", &e);
                     }
 
                     // Navigate back to overview
                     state.set_view(View::Overview);
                 }
                 Err(e) => {
-                    state.set_error(format!("Failed to add entry: {}", e));
+                    state.set_beans_error("Failed to add entry", &e);
                 }
             }
         } else {
-            state.set_error("No ledger is open".to_string());
+            state.set_no_ledger_error();
         }
     };
 
@@ -60,7 +60,7 @@ pub fn EditEntryView() -> Element {
 
                     // Reload entries
                     if let Err(e) = state.load_entries() {
-                        state.set_error(format!("Failed to reload entries: {}", e));
+                        state.set_beans_error("Failed to reload entries", &e);
                     }
 
                     // Clear selection and go back to selection mode
@@ -69,11 +69,11 @@ pub fn EditEntryView() -> Element {
                     editing.set(false);
                 }
                 Err(e) => {
-                    state.set_error(format!("Failed to update entry: {}", e));
+                    state.set_beans_error("Failed to update entry", &e);
                 }
             }
         } else {
-            state.set_error("No ledger is open".to_string());
+            state.set_no_ledger_error();
         }
     };
 
@@ -90,7 +90,7 @@ pub fn EditEntryView() -> Element {
 
                     // Reload entries
                     if let Err(e) = state.load_entries() {
-                        state.set_error(format!("Failed to reload entries: {}", e));
+                        state.set_beans_error("Failed to reload entries", &e);
                     }
 
                     // Clear selection and go back to selection mode
@@ -99,11 +99,11 @@ pub fn EditEntryView() -> Element {
                     editing.set(false);
                 }
                 Err(e) => {
-                    state.set_error(format!("Failed to delete entry: {}", e));
+                    state.set_beans_error("Failed to delete entry", &e);
                 }
             }
         } else {
-            state.set_error("No ledger is open".to_string());
+            state.set_no_ledger_error();
         }
     };
 
@@ -241,6 +241,8 @@ pub fn EditEntryView() -> Element {
                                         th { "Amount" }
                                         th { "Currency" }
                                         th { "Tags" }
+                                        th { "Added" }
+                                        th { "Last Changed" }
                                         th { "Actions" }
                                     }
                                 }
@@ -260,10 +262,12 @@ pub fn EditEntryView() -> Element {
                                             td { "{entry.currency_code()}" }
                                             td {
                                                 class: "tag-cell",
-                                                for tag in entry.tags() {
+                                                for tag in entry.sorted_tags() {
                                                     span { class: "tag-pill", "{tag.name()}" }
                                                 }
                                             }
+                                            td { "{entry.created_at().format(\"%Y-%m-%d %H:%M\")}" }
+                                            td { "{entry.updated_at().format(\"%Y-%m-%d %H:%M\")}" }
                                             td {
                                                 div {
                                                     class: "action-buttons",
@@ -19,7 +19,7 @@ pub fn ExportView() -> Element {
     let mut app_state = use_context::<Signal<AppState>>();
 
     // Local state
-    let mut format = use_signal(|| "json".to_string());
+    let mut format = use_signal(|| ExportFormat::Json);
     let mut export_path = use_signal(|| {
         app_state
             .read()
@@ -27,7 +27,7 @@ pub fn ExportView() -> Element {
             .as_ref()
             .map(|p| {
                 let mut path = p.clone();
-                path.set_extension(format());
+                path.set_extension(format().extension());
                 path.to_string_lossy().to_string()
             })
             .unwrap_or_default()
@@ -43,13 +43,13 @@ pub fn ExportView() -> Element {
     };
 
     // Handle format change
-    let mut on_format_change = move |new_format: String| {
-        format.set(new_format.clone());
+    let mut on_format_change = move |new_format: ExportFormat| {
+        format.set(new_format);
 
         // Update export path extension
         if let Some(path) = &app_state.read().ledger_path {
             let mut new_path = path.clone();
-            new_path.set_extension(new_format);
+            new_path.set_extension(new_format.extension());
             export_path.set(new_path.to_string_lossy().to_string());
         }
 
@@ -73,7 +73,7 @@ pub fn ExportView() -> Element {
 
                 if state.ledger_manager.is_none() {
                     drop(state);
-                    app_state.write().set_error("No ledger is open".to_string());
+                    app_state.write().set_no_ledger_error();
                     return;
                 }
 
@@ -96,8 +96,11 @@ pub fn ExportView() -> Element {
 
                 // Get the start and end dates from the filter
                 let start_date = filter.start_date.unwrap_or_else(|| {
-                    // Default to 30 days ago if no start date
-                    Utc::now() - chrono::Duration::days(30)
+                    // Default to the earliest entry, or 30 days ago for an empty ledger
+                    match manager.date_span() {
+                        Ok(Some((earliest, _))) => earliest,
+                        _ => Utc::now() - chrono::Duration::days(30),
+                    }
                 });
 
                 let end_date = filter.end_date.unwrap_or_else(Utc::now);
@@ -109,23 +112,14 @@ pub fn ExportView() -> Element {
                     Some(filter.tags.clone())
                 };
 
-                // Convert format string to ExportFormat
-                let export_format = match format().as_str() {
-                    "json" => ExportFormat::Json,
-                    "csv" => ExportFormat::Csv,
-                    _ => {
-                        drop(state);
-                        app_state.write().set_error("Unsupported export format".to_string());
-                        return;
-                    }
-                };
+                let export_format = format();
 
                 // Get entries (we need to do this while we have the manager reference)
                 let entries = match manager.list_entries(&filter) {
                     Ok(e) => e,
                     Err(err) => {
                         drop(state);
-                        app_state.write().set_error(format!("Failed to list entries: {}", err));
+                        app_state.write().set_beans_error("Failed to list entries", &err);
                         return;
                     }
                 };
@@ -140,15 +134,15 @@ pub fn ExportView() -> Element {
                 Ok(m) => {
                     // Add the cloned entries to the in-memory manager
                     for entry in &entries {
-                        if let Err(e) = m.add_entry(&(entry).clone()) {
-                            app_state.write().set_error(format!("Failed to prepare report data: {}", e));
+                        if let Err(e) = m.add_entry(entry) {
+                            app_state.write().set_beans_error("Failed to prepare report data", &e);
                             return;
                         }
                     }
                     m
                 },
                 Err(e) => {
-                    app_state.write().set_error(format!("Failed to create temporary ledger: {}", e));
+                    app_state.write().set_beans_error("Failed to create temporary ledger", &e);
                     return;
                 }
             };
@@ -156,24 +150,34 @@ pub fn ExportView() -> Element {
             // Create a ReportGenerator from the temporary LedgerManager
             let report_generator = ReportGenerator::new(&temp_manager);
 
-            // Generate the report
-            let report = match report_generator.tagged_report(start_date, end_date, None).await {
+            // Generate the monthly report and its tag breakdown
+            let report = match report_generator
+                .income_expense_report(start_date, end_date, TimePeriod::Monthly, None, None, None)
+                .await
+            {
                 Ok(r) => r,
                 Err(e) => {
-                    app_state.write().set_error(format!("Failed to generate report: {}", e));
+                    app_state.write().set_beans_error("Failed to generate report", &e);
+                    return;
+                }
+            };
+            let by_tag = match report_generator.tagged_report(start_date, end_date, None).await {
+                Ok(r) => r,
+                Err(e) => {
+                    app_state.write().set_beans_error("Failed to generate report", &e);
                     return;
                 }
             };
 
-            // Export the report
-            match report_generator.export_tagged_report(&report, export_format) {
+            // Export both in one document
+            match report_generator.export_income_expense_report_with_tags(&report, &by_tag, export_format) {
                 Ok(content) => {
                     preview_content.set(content);
                     has_preview.set(true);
                     app_state.write().set_success("Report generated successfully".to_string());
                 }
                 Err(e) => {
-                    app_state.write().set_error(format!("Failed to export report: {}", e));
+                    app_state.write().set_beans_error("Failed to export report", &e);
                     has_preview.set(false);
                 }
             }
@@ -192,7 +196,17 @@ pub fn ExportView() -> Element {
             return;
         }
 
-        let path = PathBuf::from(export_path());
+        // Make sure the file extension matches the exported content
+        let mut path = PathBuf::from(export_path());
+        let matches_format = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(ExportFormat::from_extension)
+            == Some(format());
+        if !matches_format {
+            path.set_extension(format().extension());
+            export_path.set(path.to_string_lossy().to_string());
+        }
 
         // Save the file
         if let Err(e) = std::fs::write(&path, preview_content()) {
@@ -281,8 +295,8 @@ pub fn ExportView() -> Element {
                                     r#type: "radio",
                                     name: "export-format",
                                     value: "json",
-                                    checked: format() == "json",
-                                    oninput: move |_| on_format_change("json".to_string())
+                                    checked: format() == ExportFormat::Json,
+                                    oninput: move |_| on_format_change(ExportFormat::Json)
                                 }
                                 "JSON"
                             }
@@ -293,8 +307,8 @@ pub fn ExportView() -> Element {
                                     r#type: "radio",
                                     name: "export-format",
                                     value: "csv",
-                                    checked: format() == "csv",
-                                    oninput: move |_| on_format_change("csv".to_string())
+                                    checked: format() == ExportFormat::Csv,
+                                    oninput: move |_| on_format_change(ExportFormat::Csv)
                                 }
                                 "CSV"
                             }
@@ -49,7 +49,7 @@ pub fn LedgerSelectionView() -> Element {
             };
             match open_ledger {
                 Ok(m) => app_state.write().set_success(m),
-                Err(e) => app_state.write().set_error(e.to_string()),
+                Err(e) => app_state.write().set_beans_error("Failed to open ledger", &e),
             }
         }
     };
@@ -90,7 +90,7 @@ pub fn LedgerSelectionView() -> Element {
             };
             match create_ledger {
                 Ok(m) => app_state.write().set_success(m),
-                Err(e) => app_state.write().set_error(e.to_string()),
+                Err(e) => app_state.write().set_beans_error("Failed to create ledger", &e),
             }
         }
     };
@@ -204,7 +204,7 @@ pub fn OverviewView() -> Element {
                                             td { "{entry.currency_code()}" }
                                             td {
                                                 class: "tag-cell",
-                                                for tag in entry.tags() {
+                                                for tag in entry.sorted_tags() {
                                                     span { class: "tag-pill", "{tag.name()}" }
                                                 }
                                             }